@@ -0,0 +1,417 @@
+//! Derive macros for `mecs`
+//! 
+//! These are re-exported by `mecs` when it's `derive` feature is enabled,
+//! and shouldn't be depended on directly.
+
+// Warnings
+//--------------------------------------------------------------------------------------------------
+	// Use all warnings from clippy
+	#![warn(
+		clippy::all,
+		clippy::pedantic,
+		clippy::nursery,
+		clippy::cargo,
+	)]
+	
+	// Restriction
+	// Note: Clippy advises against enabling all of `clippy::restriction`, so we only enable the ones we follow
+	#![warn(
+		clippy::arithmetic_side_effects,
+		clippy::as_conversions,
+	)]
+	
+	// Style
+	#![allow(clippy::tabs_in_doc_comments)] // Our examples are indented with tabs, like the rest of our code
+//--------------------------------------------------------------------------------------------------
+
+
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Collections
+use std::collections::HashMap;
+
+// Convert
+use std::convert::TryFrom;
+
+// Proc macro
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, Span};
+use quote::{quote, ToTokens};
+use syn::{
+	Data, DeriveInput, Fields, GenericParam, Generics, Ident,
+	Lifetime, LifetimeParam, LitInt, Path, Type,
+};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A variant of a storage
+	struct Variant<'v>
+	{
+		/// The name of the variant
+		name: &'v Ident,
+		
+		/// The type of the component
+		ty: &'v Type,
+		
+		/// The id of the component
+		id: u64,
+		
+		/// If no other variant stores the same type
+		unique: bool,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Macros
+//--------------------------------------------------------------------------------------------------
+	/// Implements `Storage` for an enum and `Component` for the type within each of it's variants
+	/// 
+	/// Each variant must have a single unnamed field, the component.
+	/// Besides `Storage` and `Component`, this implements `From` for
+	/// each component into the storage, and `TryFrom` for each component
+	/// from the storage, which returns the storage back on failure.
+	/// 
+	/// # Duplicate types
+	/// If multiple variants store the same type, neither `Component`, `From`
+	/// nor `TryFrom` are implemented for it, as they'd be ambiguous. Instead,
+	/// the id of each of those variants is available as an associated constant
+	/// of the enum, named after the variant in `SCREAMING_SNAKE_CASE` with an
+	/// `_ID` suffix, to be used with the id-based methods, such as `Entity::get_id`.
+	/// 
+	/// # Crate path
+	/// The generated code refers to `mecs` through `::mecs`, which may be
+	/// overridden with a `#[storage(crate = path)]` attribute on the enum,
+	/// for example, when `mecs` is re-exported by another crate.
+	/// 
+	/// # Ids
+	/// The id of each component is a `u64`. Like with `impl_enum_storage`,
+	/// each variant may be given an explicit id with an `#[id = ...]`
+	/// attribute, or is otherwise given it's index within the enum.
+	/// The ids and names of all variants are available in the generated
+	/// `COMPONENTS` associated constant of the enum, and each variant's
+	/// index within the enum is used as it's index for component masks.
+	/// 
+	/// # Lifetimes
+	/// If the enum has any lifetime parameters, `Storage` is implemented for
+	/// it's first lifetime, otherwise it's implemented for all lifetimes.
+	/// 
+	/// # Example
+	/// 
+	/// ```rust
+	/// use mecs::{Storage, Component};
+	/// use std::convert::TryFrom;
+	/// 
+	/// #[derive(Storage, PartialEq, Debug)]
+	/// enum Components<'a> {
+	/// 	#[id = 1]
+	/// 	Name(&'a str),
+	/// 
+	/// 	#[id = 2]
+	/// 	Health(u32),
+	/// }
+	/// 
+	/// let storage = Components::from("Player");
+	/// assert_eq!(storage.id(), 1);
+	/// assert_eq!(<&str as Component<Components>>::get(&storage), Some(&"Player"));
+	/// assert_eq!(u32::try_from(storage), Err( Components::Name("Player") ));
+	/// ```
+	/// 
+	/// Variants storing the same type are only accessible by their id:
+	/// 
+	/// ```rust
+	/// use mecs::{Storage, Entity};
+	/// 
+	/// #[derive(Storage)]
+	/// enum Components {
+	/// 	Health(u32),
+	/// 	Mana(u32),
+	/// }
+	/// 
+	/// let entity: Entity<Components> = mecs::entity![ Components::Health(5), Components::Mana(2) ];
+	/// assert!(matches!(entity.get_id(&Components::MANA_ID), Some(Components::Mana(2))));
+	/// ```
+	/// 
+	/// ```rust,compile_fail
+	/// #[derive(mecs::Storage)]
+	/// enum Components {
+	/// 	Health(u32),
+	/// 	Mana(u32),
+	/// }
+	/// 
+	/// let _ = Components::from(5_u32);
+	/// ```
+	#[proc_macro_derive(Storage, attributes(id, storage))]
+	pub fn derive_storage(input: TokenStream) -> TokenStream
+	{
+		let input = syn::parse_macro_input!(input as DeriveInput);
+		expand(&input)
+			.unwrap_or_else(syn::Error::into_compile_error)
+			.into()
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Expands the `Storage` derive
+	fn expand(input: &DeriveInput) -> syn::Result<TokenStream2>
+	{
+		let variants = parse_variants(input)?;
+		let krate = parse_crate(input)?;
+		let lifetime = storage_lifetime(&input.generics);
+		let impl_generics = with_lifetime(&input.generics, &lifetime);
+		
+		let name = &input.ident;
+		let (impl_generics, _, _) = impl_generics.split_for_impl();
+		let (plain_impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+		let storage = quote! { #name #ty_generics };
+		
+		let names: Vec<_> = variants.iter().map(|variant| variant.name).collect();
+		let ids   : Vec<_> = variants.iter().map(|variant| variant.id  ).collect();
+		let indices = 0..variants.len();
+		let count = variants.len();
+		let name_strs = names.iter().map(ToString::to_string);
+		
+		let components = variants.iter().filter(|variant| variant.unique).map(|Variant { name: variant_name, ty, id, .. }| quote! {
+			impl #impl_generics #krate::Component<#lifetime, #storage> for #ty #where_clause
+			{
+				fn id() -> <#storage as #krate::Storage<#lifetime>>::Id {
+					#id
+				}
+				
+				fn get<'__borrow>(storage: &'__borrow #storage) -> ::core::option::Option<&'__borrow Self> {
+					match storage {
+						#name::#variant_name(component) => ::core::option::Option::Some(component),
+						#[allow(unreachable_patterns)]
+						_ => ::core::option::Option::None,
+					}
+				}
+				
+				fn get_mut<'__borrow>(storage: &'__borrow mut #storage) -> ::core::option::Option<&'__borrow mut Self> {
+					match storage {
+						#name::#variant_name(component) => ::core::option::Option::Some(component),
+						#[allow(unreachable_patterns)]
+						_ => ::core::option::Option::None,
+					}
+				}
+			}
+			
+			impl #plain_impl_generics ::core::convert::From<#ty> for #storage #where_clause
+			{
+				fn from(component: #ty) -> Self {
+					#name::#variant_name(component)
+				}
+			}
+			
+			impl #plain_impl_generics ::core::convert::TryFrom<#storage> for #ty #where_clause
+			{
+				type Error = #storage;
+				
+				fn try_from(storage: #storage) -> ::core::result::Result<Self, Self::Error> {
+					match storage {
+						#name::#variant_name(component) => ::core::result::Result::Ok(component),
+						#[allow(unreachable_patterns)]
+						storage => ::core::result::Result::Err(storage),
+					}
+				}
+			}
+		});
+		
+		// Note: Variants whose type isn't unique are only accessible by their id
+		let duplicates = variants.iter().filter(|variant| !variant.unique).map(|Variant { name: variant_name, id, .. }| {
+			let const_name = Ident::new(&format!("{}_ID", screaming_snake_case(&variant_name.to_string())), variant_name.span());
+			let doc = format!("The id of `{variant_name}`");
+			quote! {
+				#[doc = #doc]
+				#[allow(dead_code)] // It's only used if the variant is accessed
+				pub const #const_name: u64 = #id;
+			}
+		});
+		
+		Ok(quote! {
+			impl #impl_generics #krate::Storage<#lifetime> for #storage #where_clause
+			{
+				type Id = u64;
+				
+				const COUNT: ::core::option::Option<usize> = ::core::option::Option::Some(#count);
+				
+				fn id(&self) -> Self::Id {
+					match *self {
+						#( #name::#names(_) => #ids, )*
+					}
+				}
+				
+				fn index(id: &Self::Id) -> ::core::option::Option<usize> {
+					match *id {
+						#( #ids => ::core::option::Option::Some(#indices), )*
+						_ => ::core::option::Option::None,
+					}
+				}
+			}
+			
+			impl #plain_impl_generics #storage #where_clause
+			{
+				/// The id and name of every component, in the order they were declared
+				#[allow(dead_code)] // It's only used by tooling
+				pub const COMPONENTS: &'static [(u64, &'static str)] = &[ #( (#ids, #name_strs), )* ];
+				
+				#( #duplicates )*
+			}
+			
+			#( #components )*
+		})
+	}
+	
+	/// Parses all variants of the enum
+	/// 
+	/// All errors found are returned together.
+	fn parse_variants(input: &DeriveInput) -> syn::Result< Vec<Variant<'_>> >
+	{
+		let data = match &input.data {
+			Data::Enum(data) => data,
+			Data::Struct(data) => return Err( syn::Error::new(data.struct_token.span, "`Storage` can only be derived for enums") ),
+			Data::Union (data) => return Err( syn::Error::new(data.union_token .span, "`Storage` can only be derived for enums") ),
+		};
+		
+		let mut errors: Option<syn::Error> = None;
+		let mut push_error = |error: syn::Error| match &mut errors {
+			Some(errors) => errors.combine(error),
+			None         => errors = Some(error),
+		};
+		
+		let mut variants = vec![];
+		let mut by_id  : HashMap<u64   , &Ident> = HashMap::new();
+		let mut by_type: HashMap<String, bool  > = HashMap::new();
+		for (idx, variant) in data.variants.iter().enumerate()
+		{
+			// Get the component within the variant
+			let ty = match &variant.fields {
+				Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+				_ => {
+					push_error( syn::Error::new_spanned(variant, "Variants of a storage must have a single unnamed field, the component") );
+					continue;
+				}
+			};
+			
+			// Then get it's id, or use the index
+			let id = match parse_id(variant) {
+				Ok(id) => id.unwrap_or_else(|| u64::try_from(idx).expect("Index didn't fit into a `u64`")),
+				Err(err) => {
+					push_error(err);
+					continue;
+				}
+			};
+			
+			// And check that the id wasn't already used
+			if let Some(other) = by_id.insert(id, &variant.ident) {
+				push_error( syn::Error::new_spanned(&variant.ident, format!("Component id {id} is already used by variant `{other}`")) );
+			}
+			if is_type_param(ty, &input.generics) {
+				push_error( syn::Error::new_spanned(ty, "Components can't be a generic parameter, as `Component` can't be implemented for it") );
+			}
+			
+			// Note: Types may be stored by multiple variants, we just need to know which
+			by_type.entry( ty.to_token_stream().to_string() )
+				.and_modify(|unique| *unique = false)
+				.or_insert(true);
+			variants.push( Variant { name: &variant.ident, ty, id, unique: true } );
+		}
+		
+		for variant in &mut variants {
+			variant.unique = by_type[&variant.ty.to_token_stream().to_string()];
+		}
+		
+		errors.map_or(Ok(variants), Err)
+	}
+	
+	/// Parses the path to `mecs` from the `#[storage(crate = ...)]` attribute, or `::mecs` if there isn't one
+	fn parse_crate(input: &DeriveInput) -> syn::Result<Path>
+	{
+		let mut krate = None;
+		for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("storage")) {
+			attr.parse_nested_meta(|meta| {
+				if !meta.path.is_ident("crate") {
+					return Err( meta.error("Unknown storage attribute, expected `crate`") );
+				}
+				if krate.is_some() {
+					return Err( meta.error("The crate path was already specified") );
+				}
+				
+				krate = Some( meta.value()?.parse()? );
+				Ok(())
+			})?;
+		}
+		
+		Ok( krate.unwrap_or_else(|| syn::parse_quote!(::mecs)) )
+	}
+	
+	/// Converts a `CamelCase` name into `SCREAMING_SNAKE_CASE`
+	fn screaming_snake_case(name: &str) -> String
+	{
+		let mut output = String::with_capacity( name.len() );
+		let mut prev = None;
+		for ch in name.chars() {
+			if ch.is_uppercase() && prev.is_some_and(|prev: char| prev.is_lowercase() || prev.is_ascii_digit()) {
+				output.push('_');
+			}
+			output.extend( ch.to_uppercase() );
+			prev = Some(ch);
+		}
+		
+		output
+	}
+	
+	/// Parses the explicit id of a variant, if it has one
+	fn parse_id(variant: &syn::Variant) -> syn::Result< Option<u64> >
+	{
+		let mut id = None;
+		for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("id")) {
+			if id.is_some() {
+				return Err( syn::Error::new_spanned(attr, "Variant has more than one `#[id]` attribute") );
+			}
+			
+			let value = &attr.meta.require_name_value()?.value;
+			let lit: LitInt = syn::parse2( value.to_token_stream() )
+				.map_err(|_| syn::Error::new_spanned(value, "Expected an integer literal for the id"))?;
+			id = Some( lit.base10_parse()? );
+		}
+		
+		Ok(id)
+	}
+	
+	/// Checks if a type is one of the type parameters of the enum
+	fn is_type_param(ty: &Type, generics: &Generics) -> bool
+	{
+		let ident = match ty {
+			Type::Path(path) if path.qself.is_none() => match path.path.get_ident() {
+				Some(ident) => ident,
+				None        => return false,
+			},
+			_ => return false,
+		};
+		
+		generics.type_params().any(|param| &param.ident == ident)
+	}
+	
+	/// Returns the lifetime to implement `Storage` for
+	/// 
+	/// This is the first lifetime of the enum, or a new lifetime if it has none.
+	fn storage_lifetime(generics: &Generics) -> Lifetime
+	{
+		generics.lifetimes()
+			.next()
+			.map_or_else(|| Lifetime::new("'__storage", Span::call_site()), |param| param.lifetime.clone())
+	}
+	
+	/// Returns the generics of the enum along with the storage lifetime
+	fn with_lifetime(generics: &Generics, lifetime: &Lifetime) -> Generics
+	{
+		let mut generics = generics.clone();
+		if !generics.lifetimes().any(|param| &param.lifetime == lifetime) {
+			generics.params.insert(0, GenericParam::Lifetime( LifetimeParam::new( lifetime.clone() ) ));
+		}
+		
+		generics
+	}
+//--------------------------------------------------------------------------------------------------
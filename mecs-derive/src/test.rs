@@ -0,0 +1,146 @@
+//! Tests
+
+// Convert
+use std::convert::TryFrom;
+
+// Crate
+use mecs::{World, Storage, Component};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// Storage without any generics
+	#[derive(Storage, PartialEq, Debug)]
+	enum Components {
+		/// Has an explicit id
+		#[id = 7]
+		A(i32),
+		
+		B(&'static str),
+		
+		#[id = 2]
+		C(f32),
+	}
+	
+	/// Storage with a lifetime, a type parameter and a where clause
+	#[derive(Storage, PartialEq, Debug)]
+	enum GenericComponents<'a, T>
+	where
+		T: Clone
+	{
+		Name(&'a str),
+		List(Vec<T>),
+	}
+	
+	/// Storage with multiple variants of the same type
+	#[derive(Storage, PartialEq, Debug)]
+	enum DuplicateComponents {
+		Health(u32),
+		MaxHealth(u32),
+		
+		#[id = 5]
+		Name(&'static str),
+	}
+	
+	/// Re-export of `mecs` under another path
+	mod reexport {
+		pub use ::mecs as inner;
+	}
+	
+	/// Storage referring to `mecs` through another path
+	#[derive(Storage, PartialEq, Debug)]
+	#[storage(crate = reexport::inner)]
+	enum ReexportComponents {
+		A(i32),
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn ids()
+	{
+		assert_eq!(<i32          as Component<Components>>::id(), 7);
+		assert_eq!(<&'static str as Component<Components>>::id(), 1);
+		assert_eq!(<f32          as Component<Components>>::id(), 2);
+		
+		assert_eq!(Components::A(5).id(), 7);
+		assert_eq!(Components::B("Hello, World!").id(), 1);
+		assert_eq!(Components::C(4.5).id(), 2);
+		
+		assert_eq!(Components::COMPONENTS, &[(7, "A"), (1, "B"), (2, "C")]);
+		
+		assert_eq!(<Components as Storage>::COUNT, Some(3));
+		assert_eq!(Components::index(&7), Some(0));
+		assert_eq!(Components::index(&2), Some(2));
+		assert_eq!(Components::index(&0), None);
+	}
+	
+	#[test]
+	fn get()
+	{
+		let mut a = Components::A(5);
+		let b = Components::B("Hello, World!");
+		
+		assert_eq!(i32::get(&a), Some(&5));
+		assert_eq!(i32::get(&b), None    );
+		assert_eq!(<&str>::get(&b), Some(&"Hello, World!"));
+		
+		*i32::get_mut(&mut a).unwrap() = 8;
+		assert_eq!(a, Components::A(8));
+	}
+	
+	#[test]
+	fn convert()
+	{
+		assert_eq!(Components::from(5), Components::A(5));
+		assert_eq!(Components::from(4.5), Components::C(4.5));
+		
+		assert_eq!(i32::try_from( Components::A(5) ), Ok(5));
+		assert_eq!(i32::try_from( Components::C(4.5) ), Err( Components::C(4.5) ));
+	}
+	
+	#[test]
+	fn generics()
+	{
+		let name: GenericComponents<u32> = GenericComponents::from("Player");
+		let list: GenericComponents<u32> = GenericComponents::from( vec![1, 2] );
+		
+		assert_eq!(name.id(), 0);
+		assert_eq!(list.id(), 1);
+		assert_eq!(<Vec<u32>>::get(&list), Some(&vec![1, 2]));
+		assert_eq!(<Vec<u32>>::try_from(name), Err( GenericComponents::Name("Player") ));
+	}
+	
+	#[test]
+	fn duplicates()
+	{
+		assert_eq!(DuplicateComponents::HEALTH_ID, 0);
+		assert_eq!(DuplicateComponents::MAX_HEALTH_ID, 1);
+		assert_eq!(<&str as Component<DuplicateComponents>>::id(), 5);
+		assert_eq!(DuplicateComponents::index(&DuplicateComponents::MAX_HEALTH_ID), Some(1));
+		
+		let mut world: World<DuplicateComponents> = World::new();
+		let id = world.add( mecs::entity![ DuplicateComponents::Health(5), DuplicateComponents::MaxHealth(10) ] );
+		assert_eq!(world[id].get_id(&DuplicateComponents::HEALTH_ID), Some(&DuplicateComponents::Health(5)));
+		assert_eq!(world[id].get_id(&DuplicateComponents::MAX_HEALTH_ID), Some(&DuplicateComponents::MaxHealth(10)));
+		assert!(!world[id].has::<&str>());
+	}
+	
+	#[test]
+	fn crate_path()
+	{
+		assert_eq!(ReexportComponents::from(5).id(), 0);
+		assert_eq!(<i32 as Component<ReexportComponents>>::get( &ReexportComponents::A(5) ), Some(&5));
+	}
+	
+	#[test]
+	fn world()
+	{
+		let mut world: World<Components> = World::new();
+		let id = world.add( mecs::entity![ Components::from(5), Components::from("Player") ] );
+		
+		assert_eq!(world[id].get::<i32>(), Some(&5));
+		assert_eq!(world[id].get::<&str>(), Some(&"Player"));
+		assert!(!world[id].has::<f32>());
+	}
+//--------------------------------------------------------------------------------------------------
@@ -5,7 +5,7 @@
 //! the [`Storage`] trait.
 //! All types that can live within a storage must implement
 //! [`Component`] of that storage.
-//!
+//! 
 //! # Example
 //! 
 //! ```rust
@@ -19,6 +19,12 @@
 //! }
 //! ```
 //! 
+//! # Derive
+//! 
+//! With the `derive` feature enabled, `#[derive(mecs::Storage)]` implements
+//! both traits for an enum, like [`impl_enum_storage`](crate::impl_enum_storage),
+//! but also supports generics, lifetimes and where clauses.
+//! 
 //! # Manual Implementation
 //! 
 //! ```rust
@@ -72,9 +78,13 @@
     mod test;
     mod impl_macro;
 pub mod dyn_storage;
+pub mod type_registry;
+pub mod mask;
 
 // Exports
-pub use dyn_storage::DynStorage;
+pub use dyn_storage  ::DynStorage;
+pub use type_registry::TypeRegistry;
+pub use mask         ::ComponentMask;
 
 // Traits
 //--------------------------------------------------------------------------------------------------
@@ -85,10 +95,27 @@ pub use dyn_storage::DynStorage;
 		/// within this storage.
 		type Id;
 		
+		/// The number of components within this storage, if
+		/// their ids may be mapped to indices, see [`Storage::index`].
+		/// 
+		/// When set, each entity keeps a [`ComponentMask`] of it's
+		/// components, which is used to check for components and to
+		/// match declarative filters without going through it's components.
+		const COUNT: Option<usize> = None;
+		
 		/// Returns the id of the current component within this
 		/// storage.
 		#[must_use]
 		fn id(&self) -> Self::Id;
+		
+		/// Returns the index of a component id, below [`Storage::COUNT`]
+		/// 
+		/// Distinct ids must have distinct indices.
+		/// Returns `None` if the id doesn't have an index.
+		#[must_use]
+		fn index(_id: &Self::Id) -> Option<usize> {
+			None
+		}
 	}
 	
 	/// Trait implemented by all types within a storage.
@@ -5,21 +5,37 @@
 //! that may be stored in an [Any](std::any::Any) with static lifetimes
 
 // Traits
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 // Any
 use std::any::{Any, TypeId};
 
 // Crate
 use crate::{Storage, Component};
+use super::type_registry::{TypeRegistry, Registration};
 
 // Types
 //--------------------------------------------------------------------------------------------------
 	/// Dynamic storage based on [`TypeId`](std::any::TypeId)
 	/// for types with `'static` lifetime
+	/// 
+	/// # Registry
+	/// Cloning, comparing, formatting and serializing a dynamic storage
+	/// requires the type of it's component to be registered in the global
+	/// type registry, see [`TypeRegistry`]. Use [`DynStorage::try_clone`]
+	/// and [`DynStorage::try_eq`] to check for unregistered types.
 	#[allow(clippy::module_name_repetitions)]
-	#[derive(Debug)]
-	pub struct DynStorage(TypeId, Box<dyn Any>);
+	pub struct DynStorage
+	{
+		/// The type id of the component
+		id: TypeId,
+		
+		/// The name of the type of the component
+		type_name: &'static str,
+		
+		/// The component
+		value: Box<dyn Any>,
+	}
 //--------------------------------------------------------------------------------------------------
 
 // Impl
@@ -33,16 +49,61 @@ use crate::{Storage, Component};
 			where
 				T: Component<'static, Self> + 'static
 			{
-				Self(T::id(), Box::new(value))
+				Self { id: T::id(), type_name: std::any::type_name::<T>(), value: Box::new(value) }
 			}
 		//--------------------------------------------------------------------------------------------------
 		
 		// Component
 		//--------------------------------------------------------------------------------------------------
+			/// Returns the name of the type of the component, as given by [`std::any::type_name`]
+			#[must_use]
+			pub const fn type_name(&self) -> &'static str {
+				self.type_name
+			}
+			
 			/// Consumes the storage and returns the object within
 			#[must_use]
 			pub fn into_inner(self) -> (TypeId, Box<dyn Any>) {
-				(self.0, self.1)
+				(self.id, self.value)
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Registry
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the registration of the type of the component, if it's registered
+			#[must_use]
+			pub fn registration(&self) -> Option<Registration>
+			{
+				TypeRegistry::global()
+					.read()
+					.unwrap_or_else(std::sync::PoisonError::into_inner)
+					.get(self.id)
+					.copied()
+			}
+			
+			/// Clones the component through the type registry
+			/// 
+			/// # Return value
+			/// Returns `None` if the type of the component isn't registered.
+			#[must_use]
+			pub fn try_clone(&self) -> Option<Self>
+			{
+				let value = self.registration()?.clone_value(&*self.value);
+				Some( Self { id: self.id, type_name: self.type_name, value } )
+			}
+			
+			/// Compares the components through the type registry
+			/// 
+			/// # Return value
+			/// Returns `None` if both components have the same type and it isn't registered.
+			#[must_use]
+			pub fn try_eq(&self, other: &Self) -> Option<bool>
+			{
+				if self.id != other.id {
+					return Some(false);
+				}
+				
+				Some( self.registration()?.eq_values(&*self.value, &*other.value) )
 			}
 		//--------------------------------------------------------------------------------------------------
 	}
@@ -51,9 +112,8 @@ use crate::{Storage, Component};
 	{
 		type Id = TypeId;
 		
-		#[must_use]
 		fn id(&self) -> Self::Id {
-			self.0
+			self.id
 		}
 	}
 	
@@ -61,19 +121,143 @@ use crate::{Storage, Component};
 	where
 		T: Debug + 'static
 	{
-		#[must_use]
 		fn id() -> TypeId {
 			TypeId::of::<Self>()
 		}
 		
-		#[must_use]
 		fn get(storage: &DynStorage) -> Option<&Self> {
-			storage.1.downcast_ref()
+			storage.value.downcast_ref()
 		}
 		
-		#[must_use]
 		fn get_mut(storage: &mut DynStorage) -> Option<&mut Self> {
-			storage.1.downcast_mut()
+			storage.value.downcast_mut()
+		}
+	}
+	
+	impl Clone for DynStorage
+	{
+		/// Clones the component through the type registry
+		/// 
+		/// # Panics
+		/// Panics if the type of the component isn't registered.
+		fn clone(&self) -> Self {
+			self.try_clone()
+				.unwrap_or_else(|| panic!("Type `{}` isn't registered in the type registry", self.type_name))
+		}
+	}
+	
+	impl PartialEq for DynStorage
+	{
+		/// Compares the components through the type registry
+		/// 
+		/// Components of unregistered types are never equal, not even to themselves.
+		fn eq(&self, other: &Self) -> bool {
+			self.try_eq(other).unwrap_or(false)
+		}
+	}
+	
+	impl Debug for DynStorage
+	{
+		fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+		{
+			/// Formats a component through it's registration
+			struct Value<'v>(&'v dyn Any, Registration);
+			
+			impl Debug for Value<'_> {
+				fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+					self.1.fmt_value(self.0, f)
+				}
+			}
+			
+			// If the type isn't registered, just show it's name
+			match self.registration() {
+				Some(registration) => f.debug_tuple("DynStorage").field( &Value(&*self.value, registration) ).finish(),
+				None               => f.debug_tuple("DynStorage").field( &self.type_name ).finish(),
+			}
+		}
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Serde
+//--------------------------------------------------------------------------------------------------
+	/// Serializes the component as a map from it's registered name to it's value
+	#[cfg(feature = "serde-serialize")]
+	impl serde::Serialize for DynStorage
+	{
+		fn serialize<SS>(&self, serializer: SS) -> Result<SS::Ok, SS::Error>
+		where
+			SS: serde::Serializer,
+		{
+			use serde::ser::{SerializeMap, Error};
+			
+			let registration = self.registration()
+				.ok_or_else(|| SS::Error::custom( format!("Type `{}` isn't registered in the type registry", self.type_name) ))?;
+			let value = registration.serialize_value(&*self.value)
+				.ok_or_else(|| SS::Error::custom( format!("Type `{}` isn't registered for serialization", self.type_name) ))?;
+			
+			let mut map = serializer.serialize_map( Some(1) )?;
+			map.serialize_entry(registration.name(), value)?;
+			map.end()
+		}
+	}
+	
+	#[cfg(feature = "serde-serialize")]
+	impl<'de> serde::Deserialize<'de> for DynStorage
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			use serde::de::{Visitor, MapAccess, DeserializeSeed, Error};
+			
+			/// Visitor for the map of a component
+			struct StorageVisitor;
+			
+			/// Deserializes a component given it's registration
+			struct ValueSeed(Registration);
+			
+			impl<'de> Visitor<'de> for StorageVisitor
+			{
+				type Value = DynStorage;
+				
+				fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+					f.write_str("a map from a registered type name to it's value")
+				}
+				
+				fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+				where
+					A: MapAccess<'de>,
+				{
+					let name: String = map.next_key()?
+						.ok_or_else(|| A::Error::invalid_length(0, &self))?;
+					let registration = TypeRegistry::global()
+						.read()
+						.unwrap_or_else(std::sync::PoisonError::into_inner)
+						.get_by_name(&name)
+						.copied()
+						.ok_or_else(|| A::Error::custom( format!("Unknown type name `{name}`") ))?;
+					
+					let value = map.next_value_seed( ValueSeed(registration) )?;
+					Ok( DynStorage { id: registration.id(), type_name: registration.type_name(), value } )
+				}
+			}
+			
+			impl<'de> DeserializeSeed<'de> for ValueSeed
+			{
+				type Value = Box<dyn Any>;
+				
+				fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+				where
+					D: serde::Deserializer<'de>,
+				{
+					let mut deserializer = <dyn erased_serde::Deserializer>::erase(deserializer);
+					self.0.deserialize_value(&mut deserializer)
+						.ok_or_else(|| D::Error::custom( format!("Type `{}` isn't registered for serialization", self.0.type_name()) ))?
+						.map_err(D::Error::custom)
+				}
+			}
+			
+			deserializer.deserialize_map(StorageVisitor)
 		}
 	}
 //--------------------------------------------------------------------------------------------------
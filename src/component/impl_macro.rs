@@ -3,90 +3,199 @@
 // Macros
 //--------------------------------------------------------------------------------------------------
 	/// Creates and implements [`Storage`] and [`Component`] for an enum with all possible component types
-	/// using an incremental `u64` for the id
+	/// using a `u64` for the id
+	/// 
+	/// # Ids
+	/// Each variant may be given an explicit id with an `#[id = ...]` attribute.
+	/// Variants without one are given their index within the enum, so reordering
+	/// or inserting variants changes their ids. Explicit ids should be used
+	/// whenever ids are persisted, such as when serializing.
+	/// 
+	/// Duplicate ids are detected at compile time.
+	/// 
+	/// The ids and names of all variants, in order, are available in the
+	/// generated `COMPONENTS` associated constant of the enum.
+	/// Each variant's index within the enum is used as it's index, see
+	/// [`Storage::index`](crate::Storage::index), so entities keep a mask of their components.
+	/// 
+	/// # Example
+	/// 
+	/// ```rust
+	/// # use mecs::{Component, Storage};
+	/// mecs::impl_enum_storage! {
+	/// 	enum Components {
+	/// 		/// The name of an entity
+	/// 		#[id = 7]
+	/// 		Name(&'static str),
+	/// 
+	/// 		#[id = 3]
+	/// 		Health(u32),
+	/// 	}
+	/// }
+	/// 
+	/// assert_eq!(<u32 as Component<Components>>::id(), 3);
+	/// assert_eq!(Components::Name("Player").id(), 7);
+	/// assert_eq!(Components::COMPONENTS, &[(7, "Name"), (3, "Health")]);
+	/// ```
+	/// 
+	/// ```rust,compile_fail
+	/// mecs::impl_enum_storage! {
+	/// 	enum Components {
+	/// 		#[id = 1] Name(&'static str),
+	/// 		#[id = 1] Health(u32),
+	/// 	}
+	/// }
+	/// ```
 	#[macro_export]
 	macro_rules! impl_enum_storage
 	{
-		// Component
+		// Variants
 		//--------------------------------------------------------------------------------------------------
-			// Tail
-			{@ComponentImpl
-				$cur_idx: expr,
-				$name   : ident,
-			} => {};
+			// Id attribute, after another id attribute
+			{@Variants
+				[ $($enum: tt)* ]
+				[ $cur_idx: expr ]
+				[ $($done: tt)* ]
+				[ [ $id: expr ] [ $($kept: tt)* ] [ #[id = $new_id: expr] $($attrs: tt)* ] $variant_name: ident ( $variant_type: ty ) ]
+				$($rest: tt)*
+			} =>
+			{
+				::core::compile_error!( ::core::concat!("Variant `", ::core::stringify!($variant_name), "` has more than one `#[id]` attribute") );
+			};
 			
-			// Main Branch
-			{@ComponentImpl
-				$cur_idx  : expr ,
-				$name     : ident,
-				
-				$variant_name: ident ($variant_type: ty),
+			// Id attribute
+			{@Variants
+				[ $($enum: tt)* ]
+				[ $cur_idx: expr ]
+				[ $($done: tt)* ]
+				[ [] [ $($kept: tt)* ] [ #[id = $id: expr] $($attrs: tt)* ] $variant_name: ident ( $variant_type: ty ) ]
+				$($rest: tt)*
+			} =>
+			{
+				$crate::impl_enum_storage!{@Variants
+					[ $($enum)* ]
+					[ $cur_idx ]
+					[ $($done)* ]
+					[ [ $id ] [ $($kept)* ] [ $($attrs)* ] $variant_name ( $variant_type ) ]
+					$($rest)*
+				}
+			};
+			
+			// Any other attribute, kept on the variant
+			{@Variants
+				[ $($enum: tt)* ]
+				[ $cur_idx: expr ]
+				[ $($done: tt)* ]
+				[ [ $($id: expr)? ] [ $($kept: tt)* ] [ #[ $($attr: tt)* ] $($attrs: tt)* ] $variant_name: ident ( $variant_type: ty ) ]
+				$($rest: tt)*
+			} =>
+			{
+				$crate::impl_enum_storage!{@Variants
+					[ $($enum)* ]
+					[ $cur_idx ]
+					[ $($done)* ]
+					[ [ $($id)? ] [ $($kept)* #[ $($attr)* ] ] [ $($attrs)* ] $variant_name ( $variant_type ) ]
+					$($rest)*
+				}
+			};
+			
+			// No more attributes, without an id, so use the current index
+			{@Variants
+				[ $($enum: tt)* ]
+				[ $cur_idx: expr ]
+				[ $($done: tt)* ]
+				[ [] [ $($kept: tt)* ] [] $variant_name: ident ( $variant_type: ty ) ]
+				$($rest: tt)*
+			} =>
+			{
+				$crate::impl_enum_storage!{@Variants
+					[ $($enum)* ]
+					[ $cur_idx + 1 ]
+					[ $($done)* [ [ $($kept)* ] $variant_name ( $variant_type ) [ $cur_idx ] [ $cur_idx ] ] ]
+					$($rest)*
+				}
+			};
+			
+			// No more attributes, with an id
+			{@Variants
+				[ $($enum: tt)* ]
+				[ $cur_idx: expr ]
+				[ $($done: tt)* ]
+				[ [ $id: expr ] [ $($kept: tt)* ] [] $variant_name: ident ( $variant_type: ty ) ]
+				$($rest: tt)*
+			} =>
+			{
+				$crate::impl_enum_storage!{@Variants
+					[ $($enum)* ]
+					[ $cur_idx + 1 ]
+					[ $($done)* [ [ $($kept)* ] $variant_name ( $variant_type ) [ $id ] [ $cur_idx ] ] ]
+					$($rest)*
+				}
+			};
+			
+			// No more variants
+			{@Variants
+				[ $( #[$enum_meta: meta] )* $vis: vis $name: ident ]
+				[ $cur_idx: expr ]
+				[ $( [ [ $( #[$variant_meta: meta] )* ] $variant_name: ident ( $variant_type: ty ) [ $id: expr ] [ $idx: expr ] ] )* ]
+			} =>
+			{
+				// Enum declaration
+				$( #[$enum_meta] )*
+				$vis enum $name
+				{
+					$(
+						$( #[$variant_meta] )*
+						$variant_name( $variant_type ),
+					)*
+				}
 				
-				$($variant_names: ident ($variant_types: ty),)*
+				$crate::impl_enum_storage!(@StorageImpl $name, $( $variant_name( $variant_type ) [ $id ] [ $idx ] ),*);
+				$( $crate::impl_enum_storage!(@ComponentImpl $name, $variant_name( $variant_type ) [ $id ]); )*
+			};
+		//--------------------------------------------------------------------------------------------------
+		
+		// Component
+		//--------------------------------------------------------------------------------------------------
+			{@ComponentImpl
+				$name: ident,
+				$variant_name: ident ( $variant_type: ty ) [ $id: expr ]
 			} =>
 			{
-				// Impl the head type with the current index
 				impl<'a> $crate::Component<'a, $name> for $variant_type {
-					#[must_use]
 					fn id() -> <$name as $crate::Storage<'a>>::Id {
-						$cur_idx
+						$id
 					}
 					
-					#[must_use]
 					fn get(storage: &$name) -> Option<&Self> {
 						if let $name::$variant_name(value) = storage { Some(value) }
 						else                                         { None        }
 					}
 					
-					#[must_use]
 					fn get_mut(storage: &mut $name) -> Option<&mut Self> {
 						if let $name::$variant_name(value) = storage { Some(value) }
 						else                                         { None        }
 					}
 				}
-				
-				// Implement all types within the tail
-				$crate::impl_enum_storage!{@ComponentImpl
-					$cur_idx + 1,
-					$name,
-					$($variant_names($variant_types),)*
-				}
-			};
-			
-			// Entry point
-			{@ComponentImpl
-				$name: ident,
-				
-				$($variant_name: ident ($variant_type: ty),)*
-			} =>
-			{
-				$crate::impl_enum_storage!{@ComponentImpl
-					0,
-					$name,
-					$($variant_name($variant_type),)*
-				}
 			};
 		//--------------------------------------------------------------------------------------------------
 		
-		// Component
+		// Storage
 		//--------------------------------------------------------------------------------------------------
-			// Entry point
 			{@StorageImpl
 				// Enum name
 				$name: ident,
 				
 				// Variants
-				$(
-					$( #[$variant_meta: meta] )*
-					$variant_name: ident( $variant_type: ty )
-				),*
+				$( $variant_name: ident ( $variant_type: ty ) [ $id: expr ] [ $idx: expr ] ),*
 			} =>
 			{
 				impl<'a> $crate::Storage<'a> for $name
 				{
 					type Id = u64;
 					
-					#[must_use]
+					const COUNT: Option<usize> = Some( $name::COMPONENTS.len() );
+					
 					fn id(&self) -> Self::Id {
 						match self {
 							$(
@@ -94,7 +203,36 @@
 							)*
 						}
 					}
+					
+					fn index(id: &Self::Id) -> Option<usize> {
+						$(
+							if *id == $id { return Some($idx); }
+						)*
+						None
+					}
+				}
+				
+				impl $name
+				{
+					/// The id and name of every component, in the order they were declared
+					#[allow(dead_code)] // It's only used by tooling
+					pub const COMPONENTS: &'static [(u64, &'static str)] = &[ $( ($id, ::core::stringify!($variant_name)), )* ];
 				}
+				
+				// Check that no two components share the same id
+				#[allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)] // Indices are always within the list
+				const _: () = {
+					let components = $name::COMPONENTS;
+					let mut lhs = 0;
+					while lhs < components.len() {
+						let mut rhs = lhs + 1;
+						while rhs < components.len() {
+							assert!(components[lhs].0 != components[rhs].0, ::core::concat!("Two components of `", ::core::stringify!($name), "` share the same id"));
+							rhs += 1;
+						}
+						lhs += 1;
+					}
+				};
 			};
 		//--------------------------------------------------------------------------------------------------
 		
@@ -105,7 +243,7 @@
 			$vis:vis enum $name:ident
 			{
 				$(
-					$( #[$variant_meta: meta] )*
+					$( #[ $($variant_attr: tt)* ] )*
 					$variant_name: ident ( $variant_type: ty )
 				),*
 				
@@ -113,25 +251,12 @@
 			}
 		} =>
 		{
-			// Enum declaration
-			$( #[$enum_meta] )*
-			$vis enum $name
-			{
-				$(
-					$( #[$variant_meta] )*
-					$variant_name( $variant_type ),
-				)*
+			$crate::impl_enum_storage!{@Variants
+				[ $( #[$enum_meta] )* $vis $name ]
+				[ 0 ]
+				[]
+				$( [ [] [] [ $( #[ $($variant_attr)* ] )* ] $variant_name ( $variant_type ) ] )*
 			}
-			
-			$crate::impl_enum_storage!(@StorageImpl
-				$name,
-				$(
-					$( #[$variant_meta] )*
-					$variant_name( $variant_type )
-				),*
-			);
-			
-			$crate::impl_enum_storage!(@ComponentImpl $name, $( $variant_name($variant_type), )*);
 		}
 	}
 //--------------------------------------------------------------------------------------------------
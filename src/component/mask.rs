@@ -0,0 +1,117 @@
+//! Component masks
+//! 
+//! Storages with a small and dense id space, see [`Storage::COUNT`](crate::Storage::COUNT),
+//! allow each entity to keep a bitset of the components it has, so checking
+//! for a component doesn't need to go through it's map of components.
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A set of component indices, see [`Storage::index`](crate::Storage::index)
+	#[derive(PartialEq, Eq, Clone, Default, Debug)]
+	pub struct ComponentMask
+	{
+		/// All bits, from the lowest index
+		words: Vec<u64>,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl ComponentMask
+	{
+		/// Number of bits in each word
+		const WORD_BITS: usize = 64;
+		
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty mask
+			#[must_use]
+			pub const fn new() -> Self {
+				Self { words: vec![] }
+			}
+			
+			/// Creates an empty mask with room for `count` indices
+			#[must_use]
+			pub fn with_capacity(count: usize) -> Self {
+				Self { words: vec![0; count.div_ceil(Self::WORD_BITS)] }
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Insert / Remove
+		//--------------------------------------------------------------------------------------------------
+			/// Inserts an index into this mask
+			#[allow(clippy::arithmetic_side_effects)] // The word always fits, as the index does
+			pub fn insert(&mut self, index: usize)
+			{
+				let (word, bit) = Self::position(index);
+				if word >= self.words.len() {
+					self.words.resize(word + 1, 0);
+				}
+				
+				self.words[word] |= bit;
+			}
+			
+			/// Removes an index from this mask
+			pub fn remove(&mut self, index: usize)
+			{
+				let (word, bit) = Self::position(index);
+				if let Some(word) = self.words.get_mut(word) {
+					*word &= !bit;
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Checks
+		//--------------------------------------------------------------------------------------------------
+			/// Checks if this mask contains an index
+			#[must_use]
+			pub fn contains(&self, index: usize) -> bool
+			{
+				let (word, bit) = Self::position(index);
+				self.words.get(word).is_some_and(|word| word & bit != 0)
+			}
+			
+			/// Checks if this mask contains every index in `other`
+			#[must_use]
+			pub fn contains_all(&self, other: &Self) -> bool
+			{
+				other.words.iter()
+					.enumerate()
+					.all(|(idx, &word)| word & !self.word(idx) == 0)
+			}
+			
+			/// Checks if this mask contains any index in `other`
+			#[must_use]
+			pub fn intersects(&self, other: &Self) -> bool
+			{
+				other.words.iter()
+					.enumerate()
+					.any(|(idx, &word)| word & self.word(idx) != 0)
+			}
+			
+			/// Checks if this mask is empty
+			#[must_use]
+			pub fn is_empty(&self) -> bool {
+				self.words.iter().all(|&word| word == 0)
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Helpers
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the word of an index, and the bit within that word
+			#[allow(clippy::arithmetic_side_effects)] // The divisor is never zero, and the bit always fits
+			const fn position(index: usize) -> (usize, u64) {
+				(index / Self::WORD_BITS, 1 << (index % Self::WORD_BITS))
+			}
+			
+			/// Returns a word given it's index, or `0` if it's outside of this mask
+			fn word(&self, idx: usize) -> u64 {
+				self.words.get(idx).copied().unwrap_or(0)
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+//--------------------------------------------------------------------------------------------------
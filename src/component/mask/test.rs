@@ -0,0 +1,54 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::component::ComponentMask;
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn insert_remove()
+	{
+		let mut mask = ComponentMask::with_capacity(3);
+		assert!(mask.is_empty());
+		
+		mask.insert(1);
+		mask.insert(130);
+		assert!(!mask.contains(0));
+		assert!( mask.contains(1));
+		assert!( mask.contains(130));
+		assert!(!mask.contains(500));
+		
+		mask.remove(1);
+		mask.remove(500);
+		assert!(!mask.contains(1));
+		assert!(!mask.is_empty());
+		
+		mask.remove(130);
+		assert!(mask.is_empty());
+	}
+	
+	#[test]
+	fn compare()
+	{
+		let mut mask = ComponentMask::new();
+		mask.insert(0);
+		mask.insert(2);
+		mask.insert(70);
+		
+		let mut other = ComponentMask::new();
+		assert!( mask.contains_all(&other));
+		assert!(!mask.intersects(&other));
+		
+		other.insert(2);
+		assert!(mask.contains_all(&other));
+		assert!(mask.intersects(&other));
+		
+		other.insert(100);
+		assert!(!mask.contains_all(&other));
+		assert!( mask.intersects(&other));
+		
+		other.remove(2);
+		assert!(!mask.intersects(&other));
+	}
+//--------------------------------------------------------------------------------------------------
@@ -21,6 +21,23 @@ use mecs::{Storage, Component};
 		}
 		
 	}
+	
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type with explicit ids
+		enum IdComponents {
+			/// Has an explicit id
+			#[id = 7]
+			A(i32),
+			
+			B(&'static str),
+			
+			#[allow(dead_code)]
+			#[id = 2]
+			C(f32),
+		}
+		
+	}
 //--------------------------------------------------------------------------------------------------
 
 // Functions
@@ -60,4 +77,27 @@ use mecs::{Storage, Component};
 		assert_eq!(B::get(&b), Some(&"Bye, World!"));
 		assert_eq!(C::get(&c), Some(&2.3          ));
 	}
+	
+	#[test]
+	fn explicit_ids()
+	{
+		// Variants without an id use their index
+		assert_eq!(<A as Component<IdComponents>>::id(), 7);
+		assert_eq!(<B as Component<IdComponents>>::id(), 1);
+		assert_eq!(<C as Component<IdComponents>>::id(), 2);
+		
+		assert_eq!(IdComponents::A(5).id(), 7);
+		assert_eq!(IdComponents::B("Hello, World!").id(), 1);
+		assert_eq!(IdComponents::C(4.5).id(), 2);
+		
+		assert_eq!(IdComponents::COMPONENTS, &[(7, "A"), (1, "B"), (2, "C")]);
+		assert_eq!(Components::COMPONENTS, &[(0, "A"), (1, "B"), (2, "C")]);
+		
+		// Indices are always the variant's index
+		assert_eq!(IdComponents::COUNT, Some(3));
+		assert_eq!(IdComponents::index(&7), Some(0));
+		assert_eq!(IdComponents::index(&1), Some(1));
+		assert_eq!(IdComponents::index(&2), Some(2));
+		assert_eq!(IdComponents::index(&0), None);
+	}
 //--------------------------------------------------------------------------------------------------
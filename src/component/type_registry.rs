@@ -0,0 +1,280 @@
+//! Type registry for dynamic storages
+//! 
+//! As [`DynStorage`](crate::DynStorage) doesn't know the type of it's component,
+//! it can't clone, compare or serialize it on it's own.
+//! Instead, each component type is registered once in the global
+//! [`TypeRegistry`], along with a stable name, and [`DynStorage`](crate::DynStorage)
+//! implements these traits by looking it's type up in the registry.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{Entity, DynStorage, TypeRegistry};
+//! #[derive(PartialEq, Clone, Debug)]
+//! struct Position(f32, f32);
+//! 
+//! TypeRegistry::global().write().unwrap().register::<Position>("Position");
+//! 
+//! let entity: Entity<DynStorage> = mecs::entity![ DynStorage::new( Position(1.0, 2.0) ) ];
+//! let copy = entity.clone();
+//! assert_eq!(copy.get::<Position>(), Some(&Position(1.0, 2.0)));
+//! ```
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Collections
+use std::collections::HashMap;
+
+// Any
+use std::any::{Any, TypeId};
+
+// Fmt
+use std::fmt;
+
+// Sync
+use std::sync::{OnceLock, RwLock};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// Registry of all component types that may be stored in a [`DynStorage`](crate::DynStorage)
+	/// 
+	/// Each type is registered along with a name, which should stay
+	/// the same between versions, as it's used when serializing.
+	#[derive(Default, Debug)]
+	pub struct TypeRegistry
+	{
+		/// All registrations, by their type id
+		by_id: HashMap<TypeId, Registration>,
+		
+		/// The type ids of all registrations, by their name
+		by_name: HashMap<&'static str, TypeId>,
+	}
+	
+	/// A type registered within a [`TypeRegistry`]
+	#[derive(Clone, Copy)]
+	pub struct Registration
+	{
+		/// The type id of the type
+		id: TypeId,
+		
+		/// The registered name
+		name: &'static str,
+		
+		/// The name of the type, as given by [`std::any::type_name`]
+		type_name: &'static str,
+		
+		/// Clones a value
+		clone: fn(&dyn Any) -> Box<dyn Any>,
+		
+		/// Checks if two values are equal
+		eq: fn(&dyn Any, &dyn Any) -> bool,
+		
+		/// Formats a value
+		debug: fn(&dyn Any, &mut fmt::Formatter<'_>) -> fmt::Result,
+		
+		/// Returns a value as a serializable value
+		#[cfg(feature = "serde-serialize")]
+		serialize: Option<fn(&dyn Any) -> &dyn erased_serde::Serialize>,
+		
+		/// Deserializes a value
+		#[cfg(feature = "serde-serialize")]
+		#[allow(clippy::type_complexity)] // It's just a function pointer
+		deserialize: Option<fn(&mut dyn erased_serde::Deserializer<'_>) -> Result<Box<dyn Any>, erased_serde::Error>>,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl TypeRegistry
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty registry
+			#[must_use]
+			pub fn new() -> Self {
+				Self::default()
+			}
+			
+			/// Returns the global registry, used by all dynamic storages
+			pub fn global() -> &'static RwLock<Self>
+			{
+				static GLOBAL: OnceLock< RwLock<TypeRegistry> > = OnceLock::new();
+				GLOBAL.get_or_init(|| RwLock::new( Self::new() ))
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Register
+		//--------------------------------------------------------------------------------------------------
+			/// Registers a type with a name
+			/// 
+			/// Registering the same type more than once replaces it's previous registration.
+			/// 
+			/// # Panics
+			/// Panics if the name is already used by another type.
+			pub fn register<T>(&mut self, name: &'static str)
+			where
+				T: Clone + PartialEq + fmt::Debug + 'static
+			{
+				self.insert::<T>( Registration::new::<T>(name) );
+			}
+			
+			/// Registers a type with a name, allowing it to be serialized
+			/// 
+			/// See [`TypeRegistry::register`].
+			/// 
+			/// # Panics
+			/// Panics if the name is already used by another type.
+			#[cfg(feature = "serde-serialize")]
+			pub fn register_serde<T>(&mut self, name: &'static str)
+			where
+				T: Clone + PartialEq + fmt::Debug + serde::Serialize + serde::de::DeserializeOwned + 'static
+			{
+				let registration = Registration {
+					serialize: Some(|value| downcast::<T>(value)),
+					deserialize: Some(|deserializer| erased_serde::deserialize::<T>(deserializer).map(|value| -> Box<dyn Any> { Box::new(value) })),
+					..Registration::new::<T>(name)
+				};
+				
+				self.insert::<T>(registration);
+			}
+			
+			/// Inserts the registration of a type
+			fn insert<T: 'static>(&mut self, registration: Registration)
+			{
+				let id = TypeId::of::<T>();
+				if let Some(&other_id) = self.by_name.get(registration.name) {
+					assert!(other_id == id, "Name `{}` is already registered for another type", registration.name);
+				}
+				
+				if let Some(old) = self.by_id.insert(id, registration) {
+					self.by_name.remove(old.name);
+				}
+				self.by_name.insert(registration.name, id);
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Access
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the registration of a type given it's type id
+			#[must_use]
+			pub fn get(&self, id: TypeId) -> Option<&Registration> {
+				self.by_id.get(&id)
+			}
+			
+			/// Returns the registration of a type given it's registered name
+			#[must_use]
+			pub fn get_by_name(&self, name: &str) -> Option<&Registration> {
+				self.by_name.get(name).and_then(|id| self.by_id.get(id))
+			}
+			
+			/// Returns the number of registered types
+			#[must_use]
+			pub fn len(&self) -> usize {
+				self.by_id.len()
+			}
+			
+			/// Checks if there are no registered types
+			#[must_use]
+			pub fn is_empty(&self) -> bool {
+				self.by_id.is_empty()
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl Registration
+	{
+		/// Creates the registration of a type, without serialization
+		fn new<T>(name: &'static str) -> Self
+		where
+			T: Clone + PartialEq + fmt::Debug + 'static
+		{
+			Self {
+				id: TypeId::of::<T>(),
+				name,
+				type_name: std::any::type_name::<T>(),
+				clone: |value| Box::new( downcast::<T>(value).clone() ),
+				eq: |lhs, rhs| rhs.downcast_ref::<T>().is_some_and(|rhs| downcast::<T>(lhs) == rhs),
+				debug: |value, f| fmt::Debug::fmt(downcast::<T>(value), f),
+				#[cfg(feature = "serde-serialize")]
+				serialize: None,
+				#[cfg(feature = "serde-serialize")]
+				deserialize: None,
+			}
+		}
+		
+		/// Returns the type id of the type
+		#[must_use]
+		pub const fn id(&self) -> TypeId {
+			self.id
+		}
+		
+		/// Returns the registered name
+		#[must_use]
+		pub const fn name(&self) -> &'static str {
+			self.name
+		}
+		
+		/// Returns the name of the type
+		#[must_use]
+		pub const fn type_name(&self) -> &'static str {
+			self.type_name
+		}
+		
+		/// Clones a value of this type
+		#[must_use]
+		pub fn clone_value(&self, value: &dyn Any) -> Box<dyn Any> {
+			(self.clone)(value)
+		}
+		
+		/// Checks if two values of this type are equal
+		#[must_use]
+		pub fn eq_values(&self, lhs: &dyn Any, rhs: &dyn Any) -> bool {
+			(self.eq)(lhs, rhs)
+		}
+		
+		/// Formats a value of this type
+		/// 
+		/// # Errors
+		/// Returns any errors from formatting the value.
+		pub fn fmt_value(&self, value: &dyn Any, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+			(self.debug)(value, f)
+		}
+		
+		/// Returns a value of this type as a serializable value
+		/// 
+		/// Returns `None` if this type wasn't registered for serialization
+		#[cfg(feature = "serde-serialize")]
+		#[must_use]
+		pub fn serialize_value<'v>(&self, value: &'v dyn Any) -> Option<&'v dyn erased_serde::Serialize> {
+			self.serialize.map(|serialize| serialize(value))
+		}
+		
+		/// Deserializes a value of this type
+		/// 
+		/// Returns `None` if this type wasn't registered for serialization
+		#[cfg(feature = "serde-serialize")]
+		pub fn deserialize_value(&self, deserializer: &mut dyn erased_serde::Deserializer<'_>) -> Option< Result<Box<dyn Any>, erased_serde::Error> > {
+			self.deserialize.map(|deserialize| deserialize(deserializer))
+		}
+	}
+	
+	impl fmt::Debug for Registration
+	{
+		fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+			f.debug_struct("Registration")
+				.field("name", &self.name)
+				.field("type_name", &self.type_name)
+				.finish_non_exhaustive()
+		}
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Downcasts a value to the type it was registered with
+	fn downcast<T: 'static>(value: &dyn Any) -> &T {
+		value.downcast_ref().expect("Value had a different type than it's registration")
+	}
+//--------------------------------------------------------------------------------------------------
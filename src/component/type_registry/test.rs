@@ -0,0 +1,112 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{World, DynStorage, TypeRegistry};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	// Each test uses it's own types, as the global registry is shared between tests
+	#[derive(PartialEq, Clone, Debug)]
+	struct Position(f32, f32);
+	
+	#[derive(PartialEq, Clone, Debug)]
+	struct Health(u32);
+	
+	#[derive(PartialEq, Clone, Debug)]
+	struct Name(&'static str);
+	
+	#[derive(PartialEq, Clone, Debug)]
+	struct Unregistered;
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn register()
+	{
+		let mut registry = TypeRegistry::new();
+		assert!(registry.is_empty());
+		
+		registry.register::<Position>("Position");
+		registry.register::<Health>("Health");
+		assert_eq!(registry.len(), 2);
+		
+		let registration = registry.get_by_name("Position").unwrap();
+		assert_eq!(registration.id(), std::any::TypeId::of::<Position>());
+		assert_eq!(registration.type_name(), std::any::type_name::<Position>());
+		
+		// Registering a type again replaces it's name
+		registry.register::<Position>("Pos");
+		assert_eq!(registry.len(), 2);
+		assert!(registry.get_by_name("Position").is_none());
+		assert_eq!(registry.get_by_name("Pos").unwrap().name(), "Pos");
+	}
+	
+	#[test]
+	#[should_panic(expected = "Name `Position` is already registered for another type")]
+	fn duplicate_name()
+	{
+		let mut registry = TypeRegistry::new();
+		registry.register::<Position>("Position");
+		registry.register::<Health>("Position");
+	}
+	
+	#[test]
+	fn clone_eq_debug()
+	{
+		TypeRegistry::global().write().unwrap().register::<Health>("Health");
+		
+		let health = DynStorage::new( Health(5) );
+		let copy = health.clone();
+		
+		assert_eq!(health, copy);
+		assert_ne!(health, DynStorage::new( Health(6) ));
+		assert_ne!(health, DynStorage::new( Unregistered ));
+		assert_eq!(format!("{copy:?}"), "DynStorage(Health(5))");
+		
+		// Unregistered types only show their name
+		assert_eq!(DynStorage::new( Unregistered ).type_name(), std::any::type_name::<Unregistered>());
+		assert_eq!(
+			format!("{:?}", DynStorage::new( Unregistered )),
+			format!("DynStorage({:?})", std::any::type_name::<Unregistered>()),
+		);
+	}
+	
+	#[test]
+	#[should_panic(expected = "isn't registered in the type registry")]
+	fn clone_unregistered()
+	{
+		let entity: mecs::Entity<DynStorage> = mecs::entity![ DynStorage::new( Unregistered ) ];
+		assert!(entity.clone().has::<Unregistered>());
+	}
+	
+	#[test]
+	fn try_clone_eq_unregistered()
+	{
+		let storage = DynStorage::new( Unregistered );
+		assert!(storage.try_clone().is_none());
+		assert_eq!(storage.try_eq(&storage), None);
+		assert_eq!(storage.try_eq( &DynStorage::new( Name("Player") ) ), Some(false));
+		assert!(storage != storage);
+		
+		let world = World::from_entities([ mecs::entity![ DynStorage::new( Unregistered ) ] ]);
+		assert!(world != world);
+	}
+	
+	#[test]
+	fn world()
+	{
+		TypeRegistry::global().write().unwrap().register::<Name>("Name");
+		
+		let mut world: World<DynStorage> = World::new();
+		let id = world.add( mecs::entity![ DynStorage::new( Name("Player") ) ] );
+		world.add( mecs::entity![ DynStorage::new( Name("Enemy") ) ] );
+		
+		let copy = World::from_entities( world.iter_all().cloned() );
+		assert!(world == copy);
+		
+		world.insert_component( id, DynStorage::new( Name("Boss") ) ).unwrap();
+		assert!(world != copy);
+	}
+//--------------------------------------------------------------------------------------------------
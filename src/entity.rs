@@ -2,6 +2,11 @@
 //! 
 //! An entity is a collection of components.
 //! 
+//! Once an entity is in a world, components are added to or removed from it
+//! through the world, see [`World::insert_component`](crate::World::insert_component),
+//! so that all predicates and observers are kept up to date. Entities borrowed
+//! mutably from a world may only have their components modified.
+//! 
 //! # Example
 //! 
 //! ```rust
@@ -14,8 +19,11 @@
 //! ];
 //! 
 //! let num: i32 = *entity.get().unwrap();
-//! 
 //! assert_eq!(num, 23);
+//! 
+//! let entity = entity.with( DynStorage::new(5u32) ).without::<f64>();
+//! assert!(entity.has::<u32>());
+//! assert!(!entity.has::<f64>());
 //! ```
 
 // Modules
@@ -30,7 +38,7 @@ use std::fmt::Debug;
 use std::iter::Iterator;
 
 // Crate
-use crate::{KeyType, Storage, Component};
+use crate::{KeyType, Storage, Component, Tick, ComponentTicks, component::ComponentMask};
 
 // Macros
 //--------------------------------------------------------------------------------------------------
@@ -54,14 +62,30 @@ use crate::{KeyType, Storage, Component};
 // Types
 //--------------------------------------------------------------------------------------------------
 	/// An entity
-	#[derive(PartialEq, Eq, Clone, Debug)]
+	/// 
+	/// # Change detection
+	/// Each component keeps track of the tick it was added in and
+	/// the tick it was last mutably accessed in, see [`crate::tick`].
+	/// Entities outside of a world always use the default tick.
+	/// 
+	/// # Component mask
+	/// If the storage maps it's ids to indices, see [`Storage::COUNT`],
+	/// each entity also keeps a mask of it's components, so checking
+	/// for a component is just checking a bit.
+	#[derive(Clone, Debug)]
 	pub struct Entity<'a, S>
 	where
 		S    : Storage<'a>,
 		S::Id: KeyType,
 	{
-		/// All of the components
-		components: HashMap<S::Id, S>,
+		/// All of the components, along with their ticks
+		components: HashMap<S::Id, (S, ComponentTicks)>,
+		
+		/// The indices of all components, if the storage has any
+		mask: ComponentMask,
+		
+		/// The tick to mark components with when they're added or changed
+		pub(crate) change_tick: Tick,
 	}
 //--------------------------------------------------------------------------------------------------
 
@@ -79,6 +103,8 @@ use crate::{KeyType, Storage, Component};
 			pub fn new() -> Self {
 				Self {
 					components: HashMap::new(),
+					mask: ComponentMask::with_capacity( S::COUNT.unwrap_or(0) ),
+					change_tick: Tick::default(),
 				}
 			}
 			
@@ -101,6 +127,31 @@ use crate::{KeyType, Storage, Component};
 		
 		// Add / Remove
 		//--------------------------------------------------------------------------------------------------
+			/// Adds a new component to this entity, returning it
+			/// 
+			/// If this entity already contains a component with
+			/// the same id as the one in `storage`, it's replaced.
+			#[must_use]
+			pub fn with(mut self, storage: S) -> Self
+			{
+				let _ = self.add(storage);
+				self
+			}
+			
+			/// Removes a component from this entity given it's type, returning it
+			#[must_use]
+			pub fn without<C: Component<'a, S>>(self) -> Self {
+				self.without_id( &C::id() )
+			}
+			
+			/// Removes a component from this entity given it's id, returning it
+			#[must_use]
+			pub fn without_id(mut self, id: &S::Id) -> Self
+			{
+				let _ = self.remove_id(id);
+				self
+			}
+			
 			/// Adds a new component to this entity
 			/// 
 			/// # Return
@@ -108,8 +159,14 @@ use crate::{KeyType, Storage, Component};
 			/// the same id as the one in `storage`, it is returned.
 			pub fn add(&mut self, storage: S) -> Option<S>
 			{
+				let id = storage.id();
+				if let Some(index) = S::index(&id) {
+					self.mask.insert(index);
+				}
+				
 				// Insert it and remove any that already existed
-				self.components.insert(storage.id(), storage)
+				self.components.insert(id, (storage, ComponentTicks::new(self.change_tick)))
+					.map(|(storage, _)| storage)
 			}
 			
 			/// Remove a component from this entity given it's type
@@ -122,8 +179,13 @@ use crate::{KeyType, Storage, Component};
 			/// Removes a component from this entity given it's id
 			pub fn remove_id(&mut self, id: &S::Id) -> Option<S>
 			{
+				if let Some(index) = S::index(id) {
+					self.mask.remove(index);
+				}
+				
 				// Attempt to remove it from it's id
 				self.components.remove(id)
+					.map(|(storage, _)| storage)
 			}
 		//--------------------------------------------------------------------------------------------------
 		
@@ -141,12 +203,13 @@ use crate::{KeyType, Storage, Component};
 				// Get the storage by id and try to get the component
 				// from this storage.
 				self.get_id( &C::id() )
-					.map(C::get)
-					.flatten()
+					.and_then(C::get)
 			}
 			
 			/// Returns a mutable reference to a component given it's type
 			/// 
+			/// This marks the component as changed, see [`Entity::changed`].
+			/// 
 			/// # Return
 			/// If either the component is not present in this entity
 			/// or the component could not be build from the storage
@@ -157,20 +220,28 @@ use crate::{KeyType, Storage, Component};
 				// Get the storage by id and try to get the component
 				// from this storage.
 				self.get_mut_id( &C::id() )
-					.map(C::get_mut)
-					.flatten()
+					.and_then(C::get_mut)
 			}
 			
 			/// Returns a reference to a component's storage given it's id
 			#[must_use]
 			pub fn get_id(&self, id: &S::Id) -> Option<&S> {
 				self.components.get(id)
+					.map(|(storage, _)| storage)
 			}
 			
 			/// Returns a mutable reference to a component's storage given it's id
+			/// 
+			/// This marks the component as changed, see [`Entity::changed`].
 			#[must_use]
-			pub fn get_mut_id(&mut self, id: &S::Id) -> Option<&mut S> {
+			pub fn get_mut_id(&mut self, id: &S::Id) -> Option<&mut S>
+			{
+				let change_tick = self.change_tick;
 				self.components.get_mut(id)
+					.map(|(storage, ticks)| {
+						ticks.changed = change_tick;
+						storage
+					})
 			}
 		//--------------------------------------------------------------------------------------------------
 		
@@ -184,11 +255,26 @@ use crate::{KeyType, Storage, Component};
 			/// Returns an iterator over all components in this entity
 			pub fn components(&self) -> impl Iterator<Item = &S> {
 				self.components.values()
+					.map(|(storage, _)| storage)
 			}
 			
 			/// Returns a mutable iterator over all components in this entity
-			pub fn components_mut(&mut self) -> impl Iterator<Item = &mut S> {
+			/// 
+			/// This marks all components as changed, see [`Entity::changed`].
+			pub fn components_mut(&mut self) -> impl Iterator<Item = &mut S>
+			{
+				let change_tick = self.change_tick;
 				self.components.values_mut()
+					.map(move |(storage, ticks)| {
+						ticks.changed = change_tick;
+						storage
+					})
+			}
+			
+			/// Consumes this entity and returns an iterator over all of it's components
+			pub fn into_components(self) -> impl Iterator<Item = S> {
+				self.components.into_values()
+					.map(|(storage, _)| storage)
 			}
 		//--------------------------------------------------------------------------------------------------
 		
@@ -201,19 +287,125 @@ use crate::{KeyType, Storage, Component};
 			}
 			
 			/// Checks if this entity has a component given it's id
+			/// 
+			/// If the id has an index, this only checks the component mask.
 			#[must_use]
 			pub fn has_id(&self, id: &S::Id) -> bool {
-				self.components.contains_key(id)
+				S::index(id).map_or_else(
+					|| self.components.contains_key(id),
+					|index| self.mask.contains(index),
+				)
+			}
+			
+			/// Returns the mask of all components in this entity
+			/// 
+			/// The mask is empty if the storage doesn't map it's ids to indices, see [`Storage::COUNT`].
+			#[must_use]
+			pub const fn mask(&self) -> &ComponentMask {
+				&self.mask
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Change detection
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the ticks of a component given it's type
+			#[must_use]
+			pub fn ticks<C: Component<'a, S>>(&self) -> Option<ComponentTicks> {
+				self.ticks_id( &C::id() )
+			}
+			
+			/// Returns the ticks of a component given it's id
+			#[must_use]
+			pub fn ticks_id(&self, id: &S::Id) -> Option<ComponentTicks> {
+				self.components.get(id)
+					.map(|&(_, ticks)| ticks)
+			}
+			
+			/// Checks if a component was added after the tick `since`, given it's type
+			/// 
+			/// Returns `false` if the component is not present.
+			#[must_use]
+			pub fn added<C: Component<'a, S>>(&self, since: Tick) -> bool {
+				self.ticks::<C>()
+					.is_some_and(|ticks| ticks.is_added(since))
+			}
+			
+			/// Checks if a component was added or mutably accessed after the tick `since`, given it's type
+			/// 
+			/// Returns `false` if the component is not present.
+			#[must_use]
+			pub fn changed<C: Component<'a, S>>(&self, since: Tick) -> bool {
+				self.ticks::<C>()
+					.is_some_and(|ticks| ticks.is_changed(since))
+			}
+			
+			/// Returns a mutable iterator over all components in this
+			/// entity along with their ids and ticks, without marking them as changed
+			pub(crate) fn components_ticks_mut(&mut self) -> impl Iterator<Item = (&I, &mut S, &mut ComponentTicks)> {
+				self.components.iter_mut()
+					.map(|(id, (storage, ticks))| (id, storage, ticks))
+			}
+			
+			/// Creates an entity from a list of components along with their ticks
+			pub(crate) fn from_components_ticks<C>(components: C) -> Self
+			where
+				C: IntoIterator<Item = (S, ComponentTicks)>
+			{
+				let mut entity = Self::new();
+				for (storage, ticks) in components {
+					let id = storage.id();
+					if let Some(index) = S::index(&id) {
+						entity.mask.insert(index);
+					}
+					
+					entity.components.insert(id, (storage, ticks));
+				}
+				
+				entity
+			}
+			
+			/// Consumes this entity and returns an iterator over all of it's components along with their ticks
+			pub(crate) fn into_components_ticks(self) -> impl Iterator<Item = (S, ComponentTicks)> {
+				self.components.into_values()
+			}
+			
+			/// Sets the ticks of all components to have been added in the current tick
+			pub(crate) fn reset_ticks(&mut self)
+			{
+				let change_tick = self.change_tick;
+				for (_, ticks) in self.components.values_mut() {
+					*ticks = ComponentTicks::new(change_tick);
+				}
 			}
 		//--------------------------------------------------------------------------------------------------
 	}
 	
+	impl<'a, S> PartialEq for Entity<'a, S>
+	where
+		S    : Storage<'a> + PartialEq,
+		S::Id: KeyType,
+	{
+		fn eq(&self, other: &Self) -> bool
+		{
+			// Compare just the components, ignoring their ticks
+			self.components.len() == other.components.len() &&
+			self.components.iter().all(|(id, (storage, _))| {
+				other.components.get(id).is_some_and(|(other_storage, _)| storage == other_storage)
+			})
+		}
+	}
+	
+	impl<'a, S> Eq for Entity<'a, S>
+	where
+		S    : Storage<'a> + Eq,
+		S::Id: KeyType,
+	{}
+	
 	impl<'a, S, I> Default for Entity<'a, S>
 	where
 		S: Storage<'a, Id = I>,
 		I: KeyType + 'a,
 	{
-		#[must_use]
 		fn default() -> Self {
 			Self::new()
 		}
@@ -235,7 +427,7 @@ use crate::{KeyType, Storage, Component};
 				
 				let mut seq = serializer.serialize_seq( Some(self.components.len()) )?;
 				
-				for component in self.components.values() {
+				for (component, _) in self.components.values() {
 					seq.serialize_element(component)?;
 				}
 				
@@ -2,7 +2,7 @@
 
 // Crate
 use crate as mecs;
-use mecs::{Component, Entity};
+use mecs::{Component, Entity, World, Tick, DynStorage};
 
 // Types
 //--------------------------------------------------------------------------------------------------
@@ -97,4 +97,55 @@ use mecs::{Component, Entity};
 		assert!(entity.has::<B>());
 		assert!(entity.has::<C>());
 	}
+	
+	#[test]
+	fn change_ticks()
+	{
+		let mut world: World<Components> = World::new();
+		let id = world.add( mecs::entity![ Components::A(5), Components::B("Hello, World!") ] );
+		
+		// Everything in the world was added after the default tick
+		assert!(world[id].added  ::<A>( Tick::default() ));
+		assert!(world[id].changed::<B>( Tick::default() ));
+		assert!(!world[id].added ::<C>( Tick::default() ));
+		
+		let last_tick = world.change_tick();
+		world.increment_change_tick();
+		
+		// Reading doesn't change anything
+		assert_eq!(world[id].get::<A>(), Some(&5));
+		assert!(!world[id].changed::<A>(last_tick));
+		
+		// But mutable access does
+		*world.get_mut(id).unwrap().get_mut::<A>().unwrap() = 8;
+		assert!( world[id].changed::<A>(last_tick));
+		assert!(!world[id].changed::<B>(last_tick));
+		assert!(!world[id].added  ::<A>(last_tick));
+		
+		// As does adding a component
+		world.insert_component(id, Components::C(4.5)).unwrap();
+		assert!(world[id].added::<C>(last_tick));
+		assert_eq!(world[id].ticks::<C>().map(|ticks| ticks.added), Some( world.change_tick() ));
+	}
+	
+	#[test]
+	fn mask()
+	{
+		let mut entity: Entity<Components> = mecs::entity![ Components::A(5), Components::C(4.5) ];
+		
+		assert!( entity.mask().contains(0));
+		assert!(!entity.mask().contains(1));
+		assert!( entity.mask().contains(2));
+		
+		entity.remove::<A>();
+		assert!(!entity.mask().contains(0));
+		assert!(!entity.has::<A>());
+		assert!( entity.has::<C>());
+		
+		// Storages without indices just don't use the mask
+		let entity: Entity<DynStorage> = mecs::entity![ DynStorage::new(5i32) ];
+		assert!(entity.mask().is_empty());
+		assert!( entity.has::<i32>());
+		assert!(!entity.has::<f32>());
+	}
 //--------------------------------------------------------------------------------------------------
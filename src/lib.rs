@@ -1,32 +1,24 @@
 //! Entity component system implementation
 
-// Features
-#![feature(unboxed_closures)]
-#![feature(fn_traits       )]
-#![feature(external_doc    )]
-
 // Warnings
 //--------------------------------------------------------------------------------------------------
 	// Use all warnings from clippy
 	#![warn(
 		clippy::all,
-		clippy::restriction,
 		clippy::pedantic,
 		clippy::nursery,
 		clippy::cargo,
 	)]
 	
-	// LTO
-	// TODO: Check when we can remove this
-	#![allow(clippy::missing_inline_in_public_items)]
+	// Restriction
+	// Note: Clippy advises against enabling all of `clippy::restriction`, so we only enable the ones we follow
+	#![warn(
+		clippy::arithmetic_side_effects,
+		clippy::as_conversions,
+	)]
 	
 	// Style
-	#![allow(clippy::implicit_return   )] // Our convention uses tail returns
-	#![allow(clippy::panic             )] // We use panic when we reach an unstable state
-	#![allow(clippy::result_expect_used)] // We use expect when making assertions
-	#![allow(clippy::option_expect_used)] // ...
-	#![allow(clippy::indexing_slicing  )] // We use [] when we want to panic if the value doesn't exist
-	#![allow(clippy::unreachable       )] // We use unreachable when making assertions
+	#![allow(clippy::tabs_in_doc_comments)] // Our examples are indented with tabs, like the rest of our code
 //--------------------------------------------------------------------------------------------------
 
 
@@ -36,17 +28,26 @@
 pub mod component;
 pub mod entity;
 pub mod world;
+pub mod tick;
+pub mod system;
 
 // Exports
     use util     ::KeyType;
-pub use component::{Component, Storage, DynStorage};
+pub use component::{Component, Storage, DynStorage, TypeRegistry, ComponentMask};
 pub use entity   ::Entity;
-pub use world    ::{World, EntityId};
+pub use world    ::{World, EntityId, PredId, Query, Changed, Added, Filter, FilterMask, WorldView, Commands, Resources, Events, EventReader, MapEntities, ArchetypeWorld, SparseWorld};
+pub use tick     ::{Tick, ComponentTicks};
+pub use system   ::{System, ParSystem, Access, Schedule, ParSchedule};
+
+// Derive
+#[cfg(feature = "derive")]
+pub use mecs_derive::Storage;
 
 
 
 // External docs
 
 /// Doc tests for "README.md"
-#[doc(include = "../README.md")]
+#[doc = include_str!("../README.md")]
+#[allow(clippy::doc_markdown)] // The readme isn't written with clippy in mind
 type _DoctestReadme = ();
@@ -0,0 +1,269 @@
+//! Systems
+//! 
+//! A system is a piece of logic that runs over a world every
+//! tick, declaring which components it reads and writes through
+//! [`System::access`].
+//! Systems are usually grouped into a [`Schedule`], which runs
+//! them in order.
+//! 
+//! Systems that only need the components they declare may instead
+//! implement [`ParSystem`] and be grouped into a [`ParSchedule`],
+//! which runs systems that don't conflict at the same time.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{World, DynStorage, Filter, PredId, System, Access, Schedule};
+//! /// Adds one to every `i32`
+//! #[derive(Default)]
+//! struct Increment {
+//! 	pred_id: Option< PredId<DynStorage> >,
+//! }
+//! 
+//! impl System<'static, DynStorage> for Increment {
+//! 	fn access(&self) -> Access<'static, DynStorage> {
+//! 		Access::new().write::<i32>()
+//! 	}
+//! 
+//! 	fn init(&mut self, world: &mut World<'static, DynStorage>) {
+//! 		self.pred_id = Some( world.add_filter( Filter::has::<i32>() ) );
+//! 	}
+//! 
+//! 	fn run(&mut self, world: &mut World<'static, DynStorage>) {
+//! 		world.iter_pred_mut( self.pred_id.unwrap() ).unwrap().for_each(|mut entity, _| {
+//! 			*entity.get_mut::<i32>().unwrap() += 1;
+//! 		});
+//! 	}
+//! }
+//! 
+//! let mut world = World::new();
+//! let id = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+//! 
+//! let mut schedule = Schedule::new();
+//! schedule.add( Increment::default() );
+//! 
+//! schedule.run(&mut world);
+//! schedule.run(&mut world);
+//! 
+//! assert_eq!(world[id].get::<i32>(), Some(&7));
+//! ```
+
+// Modules
+pub mod schedule;
+pub mod parallel;
+#[cfg(test)]
+mod test;
+
+// Exports
+pub use schedule::Schedule;
+pub use parallel::{ParSchedule, Conflict};
+
+// Traits
+use std::fmt::Debug;
+
+// Crate
+use crate::{util::KeyType, Storage, Component, World, WorldView, world::{Backend, Entities}};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// The components accessed by a system
+	/// 
+	/// Writing to a component implies reading it.
+	pub struct Access<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// All components read
+		reads: Vec<S::Id>,
+		
+		/// All components written
+		writes: Vec<S::Id>,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Traits
+//--------------------------------------------------------------------------------------------------
+	/// A system
+	/// 
+	/// Systems are generic over the backend of the world they run on,
+	/// see [`backend`](crate::world::backend).
+	pub trait System<'a, S, B = Entities<'a, S>>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		/// Returns the components accessed by this system
+		fn access(&self) -> Access<'a, S>;
+		
+		/// Initializes this system
+		/// 
+		/// Called by a schedule before the first time this
+		/// system is run, so it may register it's predicates.
+		fn init(&mut self, _world: &mut World<'a, S, B>) {}
+		
+		/// Runs this system
+		fn run(&mut self, world: &mut World<'a, S, B>);
+	}
+	
+	/// A system that may run at the same time as other systems
+	/// 
+	/// Instead of the whole world, these systems only get a view
+	/// over the components they declare, see [`WorldView`].
+	/// As with [`System`], they're generic over the backend of the world.
+	pub trait ParSystem<'a, S, B = Entities<'a, S>>: Send
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		/// Returns the components accessed by this system
+		fn access(&self) -> Access<'a, S>;
+		
+		/// Initializes this system
+		/// 
+		/// Called by a schedule before the first time this system is run,
+		/// so it may register the predicates of it's queries, see [`World::query_pred`].
+		fn init(&mut self, _world: &mut World<'a, S, B>) {}
+		
+		/// Runs this system
+		fn run(&mut self, view: &mut WorldView<'_, 'a, S, B>);
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S> Access<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an access without any components
+			#[must_use]
+			pub const fn new() -> Self {
+				Self {
+					reads: vec![],
+					writes: vec![],
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Builders
+		//--------------------------------------------------------------------------------------------------
+			/// Adds a component to be read given it's type
+			#[must_use]
+			pub fn read<C: Component<'a, S>>(self) -> Self {
+				self.read_id( C::id() )
+			}
+			
+			/// Adds a component to be read given it's id
+			#[must_use]
+			pub fn read_id(mut self, id: S::Id) -> Self {
+				if !self.reads.contains(&id) {
+					self.reads.push(id);
+				}
+				
+				self
+			}
+			
+			/// Adds a component to be written given it's type
+			#[must_use]
+			pub fn write<C: Component<'a, S>>(self) -> Self {
+				self.write_id( C::id() )
+			}
+			
+			/// Adds a component to be written given it's id
+			#[must_use]
+			pub fn write_id(mut self, id: S::Id) -> Self {
+				if !self.writes.contains(&id) {
+					self.writes.push(id);
+				}
+				
+				self
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Access
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the ids of all components declared as read
+			/// 
+			/// Written components aren't included, unless also declared as read.
+			#[must_use]
+			pub fn reads(&self) -> &[S::Id] {
+				&self.reads
+			}
+			
+			/// Returns the ids of all components written
+			#[must_use]
+			pub fn writes(&self) -> &[S::Id] {
+				&self.writes
+			}
+			
+			/// Checks if a component is read given it's id
+			/// 
+			/// Written components are also read.
+			#[must_use]
+			pub fn reads_id(&self, id: &S::Id) -> bool {
+				self.reads.contains(id) || self.writes_id(id)
+			}
+			
+			/// Checks if a component is written given it's id
+			#[must_use]
+			pub fn writes_id(&self, id: &S::Id) -> bool {
+				self.writes.contains(id)
+			}
+			
+			/// Checks if no components are written
+			#[must_use]
+			pub const fn is_read_only(&self) -> bool {
+				self.writes.is_empty()
+			}
+			
+			/// Returns the ids of all components this access conflicts with another on
+			/// 
+			/// Two accesses conflict on a component if either of them writes it
+			/// and the other one reads it.
+			#[must_use]
+			pub fn conflicts<'b>(&'b self, other: &'b Self) -> Vec<&'b S::Id>
+			{
+				let mut ids: Vec<&S::Id> = vec![];
+				
+				let lhs = self.writes.iter().filter(|id| other.reads_id(id));
+				let rhs = other.writes.iter().filter(|id| self.reads_id(id));
+				for id in lhs.chain(rhs) {
+					if !ids.contains(&id) {
+						ids.push(id);
+					}
+				}
+				
+				ids
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl<'a, S> Default for Access<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		fn default() -> Self {
+			Self::new()
+		}
+	}
+	
+	impl<'a, S> std::fmt::Debug for Access<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + Debug,
+	{
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			f.debug_struct("Access")
+				.field("reads", &self.reads)
+				.field("writes", &self.writes)
+				.finish()
+		}
+	}
+//--------------------------------------------------------------------------------------------------
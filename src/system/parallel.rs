@@ -0,0 +1,269 @@
+//! Parallel schedules of systems
+//! 
+//! A parallel schedule groups it's systems into stages, such that
+//! no two systems within a stage conflict, see [`Access::conflicts`].
+//! Each stage is then run in order, with all of it's systems running
+//! at the same time, each on it's own thread.
+//! 
+//! Systems are placed in the first stage after all systems added
+//! before them that they conflict with, so conflicting systems still
+//! run in the order they were added.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{World, WorldView, ParSystem, Access, ParSchedule};
+//! mecs::impl_enum_storage! {
+//! 	enum Components {
+//! 		Num(i32),
+//! 		Scale(f32),
+//! 	}
+//! }
+//! 
+//! /// Adds one to every `i32`
+//! struct Increment;
+//! 
+//! impl<'a> ParSystem<'a, Components> for Increment {
+//! 	fn access(&self) -> Access<'a, Components> {
+//! 		Access::new().write::<i32>()
+//! 	}
+//! 
+//! 	// Registers our query's predicate, so views don't go through every entity
+//! 	fn init(&mut self, world: &mut World<'a, Components>) {
+//! 		let _ = world.query_pred::<&mut i32>();
+//! 	}
+//! 
+//! 	fn run(&mut self, view: &mut WorldView<'_, 'a, Components>) {
+//! 		for (_, num) in view.query::<&mut i32>() {
+//! 			*num += 1;
+//! 		}
+//! 	}
+//! }
+//! 
+//! /// Doubles every `f32`
+//! struct Double;
+//! 
+//! impl<'a> ParSystem<'a, Components> for Double {
+//! 	fn access(&self) -> Access<'a, Components> {
+//! 		Access::new().write::<f32>()
+//! 	}
+//! 
+//! 	fn run(&mut self, view: &mut WorldView<'_, 'a, Components>) {
+//! 		for (_, scale) in view.query::<&mut f32>() {
+//! 			*scale *= 2.0;
+//! 		}
+//! 	}
+//! }
+//! 
+//! let mut world: World<Components> = World::new();
+//! let id = world.add( mecs::entity![ Components::Num(5), Components::Scale(2.0) ] );
+//! 
+//! let mut schedule = ParSchedule::new();
+//! schedule.add(Increment);
+//! schedule.add(Double);
+//! assert_eq!(schedule.stages(), [ vec![0, 1] ]);
+//! 
+//! schedule.run(&mut world);
+//! assert_eq!(world[id].get::<i32>(), Some(&6));
+//! assert_eq!(world[id].get::<f32>(), Some(&4.0));
+//! ```
+
+// Crate
+use crate::{util::KeyType, Storage, World, world::{Backend, BackendView, Entities}};
+use super::{ParSystem, Access};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A list of systems, run in stages of non-conflicting systems
+	/// 
+	/// # Predicates
+	/// Systems are initialized the first time the schedule is run,
+	/// which is when they register their predicates, so a schedule
+	/// should always be run on the same world.
+	pub struct ParSchedule<'a, S, B = Entities<'a, S>>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		/// All systems, in the order they were added
+		systems: Vec< ScheduledSystem<'a, S, B> >,
+		
+		/// The number of stages
+		stages: usize,
+	}
+	
+	/// A system within a parallel schedule
+	struct ScheduledSystem<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		/// The system
+		system: Box<dyn ParSystem<'a, S, B> + 'a>,
+		
+		/// The access of the system
+		access: Access<'a, S>,
+		
+		/// The stage the system runs in
+		stage: usize,
+		
+		/// If the system has been initialized
+		initialized: bool,
+	}
+	
+	/// A conflict between two systems of a schedule
+	#[derive(PartialEq, Eq, Clone, Debug)]
+	pub struct Conflict<'b, I>
+	{
+		/// The indices of both systems, in the order they were added
+		pub systems: (usize, usize),
+		
+		/// The ids of all components they conflict on
+		pub ids: Vec<&'b I>,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S, B> ParSchedule<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty schedule
+			#[must_use]
+			pub const fn new() -> Self {
+				Self { systems: vec![], stages: 0 }
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Systems
+		//--------------------------------------------------------------------------------------------------
+			/// Adds a system to this schedule
+			/// 
+			/// The access of the system is only checked once, when it's added.
+			#[allow(clippy::arithmetic_side_effects)] // We need to add one to get the next stage
+			pub fn add<T>(&mut self, system: T)
+			where
+				T: ParSystem<'a, S, B> + 'a
+			{
+				let access = system.access();
+				
+				// Run it after all systems we conflict with
+				let stage = self.systems.iter()
+					.filter(|scheduled| !scheduled.access.conflicts(&access).is_empty())
+					.map(|scheduled| scheduled.stage + 1)
+					.max()
+					.unwrap_or(0);
+				self.stages = self.stages.max(stage + 1);
+				
+				self.systems.push( ScheduledSystem{ system: Box::new(system), access, stage, initialized: false } );
+			}
+			
+			/// Returns the number of systems in this schedule
+			#[must_use]
+			pub const fn len(&self) -> usize {
+				self.systems.len()
+			}
+			
+			/// Checks if this schedule has no systems
+			#[must_use]
+			pub const fn is_empty(&self) -> bool {
+				self.systems.is_empty()
+			}
+			
+			/// Returns the indices of the systems in each stage, in the order they run
+			#[must_use]
+			pub fn stages(&self) -> Vec< Vec<usize> >
+			{
+				let mut stages = vec![vec![]; self.stages];
+				for (idx, scheduled) in self.systems.iter().enumerate() {
+					stages[scheduled.stage].push(idx);
+				}
+				
+				stages
+			}
+			
+			/// Returns all conflicts between systems of this schedule
+			/// 
+			/// Conflicting systems never run in the same stage.
+			#[must_use]
+			pub fn conflicts(&self) -> Vec< Conflict<'_, S::Id> >
+			{
+				let mut conflicts = vec![];
+				for (lhs_idx, lhs) in self.systems.iter().enumerate() {
+					for (rhs_idx, rhs) in self.systems.iter().enumerate().skip(lhs_idx).skip(1) {
+						let ids = lhs.access.conflicts(&rhs.access);
+						if !ids.is_empty() {
+							conflicts.push( Conflict{ systems: (lhs_idx, rhs_idx), ids } );
+						}
+					}
+				}
+				
+				conflicts
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Run
+		//--------------------------------------------------------------------------------------------------
+			/// Runs all stages in order, initializing any systems that weren't yet
+			/// 
+			/// Once all stages have run, the world's change tick is
+			/// incremented, see [`World::increment_change_tick`].
+			/// 
+			/// # Panics
+			/// Panics if any system panics.
+			pub fn run(&mut self, world: &mut World<'a, S, B>)
+			where
+				S    : Send + Sync,
+				S::Id: Send + Sync,
+				for<'w> BackendView<'a, 'w, S, B>: Send,
+			{
+				// Initialize all systems running for the first time
+				for scheduled in self.systems.iter_mut().filter(|scheduled| !scheduled.initialized) {
+					scheduled.system.init(world);
+					scheduled.initialized = true;
+				}
+				
+				// Then run each stage
+				for stage in 0..self.stages {
+					// Split the world between all systems in this stage
+					let (mut systems, accesses): (Vec<_>, Vec<_>) = self.systems.iter_mut()
+						.filter(|scheduled| scheduled.stage == stage)
+						.map(|scheduled| (&mut scheduled.system, &scheduled.access))
+						.unzip();
+					let mut views = world.views(&accesses);
+					
+					// If there's a single system, run it on this thread, else run each one on it's own thread
+					match (&mut systems[..], &mut views[..]) {
+						([system], [view]) => system.run(view),
+						(systems, views) => std::thread::scope(|scope| {
+							for (system, view) in systems.iter_mut().zip(views) {
+								scope.spawn(move || system.run(view));
+							}
+						}),
+					}
+				}
+				
+				// Then start the next tick
+				world.increment_change_tick();
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl<'a, S, B> Default for ParSchedule<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		fn default() -> Self {
+			Self::new()
+		}
+	}
+//--------------------------------------------------------------------------------------------------
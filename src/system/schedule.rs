@@ -0,0 +1,124 @@
+//! Schedules of systems
+//! 
+//! A schedule owns a list of systems and runs all of them,
+//! in the order they were added, each time it's run.
+//! Each run of a schedule is a single tick of the world,
+//! see [`crate::tick`].
+
+// Crate
+use crate::{util::KeyType, Storage, World, world::{Backend, Entities}};
+use super::{System, Access};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A list of systems, run in order
+	/// 
+	/// # Predicates
+	/// Systems are initialized the first time the schedule is run,
+	/// which is when they register their predicates, so a schedule
+	/// should always be run on the same world.
+	pub struct Schedule<'a, S, B = Entities<'a, S>>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		/// All systems, in the order they run
+		systems: Vec< ScheduledSystem<'a, S, B> >,
+	}
+	
+	/// A system within a schedule
+	struct ScheduledSystem<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		/// The system
+		system: Box<dyn System<'a, S, B> + 'a>,
+		
+		/// If the system has been initialized
+		initialized: bool,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S, B> Schedule<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty schedule
+			#[must_use]
+			pub const fn new() -> Self {
+				Self { systems: vec![] }
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Systems
+		//--------------------------------------------------------------------------------------------------
+			/// Adds a system to the end of this schedule
+			pub fn add<T>(&mut self, system: T)
+			where
+				T: System<'a, S, B> + 'a
+			{
+				self.systems.push( ScheduledSystem{ system: Box::new(system), initialized: false } );
+			}
+			
+			/// Returns the number of systems in this schedule
+			#[must_use]
+			pub const fn len(&self) -> usize {
+				self.systems.len()
+			}
+			
+			/// Checks if this schedule has no systems
+			#[must_use]
+			pub const fn is_empty(&self) -> bool {
+				self.systems.is_empty()
+			}
+			
+			/// Returns an iterator over the access of all systems, in the order they run
+			pub fn access(&self) -> impl Iterator<Item = Access<'a, S>> + '_ {
+				self.systems.iter().map(|scheduled| scheduled.system.access())
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Run
+		//--------------------------------------------------------------------------------------------------
+			/// Runs all systems in order, initializing any that weren't yet
+			/// 
+			/// Once all systems have run, the world's change tick is
+			/// incremented, see [`World::increment_change_tick`].
+			pub fn run(&mut self, world: &mut World<'a, S, B>)
+			{
+				for scheduled in &mut self.systems {
+					// Initialize it if this is it's first run
+					if !scheduled.initialized {
+						scheduled.system.init(world);
+						scheduled.initialized = true;
+					}
+					
+					scheduled.system.run(world);
+				}
+				
+				// Then start the next tick
+				world.increment_change_tick();
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl<'a, S, B> Default for Schedule<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		fn default() -> Self {
+			Self::new()
+		}
+	}
+//--------------------------------------------------------------------------------------------------
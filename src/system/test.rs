@@ -0,0 +1,276 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{World, WorldView, Filter, PredId, System, ParSystem, Access, Schedule, ParSchedule, Tick};
+
+// Cell
+use std::{rc::Rc, cell::Cell};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type
+		#[derive(PartialEq, Clone, Debug)]
+		enum Components {
+			A(i32),
+			B(&'static str),
+			C(f32),
+		}
+	
+	}
+	
+	/// Adds the `f32` of every entity to it's `i32`
+	#[derive(Default)]
+	struct AddC {
+		/// Our predicate
+		pred_id: Option< PredId<Components> >,
+		
+		/// Number of times we were initialized
+		inits: Rc< Cell<usize> >,
+	}
+	
+	/// Doubles every `i32`
+	struct Double;
+	
+	/// Sets every `f32` to it's entity's `i32`
+	struct CopyA;
+	
+	/// Adds one to every `i32` with a `&str`
+	struct IncrementNamed;
+	
+	/// Sets every `&str`
+	struct Rename(&'static str);
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a> System<'a, Components> for AddC
+	{
+		fn access(&self) -> Access<'a, Components> {
+			Access::new().write::<i32>().read::<f32>()
+		}
+		
+		#[allow(clippy::arithmetic_side_effects)] // We're never initialized enough times to overflow
+		fn init(&mut self, world: &mut World<'a, Components>) {
+			self.pred_id = Some( world.add_filter( Filter::has::<i32>().and( Filter::has::<f32>() ) ) );
+			self.inits.set( self.inits.get() + 1 );
+		}
+		
+		#[allow(clippy::arithmetic_side_effects, clippy::as_conversions, clippy::cast_possible_truncation)] // We only use small values
+		fn run(&mut self, world: &mut World<'a, Components>) {
+			world.iter_pred_mut( self.pred_id.unwrap() ).unwrap().for_each(|mut entity, _| {
+				let c = *entity.get::<f32>().unwrap();
+				*entity.get_mut::<i32>().unwrap() += c as i32;
+			});
+		}
+	}
+	
+	impl<'a> System<'a, Components> for Double
+	{
+		fn access(&self) -> Access<'a, Components> {
+			Access::new().write::<i32>()
+		}
+		
+		#[allow(clippy::arithmetic_side_effects)] // We only use small values
+		fn run(&mut self, world: &mut World<'a, Components>) {
+			for (_, num) in world.query::<&mut i32>() {
+				*num *= 2;
+			}
+		}
+	}
+	
+	impl<'a> ParSystem<'a, Components> for CopyA
+	{
+		fn access(&self) -> Access<'a, Components> {
+			Access::new().read::<i32>().write::<f32>()
+		}
+		
+		#[allow(clippy::as_conversions, clippy::cast_precision_loss)] // We only use small values
+		fn run(&mut self, view: &mut WorldView<'_, 'a, Components>) {
+			for (_, (&num, scale)) in view.query::<(&i32, &mut f32)>() {
+				*scale = num as f32;
+			}
+		}
+	}
+	
+	impl<'a> ParSystem<'a, Components> for IncrementNamed
+	{
+		fn access(&self) -> Access<'a, Components> {
+			Access::new().write::<i32>().read::<&str>()
+		}
+		
+		#[allow(clippy::arithmetic_side_effects)] // We only use small values
+		fn run(&mut self, view: &mut WorldView<'_, 'a, Components>) {
+			for (_, (num, _)) in view.query::<(&mut i32, &&str)>() {
+				*num += 1;
+			}
+		}
+	}
+	
+	impl<'a> ParSystem<'a, Components> for Rename
+	{
+		fn access(&self) -> Access<'a, Components> {
+			Access::new().write::<&str>()
+		}
+		
+		fn run(&mut self, view: &mut WorldView<'_, 'a, Components>) {
+			for (_, name) in view.query::<&mut &str>() {
+				*name = self.0;
+			}
+		}
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn access()
+	{
+		let access: Access<Components> = Access::new()
+			.read::<i32>()
+			.write::<&str>()
+			.read::<i32>();
+		
+		assert_eq!(access.reads(), [0]);
+		assert_eq!(access.writes(), [1]);
+		assert!(access.reads_id(&0));
+		assert!(access.reads_id(&1));
+		assert!(!access.reads_id(&2));
+		assert!(!access.writes_id(&0));
+		assert!(!access.is_read_only());
+	}
+	
+	#[test]
+	fn run_in_order()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1), Components::C(2.0) ] );
+		let id2 = world.add( mecs::entity![ Components::A(1), Components::B("2") ] );
+		
+		let mut schedule = Schedule::new();
+		schedule.add( AddC::default() );
+		schedule.add( Double );
+		assert_eq!(schedule.len(), 2);
+		
+		schedule.run(&mut world);
+		assert_eq!(world[id1].get::<i32>(), Some(&6));
+		assert_eq!(world[id2].get::<i32>(), Some(&2));
+		assert_eq!(world.change_tick(), Tick::new(2));
+		
+		// Entities added after the first run should still be picked up by the predicate
+		let id3 = world.add( mecs::entity![ Components::A(0), Components::C(1.0) ] );
+		
+		schedule.run(&mut world);
+		assert_eq!(world[id1].get::<i32>(), Some(&16));
+		assert_eq!(world[id2].get::<i32>(), Some(&4));
+		assert_eq!(world[id3].get::<i32>(), Some(&2));
+		assert_eq!(world.change_tick(), Tick::new(3));
+	}
+	
+	#[test]
+	fn init_once()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let system = AddC::default();
+		let inits = Rc::clone(&system.inits);
+		
+		let mut schedule = Schedule::new();
+		schedule.add(system);
+		
+		for _ in 0..3 {
+			schedule.run(&mut world);
+		}
+		assert_eq!(inits.get(), 1);
+	}
+	
+	#[test]
+	fn conflicts()
+	{
+		let nums      : Access<Components> = Access::new().write::<i32>();
+		let nums_names: Access<Components> = Access::new().read::<i32>().read::<&str>();
+		let names     : Access<Components> = Access::new().write::<&str>().read::<i32>();
+		
+		assert_eq!(nums.conflicts(&nums_names), [&0]);
+		assert_eq!(nums_names.conflicts(&names), [&1]);
+		assert_eq!(nums.conflicts(&names), [&0]);
+		assert!(nums_names.conflicts(&nums_names).is_empty());
+	}
+	
+	#[test]
+	fn par_stages()
+	{
+		let mut schedule: ParSchedule<Components> = ParSchedule::new();
+		schedule.add( CopyA );
+		schedule.add( Rename("1") );
+		schedule.add( IncrementNamed );
+		schedule.add( Rename("2") );
+		
+		// `IncrementNamed` must run after `CopyA` and `Rename("1")`, and `Rename("2")` after it.
+		assert_eq!(schedule.stages(), [ vec![0, 1], vec![2], vec![3] ]);
+		
+		let conflicts: Vec<_> = schedule.conflicts().into_iter().map(|conflict| conflict.systems).collect();
+		assert_eq!(conflicts, [ (0, 2), (1, 2), (1, 3), (2, 3) ]);
+	}
+	
+	#[test]
+	fn par_run()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1), Components::B("1"), Components::C(0.0) ] );
+		let id2 = world.add( mecs::entity![ Components::A(2), Components::C(0.0) ] );
+		let id3 = world.add( mecs::entity![ Components::B("3") ] );
+		
+		let mut schedule = ParSchedule::new();
+		schedule.add( CopyA );
+		schedule.add( Rename("renamed") );
+		schedule.add( IncrementNamed );
+		
+		schedule.run(&mut world);
+		assert_eq!(world[id1].get::<i32>(), Some(&2));
+		assert_eq!(world[id1].get::<f32>(), Some(&1.0));
+		assert_eq!(world[id1].get::<&str>(), Some(&"renamed"));
+		assert_eq!(world[id2].get::<i32>(), Some(&2));
+		assert_eq!(world[id2].get::<f32>(), Some(&2.0));
+		assert_eq!(world[id3].get::<&str>(), Some(&"renamed"));
+		assert_eq!(world.change_tick(), Tick::new(2));
+		
+		// Changes should be tracked through views
+		assert!( world[id1].changed::<i32>(Tick::new(0)));
+		assert!(!world[id1].changed::<i32>(Tick::new(1)));
+	}
+	
+	#[test]
+	fn world_is_sync()
+	{
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<World<Components>>();
+	}
+	
+	#[test]
+	#[should_panic(expected = "Query wrote a component not written by the view")]
+	fn view_access()
+	{
+		let mut world: World<Components> = World::new();
+		world.add( mecs::entity![ Components::A(1) ] );
+		
+		let access = Access::new().read::<i32>();
+		let mut views = world.views(&[&access]);
+		let _ = views[0].query::<&mut i32>().count();
+	}
+	
+	#[test]
+	#[should_panic(expected = "Views may not have conflicting accesses")]
+	fn view_conflicts()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let lhs = Access::new().read::<i32>();
+		let rhs = Access::new().write::<i32>();
+		let _ = world.views(&[&lhs, &rhs]);
+	}
+//--------------------------------------------------------------------------------------------------
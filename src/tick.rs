@@ -0,0 +1,114 @@
+//! Change detection ticks
+//! 
+//! Each world has a tick counter, which is only increased
+//! when asked to with [`World::increment_change_tick`](crate::World::increment_change_tick).
+//! Every component keeps track of the tick it was added
+//! in and the tick it was last mutably accessed in, so that
+//! systems may only process components that changed since
+//! they last ran.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{World, DynStorage};
+//! let mut world = World::new();
+//! 
+//! let id1 = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+//! let id2 = world.add( mecs::entity![ DynStorage::new(8i32) ] );
+//! 
+//! // Start a new tick
+//! let last_tick = world.change_tick();
+//! world.increment_change_tick();
+//! 
+//! *world.get_mut(id1).unwrap().get_mut::<i32>().unwrap() += 1;
+//! 
+//! assert!( world[id1].changed::<i32>(last_tick));
+//! assert!(!world[id2].changed::<i32>(last_tick));
+//! assert!(!world[id1].added  ::<i32>(last_tick));
+//! ```
+//! 
+//! # Wrapping
+//! Ticks wrap around once they reach `u32::MAX`, so ticks are compared
+//! by their distance instead of their value, and a tick is only newer
+//! than the `u32::MAX / 2` ticks before it. Components not changed for
+//! longer than that may appear as changed again.
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A change detection tick
+	/// 
+	/// As ticks wrap around, they aren't ordered, see [`Tick::is_newer_than`].
+	#[derive(PartialEq, Eq, Clone, Copy, Hash, Default, Debug)]
+	pub struct Tick(u32);
+	
+	/// The ticks of a single component
+	#[derive(PartialEq, Eq, Clone, Copy, Hash, Default, Debug)]
+	pub struct ComponentTicks
+	{
+		/// The tick the component was added in
+		pub added: Tick,
+		
+		/// The tick the component was last mutably accessed in
+		pub changed: Tick,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl Tick
+	{
+		/// Creates a tick from it's value
+		#[must_use]
+		pub const fn new(tick: u32) -> Self {
+			Self(tick)
+		}
+		
+		/// Returns the value of this tick
+		#[must_use]
+		pub const fn get(self) -> u32 {
+			self.0
+		}
+		
+		/// Returns the tick after this one, wrapping around
+		#[must_use]
+		pub const fn next(self) -> Self {
+			Self( self.0.wrapping_add(1) )
+		}
+		
+		/// Checks if this tick is newer than `other`
+		/// 
+		/// This is the case if this tick is at most `u32::MAX / 2` ticks after
+		/// `other`, which holds even if the tick counter wrapped around in between.
+		#[must_use]
+		pub const fn is_newer_than(self, other: Self) -> bool
+		{
+			let distance = self.0.wrapping_sub(other.0);
+			distance != 0 && distance <= u32::MAX / 2
+		}
+	}
+	
+	impl ComponentTicks
+	{
+		/// Creates the ticks of a component added in `tick`
+		#[must_use]
+		pub const fn new(tick: Tick) -> Self {
+			Self { added: tick, changed: tick }
+		}
+		
+		/// Checks if the component was added after `since`
+		#[must_use]
+		pub const fn is_added(self, since: Tick) -> bool {
+			self.added.is_newer_than(since)
+		}
+		
+		/// Checks if the component was changed after `since`
+		#[must_use]
+		pub const fn is_changed(self, since: Tick) -> bool {
+			self.changed.is_newer_than(since)
+		}
+	}
+//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,34 @@
+//! Tests
+
+// Crate
+use crate::{Tick, ComponentTicks};
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn newer()
+	{
+		let tick = Tick::new(5);
+		
+		assert!( tick.next().is_newer_than(tick));
+		assert!(!tick.is_newer_than( tick.next() ));
+		assert!(!tick.is_newer_than(tick));
+	}
+	
+	#[test]
+	fn wrap()
+	{
+		let last_tick = Tick::new(u32::MAX);
+		let cur_tick = last_tick.next();
+		assert_eq!(cur_tick, Tick::new(0));
+		
+		// Ticks after the counter wrapped around are still newer
+		assert!( cur_tick.is_newer_than(last_tick));
+		assert!(!last_tick.is_newer_than(cur_tick));
+		assert!( ComponentTicks::new(cur_tick).is_changed(last_tick));
+		
+		// Up until they're half the counter apart
+		assert!( Tick::new(u32::MAX / 2).is_newer_than( Tick::new(0) ));
+		assert!(!Tick::new(u32::MAX / 2 + 1).is_newer_than( Tick::new(0) ));
+	}
+//--------------------------------------------------------------------------------------------------
@@ -7,6 +7,10 @@ use std::hash::Hash;
 //--------------------------------------------------------------------------------------------------
 	/// A key type for [`HashMap`]
 	pub trait KeyType: PartialEq + Eq + Hash {}
+	
+	/// A key type that may also be sorted and copied around,
+	/// used for archetype signatures
+	pub trait SortedKeyType: KeyType + Ord + Clone {}
 //--------------------------------------------------------------------------------------------------
 
 // Impl
@@ -15,4 +19,9 @@ use std::hash::Hash;
 	where
 		T: ?Sized + PartialEq + Eq + Hash
 	{}
+	
+	impl<T> SortedKeyType for T
+	where
+		T: KeyType + Ord + Clone
+	{}
 //--------------------------------------------------------------------------------------------------
@@ -41,34 +41,67 @@
 
 // Modules
 pub mod entity_id;
+pub mod pred_id;
 pub mod iter;
+pub mod query;
+pub mod filter;
+pub mod archetype;
+pub mod sparse;
+pub mod view;
+pub mod commands;
+pub mod resources;
+pub mod events;
+pub mod map_entities;
+pub mod backend;
+pub mod entities;
     mod pred;
+    mod observers;
 
 // Exports
-pub use entity_id::EntityId;
+pub use entity_id::{EntityId, EntityIdRange};
+    use entity_id::EntityAllocator;
+pub use pred_id  ::PredId;
 pub use iter     ::{PredIter, PredIterMut};
-    use pred     ::{Predicate, PredicateIds};
+pub use query    ::{Query, QueryIter, Changed, Added};
+pub use filter   ::{Filter, FilterMask};
+pub use archetype::ArchetypeWorld;
+pub use sparse   ::SparseWorld;
+pub use view     ::{WorldView, ViewQueryIter};
+pub use commands ::Commands;
+pub use resources::Resources;
+pub use events   ::{Events, EventReader, Spawned, Despawned};
+pub use map_entities::MapEntities;
+pub use backend  ::{Backend, BackendRefs, BackendRef, BackendMut, BackendView, ReadEntity, Fetch, View};
+pub use entities ::{Entities, EntityMut};
+pub use pred     ::PredicateIds;
+    use pred     ::Predicates;
+    use observers::Observers;
 
 // Collections
 use std::collections::HashMap;
 
+// Marker
+use std::marker::PhantomData;
+
 // Traits
 use std::iter::Iterator;
 
-// Cell
-use std::cell::Cell;
-
 // Crate
-use crate::{KeyType, Storage, Entity};
+use crate::{KeyType, util::SortedKeyType, Storage, Component, Entity, Tick};
+
+// Any
+use std::any::{Any, TypeId};
 
 // Types
 //--------------------------------------------------------------------------------------------------
 	/// Collection of entities
 	/// 
 	/// # Id
-	/// Each entity has it's own unique id, that will
-	/// not be re-used even after the entity is removed
-	/// from this world.
+	/// Each entity has it's own unique id, made of a slot
+	/// index and a generation. Once an entity is removed,
+	/// it's slot may be reused by a new entity, but with a
+	/// new generation, so the old id will no longer be alive.
+	/// See [`World::is_alive`].
 	/// The entity id 0 is reserved to mean a null entity,
 	/// that is, an entity which does not exist.
 	/// 
@@ -76,27 +109,69 @@ use crate::{KeyType, Storage, Entity};
 	/// Iteration is done through either global iteration
 	/// using [`World::iter_all`] or [`World::iter_all_mut`],
 	/// although for most systems you would first register a
-	/// predicate with [`World::add_predicate`] and then iterate
+	/// predicate with [`World::add_filter`] or [`World::add_pred`] and then iterate
 	/// over it using the predicate id with either [`World::iter_pred`]
 	/// or [`World::iter_pred_mut`]
-	pub struct World<'a, S>
+	/// 
+	/// # Change detection
+	/// The world keeps a tick counter, which is used to mark
+	/// when components are added and changed, see [`crate::tick`].
+	/// Components are marked as changed whenever they're mutably
+	/// accessed through an entity borrowed mutably from the world.
+	/// 
+	/// # Resources
+	/// Besides entities, the world stores resources, singleton values
+	/// identified by their type, see [`World::insert_resource`].
+	/// As they're stored without knowing their type, resources
+	/// aren't included when serializing the world.
+	/// 
+	/// # Serialization
+	/// With the `serde-serialize` feature, worlds may be serialized
+	/// along with the ids of all entities, which stay the same once
+	/// deserialized. To load a world into an existing one, deserialize
+	/// it on it's own and then move it with [`World::merge`].
+	/// 
+	/// # Threads
+	/// As long as it's storage is, the world is both `Send` and `Sync`,
+	/// so it may be iterated over from multiple threads at once.
+	/// See [`World::views`] for writing to it from multiple threads.
+	/// 
+	/// # Backends
+	/// The components of all entities are stored by a backend, which
+	/// may be switched by changing the last type parameter, see [`backend`].
+	/// The api of the world is the same for every backend, except for
+	/// borrowing entities as an [`Entity`], which only the default backend,
+	/// [`Entities`], supports. Entities borrowed mutably are an [`EntityMut`] instead,
+	/// so components are only ever added or removed through the world.
+	pub struct World<'a, S, B = Entities<'a, S>>
 	where
 		S    : Storage<'a>,
 		S::Id: KeyType,
+		B    : Backend<'a, S>,
 	{
-		/// All of the entities, stored by their entity id
-		pub(in self) entities: HashMap<EntityId, Entity<'a, S>>,
+		/// All of the entities
+		pub(in self) entities: B,
 		
-		/// The next entity id to use when adding entities
-		next_entity_id: EntityId,
+		/// The entity id allocator
+		ids: EntityAllocator,
 		
+		/// The current change tick
+		change_tick: Tick,
 		
+		/// All of the predicates
+		pub(in self) predicates: Predicates<'a, S, B>,
 		
-		/// All of the predicate ids, stored by their predicate id
-		pub(in self) predicates: HashMap<usize, PredicateIds<'a, S>>,
+		/// The predicates of all queries, stored by their required component ids
+		queries: HashMap<Vec<S::Id>, PredId<S>>,
 		
-		/// The next predicate id to use when adding predicates
-		next_pred_id: usize,
+		/// All resources
+		resources: Resources,
+		
+		/// Updates for each registered type of event, stored by their type id
+		event_updates: HashMap<TypeId, fn(&mut Resources)>,
+		
+		/// All component observers
+		observers: Observers<'a, S>,
 	}
 //--------------------------------------------------------------------------------------------------
 
@@ -105,12 +180,14 @@ use crate::{KeyType, Storage, Entity};
 	impl<'a, S> World<'a, S>
 	where
 		S    : Storage<'a>,
-		S::Id: KeyType,
+		S::Id: KeyType + 'a,
 	{
 		// Constructors
 		//--------------------------------------------------------------------------------------------------
 			/// Creates a new world with no entities and no predicates
 			/// 
+			/// Worlds with any other backend are created with [`World::default`].
+			/// 
 			/// # Example
 			/// 
 			/// ```rust
@@ -120,16 +197,8 @@ use crate::{KeyType, Storage, Entity};
 			/// assert_eq!(world.iter_all().count(), 0);
 			/// ```
 			#[must_use]
-			pub fn new() -> Self
-			{
-				// Note: Ids start at 1 so 0 can be a null id
-				Self {
-					entities: HashMap::new(),
-					next_entity_id : EntityId::new(1),
-					
-					predicates: HashMap::new(),
-					next_pred_id: 1,
-				}
+			pub fn new() -> Self {
+				Self::default()
 			}
 			
 			/// Creates a new world from any iterator over entities
@@ -160,15 +229,34 @@ use crate::{KeyType, Storage, Entity};
 				let mut world = Self::new();
 				
 				// Move all entities into it
-				for entity in entities {
-					world.add(entity);
-				}
+				world.spawn_batch(entities);
 				
 				// And return it
 				world
 			}
 		//--------------------------------------------------------------------------------------------------
 		
+		// Iterators
+		//--------------------------------------------------------------------------------------------------
+			/// Returns a mutable iterator over all entities in this world
+			pub fn iter_all_mut(&mut self) -> impl Iterator<Item = EntityMut<'a, '_, S>> {
+				self.entities.iter_mut(self.change_tick)
+			}
+			
+			/// Returns a mutable iterator over all entities in this world
+			#[deprecated(since = "0.2.0", note = "Use `iter_all_mut` instead")]
+			pub fn iter_mut_all(&mut self) -> impl Iterator<Item = EntityMut<'a, '_, S>> {
+				self.iter_all_mut()
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl<'a, S, B> World<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
 		// Add / Remove
 		//--------------------------------------------------------------------------------------------------
 			/// Adds an entity to this world
@@ -180,6 +268,8 @@ use crate::{KeyType, Storage, Entity};
 			/// the world is done with predicates, (see [`World::add_pred`]
 			/// and [`World::iter_pred`]).
 			/// 
+			/// If registered, a [`Spawned`] event is sent with the id of the entity.
+			/// 
 			/// # Example
 			/// 
 			/// ```rust
@@ -196,31 +286,196 @@ use crate::{KeyType, Storage, Entity};
 			pub fn add(&mut self, entity: Entity<'a, S>) -> EntityId
 			{
 				// Get the id to use for this entity
-				// and increase the next id
-				let id = self.next_entity_id;
-				self.next_entity_id.inc();
+				// Note: We need to create all reserved entities first, so their slots aren't given out.
+				self.flush_reserved();
+				let id = self.ids.alloc();
 				
-				// Update each predicate
-				for PredicateIds { pred, ids } in self.predicates.values_mut()
-				{
-					// Get rid of any nulls in-place
-					ids.retain(|id| !id.get().is_null());
-					
-					// And add the id we have
-					if pred(&entity) {
-						ids.push( Cell::new(id) )
-					}
+				// Then insert it
+				self.insert_entity(id, entity);
+				id
+			}
+			
+			/// Adds a batch of entities to this world
+			/// 
+			/// This is equivalent to calling [`World::add`] for each entity,
+			/// but each predicate is only evaluated once for the whole batch,
+			/// which is considerably faster when adding many entities at once.
+			/// 
+			/// # Return value
+			/// All entities are given contiguous ids, in the order they
+			/// were yielded, which are returned as a range.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// let pred_id = world.add_pred(|entity| entity.has::<usize>());
+			/// 
+			/// let ids = world.spawn_batch( (0_usize..100).map(|num| mecs::entity![ DynStorage::new(num) ]) );
+			/// assert_eq!(ids.len(), 100);
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 100);
+			/// 
+			/// for (num, id) in ids.enumerate() {
+			/// 	assert_eq!(world[id].get::<usize>(), Some(&num));
+			/// }
+			/// ```
+			pub fn spawn_batch<I>(&mut self, entities: I) -> EntityIdRange
+			where
+				I: IntoIterator<Item = Entity<'a, S>>
+			{
+				let entities: Vec<_> = entities.into_iter().collect();
+				
+				// Note: We need to create all reserved entities first, so their slots aren't given out.
+				self.flush_reserved();
+				let ids = self.ids.alloc_range( entities.len() );
+				
+				self.insert_batch( ids.clone().zip(entities).collect() );
+				ids
+			}
+			
+			/// Adds a batch of entities to this world, reusing the slots of removed entities
+			/// 
+			/// This is the same as [`World::spawn_batch`], but the ids of the entities
+			/// aren't contiguous, so they may reuse the slots of any removed entities.
+			/// 
+			/// # Return value
+			/// Returns the ids of all entities, in the order they were yielded.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// 
+			/// let id = world.add( mecs::entity![ DynStorage::new(0_usize) ] );
+			/// world.remove(id);
+			/// 
+			/// let ids = world.add_batch( (1_usize..=2).map(|num| mecs::entity![ DynStorage::new(num) ]) );
+			/// assert_eq!(ids[0].idx(), id.idx());
+			/// assert_eq!(world[ids[1]].get::<usize>(), Some(&2));
+			/// ```
+			pub fn add_batch<I>(&mut self, entities: I) -> Vec<EntityId>
+			where
+				I: IntoIterator<Item = Entity<'a, S>>
+			{
+				let entities: Vec<_> = entities.into_iter().collect();
+				let ids = self.alloc_batch( entities.len() );
+				self.insert_batch( ids.iter().copied().zip(entities).collect() );
+				ids
+			}
+			
+			/// Allocates the ids of a batch of entities, reusing free slots
+			fn alloc_batch(&mut self, len: usize) -> Vec<EntityId>
+			{
+				// Note: We need to create all reserved entities first, so their slots aren't given out.
+				self.flush_reserved();
+				self.ids.alloc_many(len)
+			}
+			
+			/// Inserts a batch of entities given their newly allocated ids
+			fn insert_batch(&mut self, mut entities: Vec<( EntityId, Entity<'a, S> )>)
+			{
+				// Insert them, with all of their components marked as added now
+				let ids: Vec<EntityId> = entities.iter().map(|&(id, _)| id).collect();
+				for (_, entity) in &mut entities {
+					entity.change_tick = self.change_tick;
+					entity.reset_ticks();
 				}
+				self.entities.insert_batch(entities);
 				
-				// Insert the entity
+				// Add them to all predicates they match
+				let all_entities = &self.entities;
+				let batch = ids.iter()
+					.filter_map(|&id| all_entities.get(id).map(|entity| (id, entity)));
+				self.predicates.add_entities(batch);
+				
+				// And let everyone know they were added
+				for id in ids {
+					self.observe_spawn(id);
+					if let Some(events) = self.resources.get_mut::<Events<Spawned>>() {
+						events.send( Spawned(id) );
+					}
+				}
+			}
+			
+			/// Reserves the id of a new entity
+			/// 
+			/// As this only requires a shared reference, ids may be reserved
+			/// from multiple threads at once, for example, while the world is
+			/// split between parallel systems.
+			/// 
+			/// The id is only alive once the entity is created, with no components, by
+			/// [`World::flush_reserved`], which is called whenever entities are added
+			/// or commands are applied.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// 
+			/// let id = world.reserve_entity();
+			/// assert!(!world.is_alive(id));
+			/// 
+			/// world.flush_reserved();
+			/// world.insert_component(id, DynStorage::new(5i32)).unwrap();
+			/// assert_eq!(world[id].get::<i32>(), Some(&5));
+			/// ```
+			#[must_use]
+			pub fn reserve_entity(&self) -> EntityId {
+				self.ids.reserve()
+			}
+			
+			/// Reserves the contiguous ids of `len` new entities
+			/// 
+			/// See [`World::reserve_entity`] for when the ids become alive.
+			#[must_use]
+			pub fn reserve_entities(&self, len: usize) -> EntityIdRange {
+				self.ids.reserve_range(len)
+			}
+			
+			/// Creates an empty entity for every reserved id
+			/// 
+			/// See [`World::reserve_entity`].
+			pub fn flush_reserved(&mut self)
+			{
+				for id in self.ids.flush() {
+					self.insert_entity(id, Entity::new());
+				}
+			}
+			
+			/// Inserts an entity given it's newly allocated id
+			fn insert_entity(&mut self, id: EntityId, mut entity: Entity<'a, S>)
+			{
+				// Mark all of it's components as added now and insert it
+				entity.change_tick = self.change_tick;
+				entity.reset_ticks();
 				self.entities.insert(id, entity);
 				
-				// And return it's id
-				id
+				// Add the id to each predicate it matches
+				let entity = self.entities.get(id).expect("Inserted entity didn't exist");
+				self.predicates.add_entity(id, entity);
+				
+				// And let everyone know it was added
+				self.observe_spawn(id);
+				if let Some(events) = self.resources.get_mut::<Events<Spawned>>() {
+					events.send( Spawned(id) );
+				}
 			}
 			
 			/// Removes an entity from this world given it's id
 			/// 
+			/// Once removed, the id is no longer alive and it's
+			/// slot may be reused by the next entity added.
+			/// 
+			/// If registered, a [`Despawned`] event is sent with the id of the entity.
+			/// 
+			/// # Return value
+			/// Returns the entity removed, or `None` if it didn't exist. Ids reserved
+			/// but not yet created are freed without an entity, so they return `None`
+			/// and no event is sent.
+			/// 
 			/// # Example
 			/// 
 			/// ```rust
@@ -234,11 +489,161 @@ use crate::{KeyType, Storage, Entity};
 			/// world.remove(id);
 			/// 
 			/// assert_eq!(world.iter_all().count(), 0);
+			/// assert!(!world.is_alive(id));
 			/// ```
 			pub fn remove(&mut self, id: EntityId) -> Option< Entity<'a, S> >
 			{
-				// And remove the entity
-				self.entities.remove(&id)
+				// Free the id, if it's alive
+				if !self.ids.free(id) {
+					return None;
+				}
+				
+				// Remove it from all predicates
+				self.predicates.remove_entity(id);
+				
+				// Then remove the entity
+				// Note: Ids reserved while allocating are alive before their entity is
+				//       created, so we only free them, as there's no entity to remove.
+				let entity = self.entities.remove(id)?;
+				
+				// And let everyone know it was removed
+				for storage in entity.components() {
+					self.observers.removed(id, storage);
+				}
+				if let Some(events) = self.resources.get_mut::<Events<Despawned>>() {
+					events.send( Despawned(id) );
+				}
+				
+				Some(entity)
+			}
+			
+			/// Adds a component to an existing entity, updating all predicates
+			/// 
+			/// # Return value
+			/// If the entity already contains a component with
+			/// the same id as `storage`, it is replaced and returned.
+			/// 
+			/// # Errors
+			/// If no entity with the id `id` exists, `storage` is returned back.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// 
+			/// let pred_id = world.add_pred(|entity| entity.has::<&str>());
+			/// let id = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 0);
+			/// 
+			/// assert!(world.insert_component(id, DynStorage::new("Hello, World!")).is_ok());
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 1);
+			/// 
+			/// world.remove(id);
+			/// assert!(world.insert_component(id, DynStorage::new(8i32)).is_err());
+			/// ```
+			pub fn insert_component(&mut self, id: EntityId, storage: S) -> Result<Option<S>, S>
+			{
+				let component_id = storage.id();
+				let replaced = self.entities.insert_component(id, storage, self.change_tick)?;
+				
+				self.observe_insert(id, &component_id, replaced.as_ref());
+				self.update_component_preds(id, &component_id);
+				
+				Ok(replaced)
+			}
+			
+			/// Removes a component from an existing entity given it's type, updating all predicates
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// 
+			/// let pred_id = world.add_pred(|entity| entity.has::<&str>());
+			/// let id = world.add( mecs::entity![ DynStorage::new(5i32), DynStorage::new("Hello, World!") ] );
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 1);
+			/// 
+			/// assert!(world.remove_component::<&str>(id).is_some());
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 0);
+			/// ```
+			pub fn remove_component<C: Component<'a, S>>(&mut self, id: EntityId) -> Option<S>
+			{
+				self.remove_component_id( id, &C::id() )
+			}
+			
+			/// Removes a component from an existing entity given it's id, updating all predicates
+			/// 
+			/// # Return value
+			/// Returns the component removed, or `None` if either the entity
+			/// or the component doesn't exist.
+			pub fn remove_component_id(&mut self, id: EntityId, component_id: &S::Id) -> Option<S>
+			{
+				let component = self.entities.remove_component(id, component_id)?;
+				
+				self.observers.removed(id, &component);
+				self.update_component_preds(id, component_id);
+				
+				Some(component)
+			}
+			
+			/// Re-evaluates every predicate on an entity
+			/// 
+			/// This is only required for predicates that depend on the values
+			/// of components, after changing them through [`World::get_mut`], as
+			/// adding or removing components already updates all predicates.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// 
+			/// let pred_id = world.add_pred(|entity| entity.get::<i32>() == Some(&8));
+			/// let id = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+			/// 
+			/// *world.get_mut(id).unwrap().get_mut::<i32>().unwrap() = 8;
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 0);
+			/// 
+			/// world.update_preds(id);
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 1);
+			/// ```
+			pub fn update_preds(&mut self, id: EntityId)
+			{
+				if let Some(entity) = self.entities.get(id) {
+					self.predicates.update_entity(id, entity);
+				}
+			}
+			
+			/// Notifies all observers of every component of a newly added entity
+			fn observe_spawn(&mut self, id: EntityId)
+			{
+				if let Some(entity) = self.entities.get(id) {
+					for storage in entity.components() {
+						self.observers.added(id, storage);
+					}
+				}
+			}
+			
+			/// Notifies all observers of a component inserted into an entity, possibly replacing another
+			fn observe_insert(&mut self, id: EntityId, component_id: &S::Id, replaced: Option<&S>)
+			{
+				let storage = self.entities.get(id).and_then(|entity| entity.get_id(component_id));
+				
+				match (storage, replaced) {
+					(Some(storage), Some(replaced)) => self.observers.replaced(id, replaced, storage),
+					(Some(storage), None) => self.observers.added(id, storage),
+					(None, _) => (),
+				}
+			}
+			
+			/// Re-evaluates all predicates that depend on a component of an entity
+			fn update_component_preds(&mut self, id: EntityId, component_id: &S::Id)
+			{
+				if let Some(entity) = self.entities.get(id) {
+					self.predicates.update_component(id, entity, component_id);
+				}
 			}
 		//--------------------------------------------------------------------------------------------------
 		
@@ -263,12 +668,19 @@ use crate::{KeyType, Storage, Entity};
 			/// assert_eq!(world[id].get::<&'static str>(), Some(&"Hello, World!"));
 			/// ```
 			#[must_use]
-			pub fn get(&self, id: EntityId) -> Option<&Entity<'a, S>> {
-				self.entities.get(&id)
+			pub fn get(&self, id: EntityId) -> Option< BackendRef<'a, '_, S, B> > {
+				self.entities.get(id)
 			}
 			
 			/// Returns a mutable reference to an entity given it's id
 			/// 
+			/// # Predicates
+			/// Components can't be added or removed through the returned
+			/// reference, for that use [`World::insert_component`] and
+			/// [`World::remove_component`], which update all predicates.
+			/// Predicates that depend on the values of components must be
+			/// updated with [`World::update_preds`] after changing them.
+			/// 
 			/// # Example
 			/// 
 			/// ```rust
@@ -283,71 +695,414 @@ use crate::{KeyType, Storage, Entity};
 			/// assert_eq!(world.iter_all().count(), 1);
 			/// assert!   ( world.get_mut(id).is_some() );
 			/// assert_eq!(world[id].get::<i32>(), Some(&5i32));
-			/// *world[id].get_mut::<i32>().unwrap() = 8;
+			/// *world.get_mut(id).unwrap().get_mut::<i32>().unwrap() = 8;
 			/// assert_eq!(world[id].get::<i32>(), Some(&8i32));
 			/// ```
 			#[must_use]
-			pub fn get_mut(&mut self, id: EntityId) -> Option<&mut Entity<'a, S>> {
-				self.entities.get_mut(&id)
+			pub fn get_mut(&mut self, id: EntityId) -> Option< BackendMut<'a, '_, S, B> > {
+				self.entities.get_mut(id, self.change_tick)
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Checks
+		//--------------------------------------------------------------------------------------------------
+			/// Checks if an entity id is alive in this world
+			/// 
+			/// An id stops being alive once it's entity is removed,
+			/// even if it's slot is later reused by another entity.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// 
+			/// let old_id = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+			/// assert!(world.is_alive(old_id));
+			/// 
+			/// world.remove(old_id);
+			/// let new_id = world.add( mecs::entity![ DynStorage::new(8i32) ] );
+			/// 
+			/// assert!(!world.is_alive(old_id));
+			/// assert!( world.is_alive(new_id));
+			/// assert!( world.get(old_id).is_none());
+			/// assert_eq!(world[new_id].get::<i32>(), Some(&8i32));
+			/// ```
+			#[must_use]
+			pub fn is_alive(&self, id: EntityId) -> bool {
+				self.ids.is_alive(id)
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Change detection
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the current change tick
+			/// 
+			/// All components added or changed from now on
+			/// will be marked with this tick.
+			#[must_use]
+			pub const fn change_tick(&self) -> Tick {
+				self.change_tick
+			}
+			
+			/// Increments the current change tick and returns it
+			/// 
+			/// Any changes after this call will be newer than the
+			/// tick returned by [`World::change_tick`] before the call.
+			/// 
+			/// This also updates all registered events, dropping
+			/// any events sent before the previous call, see [`events`].
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// 
+			/// let id = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+			/// 
+			/// let last_tick = world.change_tick();
+			/// let cur_tick = world.increment_change_tick();
+			/// assert!(cur_tick.is_newer_than(last_tick));
+			/// 
+			/// let pred_id = world.add_pred(|entity| entity.has::<i32>());
+			/// world.iter_pred_mut(pred_id).unwrap().for_each(|mut entity, _| {
+			/// 	*entity.get_mut::<i32>().unwrap() += 1;
+			/// });
+			/// 
+			/// let changed = world.iter_pred(pred_id).unwrap()
+			/// 	.filter(|(entity, _)| entity.changed::<i32>(last_tick))
+			/// 	.count();
+			/// assert_eq!(changed, 1);
+			/// ```
+			pub fn increment_change_tick(&mut self) -> Tick
+			{
+				for update in self.event_updates.values() {
+					update(&mut self.resources);
+				}
+				
+				self.change_tick = self.change_tick.next();
+				self.change_tick
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Resources
+		//--------------------------------------------------------------------------------------------------
+			/// Inserts a resource
+			/// 
+			/// # Return value
+			/// If a resource of the same type already existed, it is replaced and returned.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// /// Time since the last tick
+			/// #[derive(PartialEq, Debug)]
+			/// struct DeltaTime(f32);
+			/// 
+			/// let mut world: World<DynStorage> = World::new();
+			/// assert_eq!(world.insert_resource( DeltaTime(0.5) ), None);
+			/// assert_eq!(world.insert_resource( DeltaTime(0.25) ), Some( DeltaTime(0.5) ));
+			/// 
+			/// world.resource_mut::<DeltaTime>().unwrap().0 *= 2.0;
+			/// assert_eq!(world.resource::<DeltaTime>(), Some(&DeltaTime(0.5)));
+			/// 
+			/// assert_eq!(world.remove_resource::<DeltaTime>(), Some( DeltaTime(0.5) ));
+			/// assert_eq!(world.resource::<DeltaTime>(), None);
+			/// ```
+			pub fn insert_resource<R>(&mut self, resource: R) -> Option<R>
+			where
+				R: Any + Send + Sync
+			{
+				self.resources.insert(resource)
+			}
+			
+			/// Removes a resource given it's type
+			pub fn remove_resource<R>(&mut self) -> Option<R>
+			where
+				R: Any + Send + Sync
+			{
+				self.resources.remove()
+			}
+			
+			/// Returns a reference to a resource given it's type
+			#[must_use]
+			pub fn resource<R>(&self) -> Option<&R>
+			where
+				R: Any + Send + Sync
+			{
+				self.resources.get()
+			}
+			
+			/// Returns a mutable reference to a resource given it's type
+			pub fn resource_mut<R>(&mut self) -> Option<&mut R>
+			where
+				R: Any + Send + Sync
+			{
+				self.resources.get_mut()
+			}
+			
+			/// Returns all resources
+			#[must_use]
+			pub const fn resources(&self) -> &Resources {
+				&self.resources
+			}
+			
+			/// Returns all resources mutably
+			pub const fn resources_mut(&mut self) -> &mut Resources {
+				&mut self.resources
 			}
 		//--------------------------------------------------------------------------------------------------
 		
 		// Register
 		//--------------------------------------------------------------------------------------------------
 			/// Registers a predicate to filter entities through
-			#[allow(clippy::integer_arithmetic)] // We need to add one to get the next pred id
-			pub fn add_pred<F>(&mut self, f: F) -> usize
+			/// 
+			/// This is equivalent to [`World::add_filter`] with [`Filter::from_fn`].
+			/// 
+			/// # Return value
+			/// Returns the id of the predicate, which may only be used
+			/// with this world, and only until the predicate is removed
+			/// with [`World::remove_pred`].
+			pub fn add_pred<F>(&mut self, f: F) -> PredId<S>
 			where
-				F: Fn(&Entity<'a, S>) -> bool + 'static
+				F: Fn(BackendRef<'a, '_, S, B>) -> bool + Send + Sync + 'a
 			{
-				// Get the id to use for this predicate
-				// and increase the next id
-				let id = self.next_pred_id;
-				self.next_pred_id += 1;
-				
-				// Go through all entities and add the ones that match this predicate
-				let mut ids = vec![];
-				for (&entity_id, entity) in &self.entities
-				{
-					if f(entity) {
-						ids.push( Cell::new(entity_id) );
-					}
-				}
-				
-				// Insert the predicate
-				self.predicates.insert(id, PredicateIds{ pred: Predicate( Box::new(f) ), ids });
-				
-				// And return it's id
-				id
+				self.add_filter( Filter::from_fn(f) )
+			}
+			
+			/// Registers a predicate from a filter
+			/// 
+			/// Predicates with identical declarative filters share their ids,
+			/// so registering the same filter more than once is cheap.
+			/// 
+			/// # Return value
+			/// Returns the id of the predicate, which may only be used
+			/// with this world, and only until the predicate is removed
+			/// with [`World::remove_pred`].
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage, Filter};
+			/// let mut world = World::new();
+			/// 
+			/// let pred_id = world.add_filter( Filter::has::<i32>().and( Filter::without::<f32>() ) );
+			/// let id = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 1);
+			/// 
+			/// world.insert_component(id, DynStorage::new(1.5f32)).unwrap();
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 0);
+			/// ```
+			pub fn add_filter(&mut self, filter: Filter<'a, S, B>) -> PredId<S> {
+				self.predicates.insert( filter, self.entities.iter() )
+			}
+			
+			/// Unregisters a predicate
+			/// 
+			/// # Return value
+			/// Returns if the predicate existed. After this, `id`,
+			/// along with any copies of it, are no longer valid.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world: World<DynStorage> = World::new();
+			/// 
+			/// let pred_id = world.add_pred(|entity| entity.has::<i32>());
+			/// assert!(world.has_pred(pred_id));
+			/// 
+			/// assert!( world.remove_pred(pred_id));
+			/// assert!(!world.remove_pred(pred_id));
+			/// assert!(!world.has_pred(pred_id));
+			/// assert!( world.iter_pred(pred_id).is_none());
+			/// 
+			/// // Ids of removed predicates stay invalid, even after their slot is reused
+			/// let new_pred_id = world.add_pred(|entity| entity.has::<f32>());
+			/// assert!(!world.has_pred(pred_id));
+			/// assert!( world.has_pred(new_pred_id));
+			/// ```
+			pub fn remove_pred(&mut self, id: PredId<S>) -> bool {
+				self.predicates.remove(id)
+			}
+			
+			/// Replaces a predicate, keeping it's id
+			/// 
+			/// # Return value
+			/// Returns if the predicate existed, if it didn't,
+			/// nothing is replaced.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// world.add( mecs::entity![ DynStorage::new(5i32) ] );
+			/// 
+			/// let pred_id = world.add_pred(|entity| entity.has::<f32>());
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 0);
+			/// 
+			/// assert!(world.replace_pred(pred_id, |entity| entity.has::<i32>()));
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 1);
+			/// ```
+			pub fn replace_pred<F>(&mut self, id: PredId<S>, f: F) -> bool
+			where
+				F: Fn(BackendRef<'a, '_, S, B>) -> bool + Send + Sync + 'a
+			{
+				self.replace_filter( id, Filter::from_fn(f) )
+			}
+			
+			/// Replaces the filter of a predicate, keeping it's id
+			/// 
+			/// # Return value
+			/// Returns if the predicate existed, if it didn't,
+			/// nothing is replaced.
+			pub fn replace_filter(&mut self, id: PredId<S>, filter: Filter<'a, S, B>) -> bool {
+				self.predicates.replace( id, filter, self.entities.iter() )
+			}
+			
+			/// Checks if a predicate exists in this world
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world1: World<DynStorage> = World::new();
+			/// let mut world2: World<DynStorage> = World::new();
+			/// 
+			/// let pred_id = world1.add_pred(|entity| entity.has::<i32>());
+			/// 
+			/// assert!( world1.has_pred(pred_id));
+			/// assert!(!world2.has_pred(pred_id));
+			/// ```
+			#[must_use]
+			pub fn has_pred(&self, id: PredId<S>) -> bool {
+				self.predicates.contains(id)
 			}
 		//--------------------------------------------------------------------------------------------------
 		
-		// Iterators
+		// Observers
 		//--------------------------------------------------------------------------------------------------
-			/// Returns an iterator over all entities in this world
-			pub fn iter_all(&self) -> impl Iterator<Item = &Entity<'a, S>> {
-				self.entities.values()
+			/// Registers an observer of a component being added to an entity, given it's type
+			/// 
+			/// Observers are called whenever a component is added through the world,
+			/// either by adding an entity, inserting a component or applying commands,
+			/// in the order they were registered.
+			/// As entities in a world may only gain or lose components through it,
+			/// every change is observed.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// # use std::sync::{Arc, Mutex};
+			/// let mut world = World::new();
+			/// 
+			/// // Keep an external index of the entities of each name
+			/// let index = Arc::new( Mutex::new( vec![] ) );
+			/// let add_index = Arc::clone(&index);
+			/// world.on_add::<&str, _>(move |id, name| add_index.lock().unwrap().push( (*name, id) ));
+			/// let remove_index = Arc::clone(&index);
+			/// world.on_remove::<&str, _>(move |id, _| remove_index.lock().unwrap().retain(|&(_, other_id)| other_id != id));
+			/// 
+			/// let id1 = world.add( mecs::entity![ DynStorage::new("Alice") ] );
+			/// let id2 = world.add( mecs::entity![ DynStorage::new("Bob") ] );
+			/// world.remove(id1);
+			/// 
+			/// assert_eq!(*index.lock().unwrap(), [ ("Bob", id2) ]);
+			/// ```
+			pub fn on_add<C, F>(&mut self, mut f: F)
+			where
+				C: Component<'a, S> + 'a,
+				F: FnMut(EntityId, &C) + Send + Sync + 'a,
+			{
+				self.on_add_id(C::id(), move |id, storage| if let Some(component) = C::get(storage) {
+					f(id, component);
+				});
 			}
 			
-			/// Returns a mutable iterator over all entities in this world
-			pub fn iter_all_mut(&mut self) -> impl Iterator<Item = &mut Entity<'a, S>> {
-				self.entities.values_mut()
+			/// Registers an observer of a component being added to an entity, given it's id
+			/// 
+			/// See [`World::on_add`] for when observers are called.
+			pub fn on_add_id<F>(&mut self, component_id: S::Id, f: F)
+			where
+				F: FnMut(EntityId, &S) + Send + Sync + 'a
+			{
+				self.observers.on_add( component_id, Box::new(f) );
 			}
 			
-			/// Returns a mutable iterator over all entities in this world
-			#[deprecated(since = "0.2.0", note = "Use `iter_all_mut` instead")]
-			pub fn iter_mut_all(&mut self) -> impl Iterator<Item = &mut Entity<'a, S>> {
-				self.iter_all_mut()
+			/// Registers an observer of a component of an entity being replaced, given it's type
+			/// 
+			/// The observer is called with the replaced component, followed by the new one.
+			/// See [`World::on_add`] for when observers are called.
+			pub fn on_replace<C, F>(&mut self, mut f: F)
+			where
+				C: Component<'a, S> + 'a,
+				F: FnMut(EntityId, &C, &C) + Send + Sync + 'a,
+			{
+				self.on_replace_id(C::id(), move |id, old, new| if let (Some(old), Some(new)) = (C::get(old), C::get(new)) {
+					f(id, old, new);
+				});
+			}
+			
+			/// Registers an observer of a component of an entity being replaced, given it's id
+			/// 
+			/// The observer is called with the replaced component, followed by the new one.
+			/// See [`World::on_add`] for when observers are called.
+			pub fn on_replace_id<F>(&mut self, component_id: S::Id, f: F)
+			where
+				F: FnMut(EntityId, &S, &S) + Send + Sync + 'a
+			{
+				self.observers.on_replace( component_id, Box::new(f) );
+			}
+			
+			/// Registers an observer of a component being removed from an entity, given it's type
+			/// 
+			/// This includes every component of an entity being removed.
+			/// See [`World::on_add`] for when observers are called.
+			pub fn on_remove<C, F>(&mut self, mut f: F)
+			where
+				C: Component<'a, S> + 'a,
+				F: FnMut(EntityId, &C) + Send + Sync + 'a,
+			{
+				self.on_remove_id(C::id(), move |id, storage| if let Some(component) = C::get(storage) {
+					f(id, component);
+				});
+			}
+			
+			/// Registers an observer of a component being removed from an entity, given it's id
+			/// 
+			/// This includes every component of an entity being removed.
+			/// See [`World::on_add`] for when observers are called.
+			pub fn on_remove_id<F>(&mut self, component_id: S::Id, f: F)
+			where
+				F: FnMut(EntityId, &S) + Send + Sync + 'a
+			{
+				self.observers.on_remove( component_id, Box::new(f) );
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Iterators
+		//--------------------------------------------------------------------------------------------------
+			/// Returns an iterator over all entities in this world
+			pub fn iter_all(&self) -> impl Iterator<Item = BackendRef<'a, '_, S, B>> {
+				self.entities.iter()
+					.map(|(_, entity)| entity)
 			}
 			
 			/// Returns an iterator over a predicate
+			/// 
+			/// # Return value
+			/// Returns `None` if the predicate doesn't exist in this world
 			#[must_use]
-			pub fn iter_pred(&self, id: usize) -> Option< PredIter<'a, '_, S> > {
-				if self.predicates.get(&id).is_some() {
+			pub fn iter_pred(&self, id: PredId<S>) -> Option< PredIter<'a, '_, S, B> > {
+				if self.has_pred(id) {
 					Some( PredIter {
 							world: self,
 							id,
-							cur_idx: 0,
+							cursor: B::Cursor::default(),
 					})
 				} else {
 					None
@@ -355,49 +1110,308 @@ use crate::{KeyType, Storage, Entity};
 			}
 			
 			/// Returns a mutable iterator over a predicate
+			/// 
+			/// # Return value
+			/// Returns `None` if the predicate doesn't exist in this world
+			/// 
+			/// # Modifying the world
+			/// Entities may be removed during iteration with [`PredIterMut::remove`],
+			/// which only removes them once the iterator is dropped, so no entities
+			/// are skipped. Any other changes may be recorded through [`PredIterMut::commands`].
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// for i in 0..4 {
+			/// 	world.add( mecs::entity![ DynStorage::new(i) ] );
+			/// }
+			/// let pred_id = world.add_pred(|entity| entity.has::<i32>());
+			/// 
+			/// // Remove all even entities while iterating
+			/// let mut pred_iter = world.iter_pred_mut(pred_id).unwrap();
+			/// let mut visited = 0;
+			/// while let Some((entity, id)) = pred_iter.next() {
+			/// 	visited += 1;
+			/// 	if entity.get::<i32>().unwrap() % 2 == 0 {
+			/// 		pred_iter.remove(id);
+			/// 	}
+			/// }
+			/// drop(pred_iter);
+			/// 
+			/// assert_eq!(visited, 4);
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 2);
+			/// ```
 			#[must_use]
-			pub fn iter_pred_mut(&mut self, id: usize) -> Option< PredIterMut<'a, '_, S> > {
-				if self.predicates.get(&id).is_some() {
+			pub fn iter_pred_mut(&mut self, id: PredId<S>) -> Option< PredIterMut<'a, '_, S, B> > {
+				if self.has_pred(id) {
+					let commands = self.commands();
 					Some( PredIterMut {
 							world: self,
 							id,
-							cur_idx: 0,
+							cursor: B::Cursor::default(),
+							commands,
 					})
 				} else {
 					None
 				}
 			}
+			
+			/// Calls a closure on every entity of a predicate from multiple threads
+			/// 
+			/// The entities of the predicate are split into chunks of `chunk_size` entities,
+			/// which are processed by as many threads as are available, see [`backend::par_chunks`].
+			/// 
+			/// # Return value
+			/// Returns if the predicate existed, if it didn't, the closure isn't called.
+			/// 
+			/// # Panics
+			/// Panics if `chunk_size` is 0 or if the closure panics.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::World;
+			/// #[derive(PartialEq, Debug)]
+			/// struct Position(f32);
+			/// struct Velocity(f32);
+			/// 
+			/// mecs::impl_enum_storage! {
+			/// 	enum Components {
+			/// 		Position(Position),
+			/// 		Velocity(Velocity),
+			/// 	}
+			/// }
+			/// 
+			/// let mut world: World<Components> = World::new();
+			/// for _ in 0..100 {
+			/// 	world.add( mecs::entity![ Components::Position( Position(0.0) ), Components::Velocity( Velocity(1.5) ) ] );
+			/// }
+			/// let pred_id = world.add_pred(|entity| entity.has::<Position>() && entity.has::<Velocity>());
+			/// 
+			/// world.par_iter_pred_mut(pred_id, 16, |mut entity, _| {
+			/// 	let velocity = entity.get::<Velocity>().unwrap().0;
+			/// 	entity.get_mut::<Position>().unwrap().0 += velocity;
+			/// });
+			/// 
+			/// assert!(world.iter_all().all(|entity| entity.get::<Position>() == Some(&Position(1.5))));
+			/// ```
+			pub fn par_iter_pred_mut<F>(&mut self, id: PredId<S>, chunk_size: usize, f: F) -> bool
+			where
+				S    : Send,
+				S::Id: Send,
+				F    : Fn(BackendMut<'a, '_, S, B>, EntityId) + Sync,
+			{
+				assert!(chunk_size != 0, "Chunk size must be non-zero");
+				let Some(ids) = self.pred_ids(id) else {
+					return false;
+				};
+				
+				self.entities.par_for_each(&ids, chunk_size, self.change_tick, &f);
+				true
+			}
+			
+			/// Returns an iterator over a query
+			/// 
+			/// The first time a query with a set of required components is
+			/// used, a predicate is registered for it, which is then reused
+			/// by all further queries requiring the same components.
+			/// See [`query`] for all supported queries.
+			/// 
+			/// # Panics
+			/// Panics if the query accesses the same component more than once.
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage};
+			/// let mut world = World::new();
+			/// 
+			/// let id = world.add( mecs::entity![ DynStorage::new(5i32), DynStorage::new(1.5f32) ] );
+			/// world.add( mecs::entity![ DynStorage::new(8i32) ] );
+			/// 
+			/// for (_, (num, scale)) in world.query::<(&mut i32, &f32)>() {
+			/// 	assert_eq!(*scale, 1.5);
+			/// 	*num *= 2;
+			/// }
+			/// 
+			/// assert_eq!(world.query::<&i32>().count(), 2);
+			/// assert_eq!(world.query::<(&i32, &f32)>().count(), 1);
+			/// assert_eq!(world[id].get::<i32>(), Some(&10));
+			/// ```
+			pub fn query<'b, Q>(&'b mut self) -> QueryIter<'a, 'b, S, Q, B>
+			where
+				Q    : Query<'a, 'b, S>,
+				S::Id: SortedKeyType,
+			{
+				let since = Tick::new( self.change_tick.get().wrapping_sub(1) );
+				self.query_since(since)
+			}
+			
+			/// Returns an iterator over a query, checking for added or changed components since a tick
+			/// 
+			/// This is the same as [`World::query`], but [`Changed`] and [`Added`] check
+			/// for components changed or added after `since`, instead of in the current tick.
+			/// 
+			/// # Panics
+			/// Panics if the query accesses the same component more than once.
+			pub fn query_since<'b, Q>(&'b mut self, since: Tick) -> QueryIter<'a, 'b, S, Q, B>
+			where
+				Q    : Query<'a, 'b, S>,
+				S::Id: SortedKeyType,
+			{
+				query::check_access::<S, Q>();
+				
+				// Get the predicate of this query and fetch it from all of it's entities
+				let (pred_id, required) = self.query_pred_required::<Q>();
+				let ids = self.pred_ids(pred_id).expect("Could not get predicate from id");
+				QueryIter {
+					rows: self.entities.fetch(&required, &ids, self.change_tick, since),
+					phantom: PhantomData,
+				}
+			}
+			
+			/// Returns the predicate of a query, registering it if it's the first time it's used
+			/// 
+			/// This is the predicate [`World::query`] goes through, shared by all queries requiring
+			/// the same components. Registering it up front lets views of this world go through
+			/// only it's entities as well, see [`WorldView::query`].
+			/// 
+			/// # Example
+			/// 
+			/// ```rust
+			/// # use mecs::{World, DynStorage, Access};
+			/// let mut world = World::new();
+			/// 
+			/// world.add( mecs::entity![ DynStorage::new(5i32), DynStorage::new(1.5f32) ] );
+			/// world.add( mecs::entity![ DynStorage::new(8i32) ] );
+			/// 
+			/// let pred_id = world.query_pred::<(&mut i32, &f32)>();
+			/// assert_eq!(world.query_pred::<(&f32, &i32)>(), pred_id);
+			/// assert_eq!(world.iter_pred(pred_id).unwrap().count(), 1);
+			/// 
+			/// let access = Access::new().write::<i32>().read::<f32>();
+			/// let mut views = world.views(&[&access]);
+			/// assert_eq!(views[0].query::<(&mut i32, &f32)>().count(), 1);
+			/// ```
+			pub fn query_pred<'b, Q>(&mut self) -> PredId<S>
+			where
+				Q    : Query<'a, 'b, S>,
+				S::Id: SortedKeyType,
+			{
+				self.query_pred_required::<Q>().0
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Helpers
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the predicate of a query along with it's sorted required ids,
+			/// registering it if it's the first time it's used
+			fn query_pred_required<'b, Q>(&mut self) -> ( PredId<S>, Vec<S::Id> )
+			where
+				Q    : Query<'a, 'b, S>,
+				S::Id: SortedKeyType,
+			{
+				// Note: The required ids are sorted, so the same components in any order share their predicate
+				let mut required = vec![];
+				Q::required(&mut required);
+				required.sort_unstable();
+				required.dedup();
+				if let Some(&pred_id) = self.queries.get(&required) {
+					return (pred_id, required);
+				}
+				
+				let filter = required.iter()
+					.cloned()
+					.map(Filter::has_id)
+					.reduce(Filter::and)
+					.unwrap_or_else(|| Filter::from_fn(|_| true));
+				
+				let pred_id = self.add_filter(filter);
+				self.queries.insert(required.clone(), pred_id);
+				(pred_id, required)
+			}
+			
+			/// Returns the ids of all entities of a predicate whose ticks match
+			/// 
+			/// # Return value
+			/// Returns `None` if the predicate doesn't exist in this world
+			fn pred_ids(&self, id: PredId<S>) -> Option< Vec<EntityId> >
+			{
+				let ids = self.predicates
+					.ids(id)?
+					.entities()
+					.iter()
+					.copied()
+					.filter(|&entity_id| self.entities.get(entity_id).is_some_and(|entity| self.predicates.matches_ticks(id, entity)))
+					.collect();
+				
+				Some(ids)
+			}
 		//--------------------------------------------------------------------------------------------------
 	}
 	
-	impl<'a, S> Default for World<'a, S>
+	impl<'a, S, B> Extend< Entity<'a, S> > for World<'a, S, B>
 	where
 		S    : Storage<'a>,
-		S::Id: KeyType,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		/// Adds all entities with [`World::add_batch`]
+		fn extend<I>(&mut self, entities: I)
+		where
+			I: IntoIterator<Item = Entity<'a, S>>
+		{
+			self.add_batch(entities);
+		}
+	}
+	
+	impl<'a, S, B> Default for World<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
 	{
-		#[must_use]
 		fn default() -> Self {
-			Self::new()
+			Self {
+				entities: B::default(),
+				ids: EntityAllocator::new(),
+				change_tick: Tick::new(1),
+				predicates: Predicates::new(),
+				queries: HashMap::new(),
+				resources: Resources::new(),
+				event_updates: HashMap::new(),
+				observers: Observers::new(),
+			}
 		}
 	}
 	
-	impl<'a, S> PartialEq for World<'a, S>
+	impl<'a, S, B> PartialEq for World<'a, S, B>
 	where
 		S    : Storage<'a> + PartialEq,
-		S::Id: KeyType,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
 	{
-		#[must_use]
+		/// Compares the components of all entities, by their ids
+		/// 
+		/// Components are compared with their storage's [`PartialEq`], so with [`DynStorage`](crate::DynStorage),
+		/// worlds with components of types not registered in the [`TypeRegistry`](crate::TypeRegistry)
+		/// are never equal, and cloning their entities panics, see [`DynStorage::try_clone`](crate::DynStorage::try_clone).
 		fn eq(&self, other: &Self) -> bool
 		{
-			// Compare just the entities
-			self.entities == other.entities
+			// Compare just the entities, by their ids, ignoring the ticks of their components
+			self.entities.iter().count() == other.entities.iter().count() &&
+			self.entities.iter().all(|(id, entity)| other.entities.get(id).is_some_and(|other_entity| {
+				entity.ids().count() == other_entity.ids().count() &&
+				entity.ids().all(|component_id| entity.get_id(component_id) == other_entity.get_id(component_id))
+			}))
 		}
 	}
 	
-	impl<'a, S> Eq for World<'a, S>
+	impl<'a, S, B> Eq for World<'a, S, B>
 	where
 		S    : Storage<'a> + Eq,
-		S::Id: KeyType,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
 	{}
 	
 	// Index
@@ -405,67 +1419,80 @@ use crate::{KeyType, Storage, Entity};
 		impl<'a, S> std::ops::Index<EntityId> for World<'a, S>
 		where
 			S    : Storage<'a>,
-			S::Id: KeyType,
+			S::Id: KeyType + 'a,
 		{
 			type Output = Entity<'a, S>;
 			
-			#[must_use]
 			fn index(&self, id: EntityId) -> &Self::Output {
 				self.get(id).expect("Unknown entity id")
 			}
 		}
-		
-		impl<'a, S> std::ops::IndexMut<EntityId> for World<'a, S>
-		where
-			S    : Storage<'a>,
-			S::Id: KeyType,
-		{
-			#[must_use]
-			fn index_mut(&mut self, id: EntityId) -> &mut Self::Output {
-				self.get_mut(id).expect("Unknown entity id")
-			}
-		}
 	//--------------------------------------------------------------------------------------------------
 	
 	// Serde
 	//--------------------------------------------------------------------------------------------------
+		/// The serialized form of a world
+		/// 
+		/// Entities are stored along with their ids, and the generation of
+		/// every slot is kept, so that ids stay the same once deserialized.
 		#[cfg(feature = "serde-serialize")]
-		impl<'a, S> serde::Serialize for World<'a, S>
+		#[derive(serde::Serialize, serde::Deserialize)]
+		struct WorldData<E>
+		{
+			/// The current generation of each slot
+			generations: Vec<u32>,
+			
+			/// All entities, along with their ids
+			entities: Vec<(EntityId, E)>,
+		}
+		
+		#[cfg(feature = "serde-serialize")]
+		impl<'a, S, B> serde::Serialize for World<'a, S, B>
 		where
 			S    : Storage<'a> + serde::Serialize,
-			S::Id: KeyType,
+			S::Id: KeyType + 'a,
+			B    : Backend<'a, S>,
 		{
 			fn serialize<SS>(&self, serializer: SS) -> Result<SS::Ok, SS::Error>
 			where
 				SS: serde::Serializer,
 			{
-				use serde::ser::SerializeSeq;
+				// Note: Entities are serialized as a sequence of their components, same as `Entity`
+				let data = WorldData {
+					generations: self.ids.generations().collect(),
+					entities: self.entities.iter()
+						.map(|(id, entity)| (id, entity.components().collect::<Vec<_>>()))
+						.collect(),
+				};
 				
-				let mut seq = serializer.serialize_seq( Some(self.entities.len()) )?;
-				
-				for entity in self.entities.values() {
-					seq.serialize_element(entity)?;
-				}
-				
-				seq.end()
+				data.serialize(serializer)
 			}
 		}
 		
 		#[cfg(feature = "serde-serialize")]
-		impl<'a, 'de, S, I> serde::Deserialize<'de> for World<'a, S>
+		impl<'a, 'de, S, I, B> serde::Deserialize<'de> for World<'a, S, B>
 		where
 			S: Storage<'a, Id=I> + serde::Deserialize<'de>,
 			I: KeyType + 'a,
+			B: Backend<'a, S>,
 		{
 			fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 			where
 				D: serde::Deserializer<'de>,
 			{
-				// Get all entities as a vec
-				let entities = Vec::deserialize(deserializer)?;
+				let data = WorldData::<Entity<'a, S>>::deserialize(deserializer)?;
+				
+				// Restore all slots
+				let mut world = Self {
+					ids: EntityAllocator::from_generations( data.generations, data.entities.iter().map(|(id, _)| *id) )
+						.ok_or_else(|| serde::de::Error::custom("Invalid or duplicate entity ids"))?,
+					..Self::default()
+				};
+				
+				// And insert all entities into them
+				world.insert_batch(data.entities);
 				
-				// And create the world from all entities
-				Ok( Self::from_entities(entities) )
+				Ok(world)
 			}
 		}
 	//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,477 @@
+//! An archetype based backend
+//! 
+//! This module provides the [`Archetypes`] backend, an alternative to
+//! [`Entities`](super::Entities) which, instead of storing each entity with it's
+//! own map of components, groups all entities with the same set of component
+//! ids into an [`Archetype`], which stores each component in a contiguous column.
+//! 
+//! As with any backend, the world's api stays the same, so switching is a matter of
+//! replacing `World<Components>` with [`ArchetypeWorld<Components>`](ArchetypeWorld).
+//! Adding or removing a component moves the entity to another archetype, while
+//! queries walk the columns of each archetype directly. Declarative predicates
+//! are checked once for each archetype, then iterated over it's rows.
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use mecs::{ArchetypeWorld, DynStorage};
+//! 
+//! let mut world: ArchetypeWorld<DynStorage> = ArchetypeWorld::default();
+//! 
+//! let pred_id = world.add_pred(|entity| entity.has::<i32>() && entity.has::<&str>());
+//! 
+//! world.add( mecs::entity![ DynStorage::new(1i32), DynStorage::new("hello") ] );
+//! world.add( mecs::entity![ DynStorage::new(2i32), DynStorage::new("world") ] );
+//! world.add( mecs::entity![ DynStorage::new(3i32) ] );
+//! 
+//! let mut iter = world.iter_pred_mut(pred_id).unwrap();
+//! while let Some( (mut entity, _id) ) = iter.next() {
+//! 	*entity.get_mut::<i32>().unwrap() *= 10;
+//! }
+//! drop(iter);
+//! 
+//! let iter = world.iter_pred(pred_id).unwrap();
+//! for (entity, id) in iter {
+//! 	let num : &i32  = entity.get().unwrap();
+//! 	let name: &&str = entity.get().unwrap();
+//! 
+//! 	println!("{}: {}", num, name);
+//! }
+//! ```
+//! 
+//! Prints:
+//! 
+//! ```text
+//! 10: hello
+//! 20: world
+//! ```
+
+// Modules
+#[cfg(test)]
+    mod test;
+    mod table;
+    mod view;
+    mod fetch;
+
+// Exports
+pub use table::Archetype;
+pub use view ::{EntityRef, EntityMut};
+pub use fetch::{ArchetypesFetch, ArchetypesView};
+
+// Collections
+use std::collections::HashMap;
+
+// Traits
+use std::iter::Iterator;
+
+// Crate
+use crate::{util::SortedKeyType, Storage, Entity, EntityId, Tick, ComponentTicks, Access};
+use super::{backend, World, Backend, BackendRefs, BackendMut, PredicateIds};
+use view::RowsChunk;
+use fetch::Column;
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A world whose entities are grouped by archetype
+	/// 
+	/// This is a [`World`] using the [`Archetypes`] backend, see the module docs.
+	#[allow(clippy::module_name_repetitions)]
+	pub type ArchetypeWorld<'a, S> = World<'a, S, Archetypes<'a, S>>;
+	
+	/// Backend grouping entities by archetype
+	/// 
+	/// Entities are stored as a row of the archetype with their component ids,
+	/// so they're borrowed as an [`EntityRef`] or [`EntityMut`] instead of an [`Entity`].
+	pub struct Archetypes<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// All of the archetypes
+		tables: Vec< Archetype<'a, S> >,
+		
+		/// The index of each archetype, by it's sorted component ids
+		archetype_idxs: HashMap<Vec<S::Id>, usize>,
+		
+		/// The location of each entity, indexed by their entity id's index
+		locations: Vec< Option<EntityLocation> >,
+	}
+	
+	/// A position within the rows of all archetypes, see [`Backend::next_pred`]
+	#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+	pub struct ArchetypesCursor
+	{
+		/// The index of the archetype
+		archetype: usize,
+		
+		/// The next row to check within the archetype
+		row: usize,
+	}
+	
+	/// The location of an entity within the world
+	#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+	struct EntityLocation
+	{
+		/// The index of the archetype
+		archetype: usize,
+		
+		/// The row within the archetype
+		row: usize,
+		
+		/// The generation of the entity's id
+		generation: u32,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S> Archetypes<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType + 'a,
+	{
+		/// Returns all archetypes
+		#[must_use]
+		pub fn archetypes(&self) -> &[Archetype<'a, S>] {
+			&self.tables
+		}
+		
+		/// Pushes an entity's components onto the archetype with their ids
+		/// 
+		/// `components` must be sorted by id.
+		#[allow(clippy::arithmetic_side_effects)] // We need to add one to get the number of locations
+		fn push(&mut self, id: EntityId, components: Vec<(S, ComponentTicks)>)
+		{
+			let ids = components.iter().map(|(storage, _)| storage.id()).collect();
+			let archetype = self.archetype_idx(ids);
+			let row = self.tables[archetype].push(id, components);
+			
+			if self.locations.len() <= id.idx() {
+				self.locations.resize(id.idx() + 1, None);
+			}
+			self.locations[id.idx()] = Some( EntityLocation{ archetype, row, generation: id.generation() } );
+		}
+		
+		/// Removes an entity's row from it's archetype and returns it's components, sorted by id
+		/// 
+		/// The entity's location must be pushed again afterwards, if it's still in this backend.
+		fn take(&mut self, location: EntityLocation) -> Vec<(S, ComponentTicks)>
+		{
+			// Remove it's row and fix the location of the entity that took it's place
+			let (components, moved) = self.tables[location.archetype].swap_remove(location.row);
+			if let Some(moved) = moved {
+				self.locations[moved.idx()]
+					.as_mut()
+					.expect("Moved entity had no location")
+					.row = location.row;
+			}
+			
+			components
+		}
+		
+		/// Returns the location of the next entity matched by a predicate, moving `cursor` past it
+		#[allow(clippy::arithmetic_side_effects)] // We need to add one to get the next row and archetype
+		fn next_pred_location(&self, pred: &PredicateIds<'a, S, Self>, cursor: &mut ArchetypesCursor) -> Option<(EntityId, EntityLocation)>
+		{
+			loop {
+				let archetype = self.tables.get(cursor.archetype)?;
+				
+				// If the predicate is declarative, all rows of the archetype match it alike, so only check the first
+				if cursor.row == 0 && pred.is_declarative() && !archetype.is_empty() && !pred.matches( EntityRef{ archetype, row: 0 } ) {
+					cursor.row = archetype.len();
+				}
+				
+				let Some(&id) = archetype.entities.get(cursor.row) else {
+					cursor.archetype += 1;
+					cursor.row = 0;
+					continue;
+				};
+				let location = EntityLocation{ archetype: cursor.archetype, row: cursor.row, generation: id.generation() };
+				cursor.row += 1;
+				
+				// Else check each row against the ids of the predicate
+				let matches = pred.is_declarative() || pred.ids().contains(id);
+				if matches && pred.matches_ticks( EntityRef{ archetype, row: location.row } ) {
+					return Some( (id, location) );
+				}
+			}
+		}
+		
+		/// Returns the index of the archetype with the given sorted ids,
+		/// creating it if it doesn't exist yet
+		fn archetype_idx(&mut self, ids: Vec<S::Id>) -> usize
+		{
+			if let Some(&idx) = self.archetype_idxs.get(&ids) {
+				return idx;
+			}
+			
+			let idx = self.tables.len();
+			self.tables.push( Archetype::new( ids.clone() ) );
+			self.archetype_idxs.insert(ids, idx);
+			idx
+		}
+	}
+	
+	impl<'a, S> World<'a, S, Archetypes<'a, S>>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType + 'a,
+	{
+		/// Returns all archetypes in this world
+		/// 
+		/// # Example
+		/// 
+		/// ```rust
+		/// # use mecs::{ArchetypeWorld, DynStorage};
+		/// let mut world = ArchetypeWorld::default();
+		/// 
+		/// world.add( mecs::entity![ DynStorage::new(1i32), DynStorage::new("1") ] );
+		/// world.add( mecs::entity![ DynStorage::new("2"), DynStorage::new(2i32) ] );
+		/// 
+		/// assert_eq!(world.archetypes().len(), 1);
+		/// assert_eq!(world.archetypes()[0].column::<i32>().unwrap().len(), 2);
+		/// ```
+		#[must_use]
+		pub fn archetypes(&self) -> &[Archetype<'a, S>] {
+			self.entities.archetypes()
+		}
+	}
+	
+	impl<'a, S> Backend<'a, S> for Archetypes<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType + 'a,
+	{
+		type Fetch<'w> = ArchetypesFetch<'a, 'w, S> where Self: 'w;
+		type Cursor = ArchetypesCursor;
+		
+		fn insert(&mut self, id: EntityId, entity: Entity<'a, S>)
+		{
+			let mut components: Vec<_> = entity.into_components_ticks().collect();
+			components.sort_by_key(|(storage, _)| storage.id());
+			self.push(id, components);
+		}
+		
+		fn remove(&mut self, id: EntityId) -> Option< Entity<'a, S> >
+		{
+			let location = location(&self.locations, id)?;
+			self.locations[id.idx()] = None;
+			let components = self.take(location);
+			
+			Some( Entity::from_components_ticks(components) )
+		}
+		
+		fn insert_component(&mut self, id: EntityId, storage: S, change_tick: Tick) -> Result<Option<S>, S>
+		{
+			let Some(location) = location(&self.locations, id) else {
+				return Err(storage);
+			};
+			
+			// If the entity already has the component, replace it in place
+			let archetype = &mut self.tables[location.archetype];
+			let column = match archetype.ids.binary_search( &storage.id() ) {
+				Ok(column) => {
+					archetype.ticks[column][location.row] = ComponentTicks::new(change_tick);
+					return Ok( Some( std::mem::replace(&mut archetype.columns[column][location.row], storage) ) );
+				},
+				Err(column) => column,
+			};
+			
+			// Else move it to the archetype with the component
+			let mut components = self.take(location);
+			components.insert(column, (storage, ComponentTicks::new(change_tick)));
+			self.push(id, components);
+			
+			Ok(None)
+		}
+		
+		fn remove_component(&mut self, id: EntityId, component_id: &S::Id) -> Option<S>
+		{
+			let location = location(&self.locations, id)?;
+			let column = self.tables[location.archetype].column_idx(component_id)?;
+			
+			// Move the entity to the archetype without the component
+			let mut components = self.take(location);
+			let (storage, _) = components.remove(column);
+			self.push(id, components);
+			
+			Some(storage)
+		}
+		
+		fn get(&self, id: EntityId) -> Option< EntityRef<'a, '_, S> >
+		{
+			let location = location(&self.locations, id)?;
+			
+			Some( EntityRef {
+				archetype: &self.tables[location.archetype],
+				row: location.row,
+			})
+		}
+		
+		fn get_mut(&mut self, id: EntityId, change_tick: Tick) -> Option< EntityMut<'a, '_, S> >
+		{
+			let location = location(&self.locations, id)?;
+			
+			Some( EntityMut {
+				rows: &mut self.tables[location.archetype],
+				row: location.row,
+				change_tick,
+			})
+		}
+		
+		fn next_pred(&self, pred: &PredicateIds<'a, S, Self>, cursor: &mut ArchetypesCursor) -> Option<( EntityId, EntityRef<'a, '_, S> )>
+		{
+			let (id, location) = self.next_pred_location(pred, cursor)?;
+			
+			Some( (id, EntityRef {
+				archetype: &self.tables[location.archetype],
+				row: location.row,
+			}))
+		}
+		
+		fn next_pred_mut(&mut self, pred: &PredicateIds<'a, S, Self>, cursor: &mut ArchetypesCursor, change_tick: Tick) -> Option<( EntityId, EntityMut<'a, '_, S> )>
+		{
+			let (id, location) = self.next_pred_location(pred, cursor)?;
+			
+			Some( (id, EntityMut {
+				rows: &mut self.tables[location.archetype],
+				row: location.row,
+				change_tick,
+			}))
+		}
+		
+		fn iter(&self) -> impl Iterator<Item = ( EntityId, EntityRef<'a, '_, S> )> {
+			self.tables.iter().flat_map(|archetype| {
+				(0..archetype.len()).map(move |row| (archetype.entities[row], EntityRef{ archetype, row }))
+			})
+		}
+		
+		fn into_entities(self) -> Vec<( EntityId, Entity<'a, S> )> {
+			self.tables.into_iter()
+				.flat_map(Archetype::into_rows)
+				.map(|(id, components)| (id, Entity::from_components_ticks(components)))
+				.collect()
+		}
+		
+		fn par_for_each<F>(&mut self, ids: &[EntityId], chunk_size: usize, change_tick: Tick, f: &F)
+		where
+			S    : Send,
+			S::Id: Send,
+			F    : Fn(BackendMut<'a, '_, S, Self>, EntityId) + Sync,
+		{
+			let mut rows = rows(&self.locations, self.tables.len(), ids);
+			for rows in &mut rows {
+				rows.sort_unstable();
+			}
+			
+			// Note: Each chunk borrows a different part of the columns of it's archetype, so it may be sent to a different thread
+			let chunks = self.tables.iter_mut()
+				.zip(&rows)
+				.flat_map(|(archetype, rows)| RowsChunk::split(archetype, rows, chunk_size));
+			backend::par_chunks(chunks, |chunk| chunk.for_each(change_tick, f));
+		}
+		
+		fn fetch<'w>(&'w mut self, _required: &[S::Id], ids: &[EntityId], change_tick: Tick, since: Tick) -> Self::Fetch<'w>
+		{
+			let rows = rows(&self.locations, self.tables.len(), ids);
+			let tables = self.tables.iter_mut()
+				.zip(rows)
+				.filter(|(_, rows)| !rows.is_empty())
+				.map(|(archetype, rows)| {
+					let Archetype { ids, columns, ticks, entities, .. } = archetype;
+					let columns = ids.iter()
+						.zip( columns.iter_mut().zip(ticks) )
+						.map(|(id, (column, ticks))| (id, Column::Unique(column, ticks)))
+						.collect();
+					(&**entities, columns, rows)
+				});
+			
+			ArchetypesFetch::new(tables, change_tick, since)
+		}
+		
+		fn views(&mut self, accesses: &[&Access<'a, S>]) -> Vec< ArchetypesView<'a, '_, S> >
+		{
+			let Self { tables, locations, .. } = self;
+			let locations = &*locations;
+			let mut views: Vec<_> = accesses.iter()
+				.map(|_| ArchetypesView { tables: Vec::with_capacity( tables.len() ), locations })
+				.collect();
+			
+			for archetype in tables {
+				// Give each column to the view that writes it, or else to all views that read it
+				let Archetype { ids, columns, ticks, entities, .. } = archetype;
+				let mut view_columns: Vec<Vec<_>> = views.iter().map(|_| vec![]).collect();
+				for (id, (column, ticks)) in ids.iter().zip( columns.iter_mut().zip(ticks) ) {
+					if let Some(writer) = accesses.iter().position(|access| access.writes_id(id)) {
+						view_columns[writer].push( (id, Column::Unique(column, ticks)) );
+						continue;
+					}
+					
+					let (column, ticks) = (&**column, &**ticks);
+					for (columns, access) in view_columns.iter_mut().zip(accesses) {
+						if access.reads_id(id) {
+							columns.push( (id, Column::Shared(column, ticks)) );
+						}
+					}
+				}
+				
+				for (view, columns) in views.iter_mut().zip(view_columns) {
+					view.tables.push( (&**entities, columns) );
+				}
+			}
+			
+			views
+		}
+	}
+	
+	impl<'a, 'w, S> BackendRefs<'a, 'w, S> for Archetypes<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType + 'a,
+	{
+		type Ref = EntityRef<'a, 'w, S>;
+		type Mut = EntityMut<'a, 'w, S>;
+		type View = ArchetypesView<'a, 'w, S>;
+	}
+	
+	impl<'a, S> Default for Archetypes<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		fn default() -> Self {
+			Self {
+				tables: vec![],
+				archetype_idxs: HashMap::new(),
+				locations: vec![],
+			}
+		}
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Returns the location of an entity, if it's generation matches
+	fn location(locations: &[Option<EntityLocation>], id: EntityId) -> Option<EntityLocation>
+	{
+		locations.get( id.idx() )
+			.copied()
+			.flatten()
+			.filter(|location| location.generation == id.generation())
+	}
+	
+	/// Groups the rows of a set of entities by archetype
+	/// 
+	/// # Panics
+	/// Panics if any of the entities doesn't exist.
+	fn rows(locations: &[Option<EntityLocation>], archetypes: usize, ids: &[EntityId]) -> Vec< Vec<usize> >
+	{
+		let mut rows = vec![vec![]; archetypes];
+		for &id in ids {
+			let location = location(locations, id).expect("Entity didn't exist");
+			rows[location.archetype].push(location.row);
+		}
+		
+		rows
+	}
+//--------------------------------------------------------------------------------------------------
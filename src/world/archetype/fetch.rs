@@ -0,0 +1,206 @@
+//! Queries over archetypes
+
+// Crate
+use crate::{util::SortedKeyType, Storage, EntityId, Tick, ComponentTicks};
+use crate::world::{Fetch, View, Query, query::{self, QueryComponent, ComponentRef}};
+use super::EntityLocation;
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A view over the columns of [`Archetypes`](super::Archetypes)
+	pub struct ArchetypesView<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// The entity ids of each archetype, along with it's columns in this view
+		#[allow(clippy::type_complexity)] // It's just a list of columns per archetype
+		pub(in super) tables: Vec<( &'w [EntityId], Vec<( &'w S::Id, Column<'w, S> )> )>,
+		
+		/// The location of each entity
+		pub(in super) locations: &'w [Option<EntityLocation>],
+	}
+	
+	/// The rows of a query over [`Archetypes`](super::Archetypes)
+	pub struct ArchetypesFetch<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// The rows left to fetch of each archetype, with the next archetype last
+		tables: Vec< FetchTable<'a, 'w, S> >,
+		
+		/// The tick to mark components with if they're changed
+		change_tick: Tick,
+		
+		/// The tick to check if components were added or changed since
+		since: Tick,
+		
+		/// The number of rows left in all archetypes
+		len: usize,
+	}
+	
+	/// The rows left to fetch of an archetype
+	struct FetchTable<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// The entity id of each row of the archetype
+		entities: &'w [EntityId],
+		
+		/// The columns of the archetype, along with their ids
+		/// 
+		/// Unique columns only contain the rows after the last row we returned.
+		columns: Vec<( &'w S::Id, Column<'w, S> )>,
+		
+		/// The row of the archetype the columns start at
+		offset: usize,
+		
+		/// The rows left to return, sorted
+		rows: std::vec::IntoIter<usize>,
+	}
+	
+	/// A column of an archetype, along with it's ticks
+	pub(in super) enum Column<'w, S>
+	{
+		/// The column may only be read
+		Shared(&'w [S], &'w [ComponentTicks]),
+		
+		/// The column may be read and written
+		Unique(&'w mut [S], &'w mut [ComponentTicks]),
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, 'w, S> ArchetypesFetch<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// Creates the rows of a query given the rows of each archetype to return
+		/// 
+		/// `tables` are the entity ids and columns of each archetype, along with it's rows to return.
+		pub(in super) fn new<T>(tables: T, change_tick: Tick, since: Tick) -> Self
+		where
+			T: IntoIterator<Item = ( &'w [EntityId], Vec<( &'w S::Id, Column<'w, S> )>, Vec<usize> )>,
+		{
+			let mut tables: Vec<_> = tables.into_iter()
+				.map(|(entities, columns, mut rows)| {
+					rows.sort_unstable();
+					
+					FetchTable {
+						entities,
+						columns,
+						offset: 0,
+						rows: rows.into_iter(),
+					}
+				})
+				.collect();
+			let len = tables.iter().map(|table| table.rows.len()).sum();
+			tables.reverse();
+			
+			Self { tables, change_tick, since, len }
+		}
+	}
+	
+	impl<'a, 'w, S> View<'a, 'w, S> for ArchetypesView<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType + 'a,
+	{
+		type Fetch<'b> = ArchetypesFetch<'a, 'b, S> where Self: 'b;
+		
+		fn fetch(&mut self, ids: Option<&[EntityId]>, change_tick: Tick, since: Tick) -> ArchetypesFetch<'a, '_, S>
+		{
+			let rows = match ids {
+				Some(ids) => super::rows(self.locations, self.tables.len(), ids),
+				None => self.tables.iter().map(|(entities, _)| (0..entities.len()).collect()).collect(),
+			};
+			
+			// Note: Archetypes without any of our columns don't have any entities in this view
+			let tables = self.tables.iter_mut()
+				.zip(rows)
+				.filter(|((_, columns), rows)| !columns.is_empty() && !rows.is_empty())
+				.map(|((entities, columns), rows)| {
+					let columns = columns.iter_mut().map(|(id, column)| (*id, column.reborrow())).collect();
+					(*entities, columns, rows)
+				});
+			
+			ArchetypesFetch::new(tables, change_tick, since)
+		}
+	}
+	
+	impl<'a, 'w, S> Fetch<'a, 'w, S> for ArchetypesFetch<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType + 'a,
+	{
+		#[allow(clippy::arithmetic_side_effects)] // We need to subtract the offset and add one to get the next offset
+		fn next<Q: Query<'a, 'w, S>>(&mut self) -> Option<(EntityId, Q)>
+		{
+			let (change_tick, since) = (self.change_tick, self.since);
+			while let Some(table) = self.tables.last_mut()
+			{
+				// If the archetype has no rows left, go to the next one
+				let Some(row) = table.rows.next() else {
+					self.tables.pop();
+					continue;
+				};
+				self.len -= 1;
+				
+				// Split the row off from the ones after it in each unique column
+				// Note: As the rows are sorted, we never need to go back.
+				let offset = table.offset;
+				table.offset = row + 1;
+				let components = table.columns.iter_mut()
+					.map(|(id, column)| {
+						let storage = match column {
+							Column::Shared(column, ticks) => ComponentRef::Shared(&column[row], &ticks[row]),
+							Column::Unique(column, ticks) => {
+								let (storage, rest) = std::mem::take(column)[row - offset..]
+									.split_first_mut()
+									.expect("Query row was out of bounds");
+								*column = rest;
+								
+								let (component_ticks, rest) = std::mem::take(ticks)[row - offset..]
+									.split_first_mut()
+									.expect("Query row was out of bounds");
+								*ticks = rest;
+								
+								ComponentRef::Unique(storage, component_ticks)
+							},
+						};
+						
+						QueryComponent::new(*id, storage, change_tick, since)
+					});
+				
+				// And fetch the query from it
+				// Note: Every component is offered to the query, so all unique columns are split.
+				if let Some(query) = query::fetch(components) {
+					return Some( (table.entities[row], query) );
+				}
+			}
+			
+			// If we get here, return None
+			None
+		}
+		
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(0, Some(self.len))
+		}
+	}
+	
+	impl<S> Column<'_, S>
+	{
+		/// Reborrows this column for a shorter lifetime
+		pub(in super) const fn reborrow(&mut self) -> Column<'_, S>
+		{
+			match self {
+				Column::Shared(column, ticks) => Column::Shared(column, ticks),
+				Column::Unique(column, ticks) => Column::Unique(column, ticks),
+			}
+		}
+	}
+//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,196 @@
+//! Archetype tables
+
+// Crate
+use crate::{util::SortedKeyType, Storage, Component, EntityId, ComponentTicks, component::ComponentMask};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A table of entities which all share the same set of component ids
+	/// 
+	/// Each component id has it's own column, where the components
+	/// of every entity within this archetype are stored contiguously.
+	/// The `n`th row of every column belongs to the `n`th entity.
+	#[derive(Debug)]
+	pub struct Archetype<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// All of the component ids, sorted
+		pub(in super) ids: Vec<S::Id>,
+		
+		/// The mask of all component ids
+		pub(in super) mask: ComponentMask,
+		
+		/// All of the columns, in the same order as `ids`
+		pub(in super) columns: Vec< Vec<S> >,
+		
+		/// The ticks of every component, in the same order as `columns`
+		pub(in super) ticks: Vec< Vec<ComponentTicks> >,
+		
+		/// The entity id of each row
+		pub(in super) entities: Vec<EntityId>,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S> Archetype<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty archetype from it's sorted component ids
+			#[must_use]
+			pub(in super) fn new(ids: Vec<S::Id>) -> Self
+			{
+				let mut mask = ComponentMask::with_capacity( S::COUNT.unwrap_or(0) );
+				for index in ids.iter().filter_map(S::index) {
+					mask.insert(index);
+				}
+				
+				let columns = ids.iter().map(|_| vec![]).collect();
+				let ticks = ids.iter().map(|_| vec![]).collect();
+				
+				Self {
+					ids,
+					mask,
+					columns,
+					ticks,
+					entities: vec![],
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Add / Remove
+		//--------------------------------------------------------------------------------------------------
+			/// Adds a row to this archetype and returns it's index
+			/// 
+			/// `components` must be sorted by id and have the
+			/// same ids as this archetype.
+			#[allow(clippy::arithmetic_side_effects)] // We need to subtract one to get the last index
+			pub(in super) fn push(&mut self, id: EntityId, components: Vec<(S, ComponentTicks)>) -> usize
+			{
+				debug_assert_eq!(components.len(), self.columns.len());
+				
+				// Push each component onto it's column
+				for ((column, ticks), (storage, component_ticks)) in self.columns.iter_mut().zip(&mut self.ticks).zip(components) {
+					column.push(storage);
+					ticks.push(component_ticks);
+				}
+				
+				// Then push the entity id
+				self.entities.push(id);
+				
+				self.entities.len() - 1
+			}
+			
+			/// Removes a row from this archetype by swapping it with the last row
+			/// 
+			/// # Return value
+			/// Returns the components of the row, sorted by id, along with the
+			/// id of the entity which was moved into `row`, if any.
+			pub(in super) fn swap_remove(&mut self, row: usize) -> (Vec<(S, ComponentTicks)>, Option<EntityId>)
+			{
+				// Remove the component from each column
+				let components = self.columns.iter_mut()
+					.zip(&mut self.ticks)
+					.map(|(column, ticks)| (column.swap_remove(row), ticks.swap_remove(row)))
+					.collect();
+				
+				// Then remove the entity id and check who took it's place
+				self.entities.swap_remove(row);
+				let moved = self.entities.get(row).copied();
+				
+				(components, moved)
+			}
+			
+			/// Consumes this archetype and returns all of it's rows, along with their entity ids
+			pub(in super) fn into_rows(self) -> impl Iterator<Item = ( EntityId, Vec<(S, ComponentTicks)> )>
+			{
+				let mut columns: Vec<_> = self.columns.into_iter()
+					.zip(self.ticks)
+					.map(|(column, ticks)| column.into_iter().zip(ticks))
+					.collect();
+				
+				self.entities.into_iter().map(move |id| {
+					let components = columns.iter_mut()
+						.map(|column| column.next().expect("Column was shorter than the archetype"))
+						.collect();
+					(id, components)
+				})
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Access
+		//--------------------------------------------------------------------------------------------------
+			/// Returns all of the component ids of this archetype, sorted
+			#[must_use]
+			pub fn ids(&self) -> &[S::Id] {
+				&self.ids
+			}
+			
+			/// Returns the mask of all component ids of this archetype
+			/// 
+			/// The mask is empty if the storage doesn't map it's ids to indices, see [`Storage::COUNT`].
+			#[must_use]
+			pub const fn mask(&self) -> &ComponentMask {
+				&self.mask
+			}
+			
+			/// Returns the entity ids of every row in this archetype
+			#[must_use]
+			pub fn entities(&self) -> &[EntityId] {
+				&self.entities
+			}
+			
+			/// Returns the number of entities in this archetype
+			#[must_use]
+			pub const fn len(&self) -> usize {
+				self.entities.len()
+			}
+			
+			/// Checks if this archetype has no entities
+			#[must_use]
+			pub const fn is_empty(&self) -> bool {
+				self.entities.is_empty()
+			}
+			
+			/// Returns the column of a component given it's type
+			#[must_use]
+			pub fn column<C: Component<'a, S>>(&self) -> Option<&[S]> {
+				self.column_id( &C::id() )
+			}
+			
+			/// Returns the column of a component given it's id
+			#[must_use]
+			pub fn column_id(&self, id: &S::Id) -> Option<&[S]> {
+				self.column_idx(id)
+					.map(|idx| self.columns[idx].as_slice())
+			}
+			
+			/// Returns the index of the column of a component id
+			#[must_use]
+			pub(in super) fn column_idx(&self, id: &S::Id) -> Option<usize> {
+				self.ids.binary_search(id).ok()
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Checks
+		//--------------------------------------------------------------------------------------------------
+			/// Checks if this archetype has a component given it's type
+			#[must_use]
+			pub fn has<C: Component<'a, S>>(&self) -> bool {
+				self.has_id( &C::id() )
+			}
+			
+			/// Checks if this archetype has a component given it's id
+			#[must_use]
+			pub fn has_id(&self, id: &S::Id) -> bool {
+				self.column_idx(id).is_some()
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,200 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{ArchetypeWorld, Access, Filter};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type
+		#[derive(PartialEq, Clone, Debug)]
+		enum Components {
+			A(i32),
+			B(&'static str),
+			C(f32),
+		}
+	
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn archetypes()
+	{
+		let mut world: ArchetypeWorld<Components> = ArchetypeWorld::default();
+		
+		world.add( mecs::entity![ Components::A(1), Components::B("1") ] );
+		world.add( mecs::entity![ Components::B("2"), Components::A(2) ] );
+		world.add( mecs::entity![ Components::A(3) ] );
+		
+		// Component order shouldn't matter
+		assert_eq!(world.archetypes().len(), 2);
+		assert_eq!(world.archetypes()[0].len(), 2);
+		assert_eq!(world.archetypes()[1].len(), 1);
+	}
+	
+	#[test]
+	fn remove_moves_last_row()
+	{
+		let mut world: ArchetypeWorld<Components> = ArchetypeWorld::default();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		let id2 = world.add( mecs::entity![ Components::A(2) ] );
+		let id3 = world.add( mecs::entity![ Components::A(3) ] );
+		
+		let entity = world.remove(id1).expect("Entity wasn't in world");
+		assert_eq!(entity.get::<i32>(), Some(&1));
+		assert!(world.get(id1).is_none());
+		assert!(world.remove(id1).is_none());
+		
+		assert_eq!(world.get(id2).map(|entity| entity.get::<i32>().copied()), Some(Some(2)));
+		assert_eq!(world.get(id3).map(|entity| entity.get::<i32>().copied()), Some(Some(3)));
+		assert_eq!(world.get(id3).map(|entity| entity.id()), Some(id3));
+	}
+	
+	#[test]
+	fn pred_new_archetypes()
+	{
+		let mut world: ArchetypeWorld<Components> = ArchetypeWorld::default();
+		
+		world.add( mecs::entity![ Components::A(1), Components::B("1") ] );
+		
+		let pred_id = world.add_pred(|entity| entity.has::<i32>());
+		
+		world.add( mecs::entity![ Components::A(2), Components::C(2.0) ] );
+		world.add( mecs::entity![ Components::B("3") ] );
+		
+		let mut iter = world.iter_pred_mut(pred_id).expect("Unknown predicate");
+		while let Some( (mut entity, _) ) = iter.next() {
+			*entity.get_mut::<i32>().expect("Entity had no `i32`") += 10;
+		}
+		drop(iter);
+		
+		let mut nums: Vec<i32> = world.iter_pred(pred_id)
+			.expect("Unknown predicate")
+			.map(|(entity, _)| *entity.get::<i32>().expect("Entity had no `i32`"))
+			.collect();
+		nums.sort_unstable();
+		assert_eq!(nums, [11, 12]);
+		
+		assert_eq!(world.archetypes().len(), 3);
+		assert!(world.remove_pred(pred_id));
+		assert!(world.iter_pred(pred_id).is_none());
+	}
+	
+	#[test]
+	fn iter_pred_filters()
+	{
+		let mut world: ArchetypeWorld<Components> = ArchetypeWorld::default();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1), Components::B("1") ] );
+		let id2 = world.add( mecs::entity![ Components::A(2), Components::C(2.0) ] );
+		world.add( mecs::entity![ Components::A(3) ] );
+		world.add( mecs::entity![ Components::B("4") ] );
+		
+		let without_b = world.add_filter( Filter::has::<i32>().and( Filter::without::<&str>() ) );
+		let even = world.add_pred(|entity| entity.get::<i32>().is_some_and(|num| num % 2 == 0));
+		let changed = world.add_filter( Filter::changed::<i32>(world.change_tick()) );
+		
+		let mut nums: Vec<i32> = world.iter_pred(without_b)
+			.expect("Unknown predicate")
+			.map(|(entity, _)| *entity.get::<i32>().expect("Entity had no `i32`"))
+			.collect();
+		nums.sort_unstable();
+		assert_eq!(nums, [2, 3]);
+		assert_eq!(world.iter_pred(even).map(|iter| iter.map(|(_, id)| id).collect::<Vec<_>>()), Some( vec![id2] ));
+		
+		// Only entities changed after the predicate's tick should be iterated
+		assert_eq!(world.iter_pred(changed).map(Iterator::count), Some(0));
+		world.increment_change_tick();
+		if let Some(mut entity) = world.get_mut(id1) {
+			*entity.get_mut::<i32>().expect("Entity had no `i32`") += 10;
+		}
+		assert_eq!(world.iter_pred(changed).map(|iter| iter.map(|(_, id)| id).collect::<Vec<_>>()), Some( vec![id1] ));
+		
+		// Stale ids shouldn't find the entity that reused their slot
+		world.remove(id1);
+		let id5 = world.add( mecs::entity![ Components::A(5) ] );
+		assert_eq!(id5.idx(), id1.idx());
+		assert!(world.get(id1).is_none());
+		assert_eq!(world.get(id5).and_then(|entity| entity.get::<i32>().copied()), Some(5));
+	}
+	
+	#[test]
+	fn components_move_archetypes()
+	{
+		let mut world: ArchetypeWorld<Components> = ArchetypeWorld::default();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		let id2 = world.add( mecs::entity![ Components::A(2) ] );
+		let pred_id = world.add_pred(|entity| entity.has::<f32>());
+		
+		assert_eq!(world.insert_component(id1, Components::C(1.0)), Ok(None));
+		assert_eq!(world.insert_component(id1, Components::A(3)), Ok( Some(Components::A(1)) ));
+		assert_eq!(world.iter_pred(pred_id).map(Iterator::count), Some(1));
+		assert_eq!(world.get(id2).and_then(|entity| entity.get::<i32>().copied()), Some(2));
+		
+		let mut nums: Vec<(i32, f32)> = world.query::<(&i32, &mut f32)>()
+			.map(|(_, (&num, float))| { *float += 1.0; (num, *float) })
+			.collect();
+		nums.sort_by_key(|&(num, _)| num);
+		assert_eq!(nums, [(3, 2.0)]);
+		
+		assert_eq!(world.remove_component::<f32>(id1), Some( Components::C(2.0) ));
+		assert_eq!(world.iter_pred(pred_id).map(Iterator::count), Some(0));
+		assert_eq!(world.iter_all().count(), 2);
+	}
+	
+	#[test]
+	fn par_iter_pred_mut()
+	{
+		let mut world: ArchetypeWorld<Components> = ArchetypeWorld::default();
+		
+		for num in 0..10 {
+			world.add( mecs::entity![ Components::A(num) ] );
+			world.add( mecs::entity![ Components::A(num), Components::C(0.0) ] );
+		}
+		let pred_id = world.add_pred(|entity| entity.has::<i32>());
+		
+		assert!(world.par_iter_pred_mut(pred_id, 3, |mut entity, _| {
+			*entity.get_mut::<i32>().expect("Entity had no `i32`") += 1;
+		}));
+		
+		let sum: i32 = world.iter_all().map(|entity| *entity.get::<i32>().expect("Entity had no `i32`")).sum();
+		assert_eq!(sum, 2 * (1..=10).sum::<i32>());
+	}
+	
+	#[test]
+	fn views()
+	{
+		let mut world: ArchetypeWorld<Components> = ArchetypeWorld::default();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1), Components::C(2.0) ] );
+		let id2 = world.add( mecs::entity![ Components::B("2"), Components::C(3.0) ] );
+		let id3 = world.add( mecs::entity![ Components::A(3) ] );
+		let _ = world.query_pred::<(&mut i32, &f32)>();
+		
+		// Both views share `f32`, while each writes it's own component
+		let nums = Access::new().write::<i32>().read::<f32>();
+		let names = Access::new().write::<&str>().read::<f32>();
+		let mut views = world.views(&[&nums, &names]);
+		let (lhs, rhs) = views.split_at_mut(1);
+		for (_, (num, scale)) in lhs[0].query::<(&mut i32, &f32)>() {
+			if *scale > 1.0 {
+				*num += 1;
+			}
+		}
+		for (_, (name, _)) in rhs[0].query::<(&mut &str, &f32)>() {
+			*name = "scaled";
+		}
+		assert_eq!(lhs[0].query::<&i32>().count(), 2);
+		drop(views);
+		
+		assert_eq!(world.get(id1).and_then(|entity| entity.get::<i32>().copied()), Some(2));
+		assert_eq!(world.get(id2).and_then(|entity| entity.get::<&str>().copied()), Some("scaled"));
+		assert_eq!(world.get(id3).and_then(|entity| entity.get::<i32>().copied()), Some(3));
+	}
+//--------------------------------------------------------------------------------------------------
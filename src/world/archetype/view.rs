@@ -0,0 +1,458 @@
+//! Views over a single row of an archetype
+
+// Traits
+use std::iter::Iterator;
+
+// Crate
+use crate::{util::SortedKeyType, Storage, Component, EntityId, Tick, ComponentTicks, component::ComponentMask};
+use crate::world::ReadEntity;
+use super::Archetype;
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A reference to an entity living within an archetype
+	pub struct EntityRef<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// The archetype of the entity
+		pub(in super) archetype: &'w Archetype<'a, S>,
+		
+		/// The row of the entity
+		pub(in super) row: usize,
+	}
+	
+	/// A mutable reference to an entity living within an archetype
+	/// 
+	/// Components may only be modified through it, not added or removed.
+	pub struct EntityMut<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// The rows the entity lives in
+		pub(in super) rows: &'w mut (dyn Rows<'a, S> + 'w),
+		
+		/// The row of the entity
+		pub(in super) row: usize,
+		
+		/// The tick to mark components with if they're changed
+		pub(in super) change_tick: Tick,
+	}
+	
+	/// A chunk of rows of an archetype, split off from the rest so it may be sent to another thread
+	pub(in super) struct RowsChunk<'a, 'c, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// All of the component ids of the archetype, sorted
+		ids: Vec<S::Id>,
+		
+		/// The mask of all component ids of the archetype
+		mask: ComponentMask,
+		
+		/// The entity id of each row of the archetype
+		entities: &'c [EntityId],
+		
+		/// The part of each column within this chunk
+		columns: Vec<&'c mut [S]>,
+		
+		/// The part of the ticks of each column within this chunk
+		ticks: Vec<&'c mut [ComponentTicks]>,
+		
+		/// The row of the archetype this chunk starts at
+		offset: usize,
+		
+		/// The rows of this chunk to visit, sorted
+		rows: &'c [usize],
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Traits
+//--------------------------------------------------------------------------------------------------
+	/// Rows of an archetype entities may be mutably borrowed from
+	pub(in super) trait Rows<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// Returns all of the component ids, sorted
+		fn ids(&self) -> &[S::Id];
+		
+		/// Returns the mask of all component ids
+		fn mask(&self) -> &ComponentMask;
+		
+		/// Returns the entity id of a row
+		fn entity(&self, row: usize) -> EntityId;
+		
+		/// Returns a component of a row given it's column, along with it's ticks
+		fn component(&self, row: usize, column: usize) -> (&S, ComponentTicks);
+		
+		/// Returns a mutable component of a row given it's column, along with it's ticks
+		fn component_mut(&mut self, row: usize, column: usize) -> (&mut S, &mut ComponentTicks);
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, 'w, S> EntityRef<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// Returns the id of this entity
+		#[must_use]
+		pub fn id(&self) -> EntityId {
+			self.archetype.entities[self.row]
+		}
+		
+		/// Returns the archetype this entity lives in
+		#[must_use]
+		pub const fn archetype(&self) -> &'w Archetype<'a, S> {
+			self.archetype
+		}
+		
+		/// Returns a reference to a component given it's type
+		#[must_use]
+		pub fn get<C: Component<'a, S>>(&self) -> Option<&'w C>
+		{
+			self.get_id( &C::id() )
+				.and_then(C::get)
+		}
+		
+		/// Returns a reference to a component's storage given it's id
+		#[must_use]
+		pub fn get_id(&self, id: &S::Id) -> Option<&'w S> {
+			self.archetype.column_id(id)
+				.map(|column| &column[self.row])
+		}
+		
+		/// Returns an iterator over all component ids in this entity
+		pub fn ids(&self) -> impl Iterator<Item = &'w S::Id> {
+			self.archetype.ids.iter()
+		}
+		
+		/// Returns an iterator over all components in this entity
+		pub fn components(&self) -> impl Iterator<Item = &'w S>
+		{
+			let row = self.row;
+			self.archetype.columns.iter()
+				.map(move |column| &column[row])
+		}
+		
+		/// Checks if this entity has a component given it's type
+		#[must_use]
+		pub fn has<C: Component<'a, S>>(&self) -> bool {
+			self.archetype.has::<C>()
+		}
+		
+		/// Checks if this entity has a component given it's id
+		#[must_use]
+		pub fn has_id(&self, id: &S::Id) -> bool {
+			self.archetype.has_id(id)
+		}
+		
+		/// Returns the mask of all components in this entity
+		/// 
+		/// The mask is empty if the storage doesn't map it's ids to indices, see [`Storage::COUNT`].
+		#[must_use]
+		pub const fn mask(&self) -> &'w ComponentMask {
+			&self.archetype.mask
+		}
+		
+		/// Returns the ticks of a component given it's type
+		#[must_use]
+		pub fn ticks<C: Component<'a, S>>(&self) -> Option<ComponentTicks> {
+			self.ticks_id( &C::id() )
+		}
+		
+		/// Returns the ticks of a component given it's id
+		#[must_use]
+		pub fn ticks_id(&self, id: &S::Id) -> Option<ComponentTicks> {
+			self.archetype.column_idx(id)
+				.map(|column| self.archetype.ticks[column][self.row])
+		}
+		
+		/// Checks if a component was added after the tick `since`, given it's type
+		/// 
+		/// Returns `false` if the component is not present.
+		#[must_use]
+		pub fn added<C: Component<'a, S>>(&self, since: Tick) -> bool {
+			self.ticks::<C>()
+				.is_some_and(|ticks| ticks.is_added(since))
+		}
+		
+		/// Checks if a component was added or mutably accessed after the tick `since`, given it's type
+		/// 
+		/// Returns `false` if the component is not present.
+		#[must_use]
+		pub fn changed<C: Component<'a, S>>(&self, since: Tick) -> bool {
+			self.ticks::<C>()
+				.is_some_and(|ticks| ticks.is_changed(since))
+		}
+	}
+	
+	impl<'a, S> EntityMut<'a, '_, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// Returns the id of this entity
+		#[must_use]
+		pub fn id(&self) -> EntityId {
+			self.rows.entity(self.row)
+		}
+		
+		/// Returns a reference to a component given it's type
+		#[must_use]
+		pub fn get<C: Component<'a, S>>(&self) -> Option<&C>
+		{
+			self.get_id( &C::id() )
+				.and_then(C::get)
+		}
+		
+		/// Returns a mutable reference to a component given it's type
+		/// 
+		/// This marks the component as changed.
+		#[must_use]
+		pub fn get_mut<C: Component<'a, S>>(&mut self) -> Option<&mut C>
+		{
+			self.get_mut_id( &C::id() )
+				.and_then(C::get_mut)
+		}
+		
+		/// Returns a reference to a component's storage given it's id
+		#[must_use]
+		pub fn get_id(&self, id: &S::Id) -> Option<&S>
+		{
+			let column = self.column_idx(id)?;
+			Some( self.rows.component(self.row, column).0 )
+		}
+		
+		/// Returns a mutable reference to a component's storage given it's id
+		/// 
+		/// This marks the component as changed.
+		#[must_use]
+		pub fn get_mut_id(&mut self, id: &S::Id) -> Option<&mut S>
+		{
+			let column = self.column_idx(id)?;
+			let (storage, ticks) = self.rows.component_mut(self.row, column);
+			ticks.changed = self.change_tick;
+			Some(storage)
+		}
+		
+		/// Returns an iterator over all component ids in this entity
+		pub fn ids(&self) -> impl Iterator<Item = &S::Id> {
+			self.rows.ids().iter()
+		}
+		
+		/// Checks if this entity has a component given it's type
+		#[must_use]
+		pub fn has<C: Component<'a, S>>(&self) -> bool {
+			self.has_id( &C::id() )
+		}
+		
+		/// Checks if this entity has a component given it's id
+		#[must_use]
+		pub fn has_id(&self, id: &S::Id) -> bool {
+			self.column_idx(id).is_some()
+		}
+		
+		/// Returns the mask of all components in this entity
+		/// 
+		/// The mask is empty if the storage doesn't map it's ids to indices, see [`Storage::COUNT`].
+		#[must_use]
+		pub fn mask(&self) -> &ComponentMask {
+			self.rows.mask()
+		}
+		
+		/// Returns the ticks of a component given it's type
+		#[must_use]
+		pub fn ticks<C: Component<'a, S>>(&self) -> Option<ComponentTicks> {
+			self.ticks_id( &C::id() )
+		}
+		
+		/// Returns the ticks of a component given it's id
+		#[must_use]
+		pub fn ticks_id(&self, id: &S::Id) -> Option<ComponentTicks>
+		{
+			let column = self.column_idx(id)?;
+			Some( self.rows.component(self.row, column).1 )
+		}
+		
+		/// Returns the index of the column of a component id
+		fn column_idx(&self, id: &S::Id) -> Option<usize> {
+			self.rows.ids().binary_search(id).ok()
+		}
+	}
+	
+	impl<'a, 'c, S> RowsChunk<'a, 'c, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		/// Splits the rows of an archetype into chunks of `chunk_size` rows
+		/// 
+		/// `rows` must be sorted and may not contain any row more than once.
+		#[allow(clippy::arithmetic_side_effects)] // We need to add one to get the end of the chunk and subtract the offset
+		pub(in super) fn split(archetype: &'c mut Archetype<'a, S>, rows: &'c [usize], chunk_size: usize) -> Vec<Self>
+		{
+			let Archetype { ids, mask, columns, ticks, entities } = archetype;
+			let entities: &[EntityId] = entities;
+			let mut columns: Vec<&mut [S]> = columns.iter_mut().map(Vec::as_mut_slice).collect();
+			let mut ticks: Vec<&mut [ComponentTicks]> = ticks.iter_mut().map(Vec::as_mut_slice).collect();
+			
+			// Split every column right after the last row of each chunk
+			// Note: As the rows are sorted, each chunk starts where the last one ended.
+			let mut offset = 0;
+			rows.chunks(chunk_size)
+				.map(|rows| {
+					let end = rows.last().expect("Chunk was empty") + 1;
+					let chunk = Self {
+						ids: ids.clone(),
+						mask: mask.clone(),
+						entities,
+						columns: columns.iter_mut().map(|column| split_off_front(column, end - offset)).collect(),
+						ticks: ticks.iter_mut().map(|ticks| split_off_front(ticks, end - offset)).collect(),
+						offset,
+						rows,
+					};
+					offset = end;
+					
+					chunk
+				})
+				.collect()
+		}
+		
+		/// Calls a closure on every row of this chunk
+		pub(in super) fn for_each<F>(mut self, change_tick: Tick, f: &F)
+		where
+			F: Fn(EntityMut<'a, '_, S>, EntityId)
+		{
+			for &row in self.rows {
+				let id = self.entities[row];
+				f(EntityMut { rows: &mut self, row, change_tick }, id);
+			}
+		}
+	}
+	
+	impl<'a, S> Rows<'a, S> for Archetype<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		fn ids(&self) -> &[S::Id] {
+			&self.ids
+		}
+		
+		fn mask(&self) -> &ComponentMask {
+			&self.mask
+		}
+		
+		fn entity(&self, row: usize) -> EntityId {
+			self.entities[row]
+		}
+		
+		fn component(&self, row: usize, column: usize) -> (&S, ComponentTicks) {
+			(&self.columns[column][row], self.ticks[column][row])
+		}
+		
+		fn component_mut(&mut self, row: usize, column: usize) -> (&mut S, &mut ComponentTicks) {
+			(&mut self.columns[column][row], &mut self.ticks[column][row])
+		}
+	}
+	
+	impl<'a, S> Rows<'a, S> for RowsChunk<'a, '_, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		fn ids(&self) -> &[S::Id] {
+			&self.ids
+		}
+		
+		fn mask(&self) -> &ComponentMask {
+			&self.mask
+		}
+		
+		fn entity(&self, row: usize) -> EntityId {
+			self.entities[row]
+		}
+		
+		#[allow(clippy::arithmetic_side_effects)] // We need to subtract the offset to get the row within the chunk
+		fn component(&self, row: usize, column: usize) -> (&S, ComponentTicks) {
+			(&self.columns[column][row - self.offset], self.ticks[column][row - self.offset])
+		}
+		
+		#[allow(clippy::arithmetic_side_effects)] // We need to subtract the offset to get the row within the chunk
+		fn component_mut(&mut self, row: usize, column: usize) -> (&mut S, &mut ComponentTicks) {
+			(&mut self.columns[column][row - self.offset], &mut self.ticks[column][row - self.offset])
+		}
+	}
+	
+	impl<'a, S> Clone for EntityRef<'a, '_, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		fn clone(&self) -> Self {
+			*self
+		}
+	}
+	
+	impl<'a, S> Copy for EntityRef<'a, '_, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{}
+	
+	impl<'a, 'w, S> ReadEntity<'a, 'w, S> for EntityRef<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: SortedKeyType,
+	{
+		fn get_id(self, id: &S::Id) -> Option<&'w S> {
+			Self::get_id(&self, id)
+		}
+		
+		fn has_id(self, id: &S::Id) -> bool {
+			Self::has_id(&self, id)
+		}
+		
+		fn ticks_id(self, id: &S::Id) -> Option<ComponentTicks> {
+			Self::ticks_id(&self, id)
+		}
+		
+		fn mask(self) -> &'w ComponentMask {
+			Self::mask(&self)
+		}
+		
+		fn ids(self) -> impl Iterator<Item = &'w S::Id>
+		where
+			S::Id: 'w,
+		{
+			Self::ids(&self)
+		}
+		
+		fn components(self) -> impl Iterator<Item = &'w S>
+		where
+			S: 'w,
+		{
+			Self::components(&self)
+		}
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Splits off the first `len` elements of a slice, leaving the rest in it
+	#[allow(clippy::mut_mut)] // We need to replace the slice with the rest of it
+	fn split_off_front<'c, T>(slice: &mut &'c mut [T], len: usize) -> &'c mut [T]
+	{
+		let (front, rest) = std::mem::take(slice).split_at_mut(len);
+		*slice = rest;
+		front
+	}
+//--------------------------------------------------------------------------------------------------
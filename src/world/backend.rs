@@ -0,0 +1,287 @@
+//! Storage backends of a world
+//! 
+//! A [`World`](super::World) keeps track of entity ids, predicates, resources
+//! and observers, while the components of all of it's entities are stored by
+//! a backend, given by it's last type parameter. As the world's api is the same
+//! for every backend, switching is a matter of changing that type parameter.
+//! 
+//! - [`Entities`](super::Entities), the default, stores each entity with it's own map of components.
+//! - [`Archetypes`](super::archetype::Archetypes) groups all entities with the same component
+//!   ids into an archetype, which stores each component in a contiguous column.
+//! - [`SparseSets`](super::sparse::SparseSets) stores each component id in it's own sparse set,
+//!   so adding and removing components doesn't move any other components.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! use mecs::{World, DynStorage, world::archetype::Archetypes};
+//! 
+//! let mut world: World<DynStorage, Archetypes<DynStorage>> = World::default();
+//! 
+//! let pred_id = world.add_pred(|entity| entity.has::<i32>());
+//! let id = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+//! 
+//! world.insert_component(id, DynStorage::new("Hello, World!")).unwrap();
+//! world.iter_pred_mut(pred_id).unwrap().for_each(|mut entity, _| {
+//! 	*entity.get_mut::<i32>().unwrap() += 1;
+//! });
+//! 
+//! assert_eq!(world.get(id).unwrap().get::<i32>(), Some(&6));
+//! ```
+
+// Sync
+use std::sync::{Mutex, PoisonError};
+
+// Crate
+use crate::{util::KeyType, Storage, Entity, EntityId, Tick, ComponentTicks, Access, component::ComponentMask};
+use super::{Query, PredicateIds};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A reference to an entity of a backend
+	pub type BackendRef<'a, 'w, S, B> = <B as BackendRefs<'a, 'w, S>>::Ref;
+	
+	/// A mutable reference to an entity of a backend
+	pub type BackendMut<'a, 'w, S, B> = <B as BackendRefs<'a, 'w, S>>::Mut;
+	
+	/// A view over the components of a backend
+	pub type BackendView<'a, 'w, S, B> = <B as BackendRefs<'a, 'w, S>>::View;
+//--------------------------------------------------------------------------------------------------
+
+// Traits
+//--------------------------------------------------------------------------------------------------
+	/// The storage of all entities of a world
+	/// 
+	/// Backends only store components, the world itself checks
+	/// whether ids are alive and keeps all predicates up to date.
+	pub trait Backend<'a, S>: Default + for<'w> BackendRefs<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The rows of a query, see [`Fetch`]
+		type Fetch<'w>: Fetch<'a, 'w, S>
+		where
+			Self: 'w;
+		
+		/// A position within the entities of a predicate, see [`Backend::next_pred`]
+		type Cursor: Default;
+		
+		/// Inserts an entity given it's id
+		/// 
+		/// The entity's components keep the ticks they were given by the world.
+		fn insert(&mut self, id: EntityId, entity: Entity<'a, S>);
+		
+		/// Inserts a batch of entities given their ids
+		fn insert_batch(&mut self, entities: Vec<( EntityId, Entity<'a, S> )>)
+		{
+			for (id, entity) in entities {
+				self.insert(id, entity);
+			}
+		}
+		
+		/// Removes an entity given it's id
+		fn remove(&mut self, id: EntityId) -> Option< Entity<'a, S> >;
+		
+		/// Adds a component to an entity, marking it as added in `change_tick`
+		/// 
+		/// # Return value
+		/// If the entity already contains a component with
+		/// the same id as `storage`, it is replaced and returned.
+		/// 
+		/// # Errors
+		/// If the entity doesn't exist, `storage` is returned back.
+		fn insert_component(&mut self, id: EntityId, storage: S, change_tick: Tick) -> Result<Option<S>, S>;
+		
+		/// Removes a component from an entity given it's id
+		fn remove_component(&mut self, id: EntityId, component_id: &S::Id) -> Option<S>;
+		
+		/// Returns a reference to an entity given it's id
+		fn get(&self, id: EntityId) -> Option< BackendRef<'a, '_, S, Self> >;
+		
+		/// Returns a mutable reference to an entity given it's id, marking any changes with `change_tick`
+		fn get_mut(&mut self, id: EntityId, change_tick: Tick) -> Option< BackendMut<'a, '_, S, Self> >;
+		
+		/// Returns the next entity matched by a predicate, moving `cursor` past it
+		/// 
+		/// Backends may either go through the ids of the predicate, or, if it's declarative,
+		/// check it once for each group of entities with the same component ids,
+		/// see [`PredicateIds::is_declarative`].
+		fn next_pred(&self, pred: &PredicateIds<'a, S, Self>, cursor: &mut Self::Cursor) -> Option<( EntityId, BackendRef<'a, '_, S, Self> )>;
+		
+		/// Returns the next entity matched by a predicate mutably, moving `cursor` past it
+		/// 
+		/// Any changes to the entity are marked with `change_tick`. See [`Backend::next_pred`].
+		fn next_pred_mut(&mut self, pred: &PredicateIds<'a, S, Self>, cursor: &mut Self::Cursor, change_tick: Tick) -> Option<( EntityId, BackendMut<'a, '_, S, Self> )>;
+		
+		/// Returns an iterator over all entities, along with their ids
+		fn iter(&self) -> impl Iterator<Item = ( EntityId, BackendRef<'a, '_, S, Self> )>;
+		
+		/// Consumes this backend and returns all entities, along with their ids
+		fn into_entities(self) -> Vec<( EntityId, Entity<'a, S> )>;
+		
+		/// Calls a closure on a set of entities from multiple threads
+		/// 
+		/// The entities are split into chunks of `chunk_size` entities, which are
+		/// then processed by as many threads as are available, see [`par_chunks`].
+		fn par_for_each<F>(&mut self, ids: &[EntityId], chunk_size: usize, change_tick: Tick, f: &F)
+		where
+			S    : Send,
+			S::Id: Send,
+			F    : Fn(BackendMut<'a, '_, S, Self>, EntityId) + Sync;
+		
+		/// Returns the rows of a query
+		/// 
+		/// `required` are the sorted ids of all components required by the query, while
+		/// `ids` are the entities matched by it's predicate, in no particular order.
+		fn fetch<'w>(&'w mut self, required: &[S::Id], ids: &[EntityId], change_tick: Tick, since: Tick) -> Self::Fetch<'w>;
+		
+		/// Splits the components of all entities into views, one for each access
+		/// 
+		/// Each written component is given only to the view that writes it, while
+		/// read components are shared between all views that read them.
+		/// The accesses must not conflict, see [`Access::conflicts`].
+		fn views(&mut self, accesses: &[&Access<'a, S>]) -> Vec< BackendView<'a, '_, S, Self> >;
+	}
+	
+	/// The references to the entities of a backend
+	/// 
+	/// These are kept out of [`Backend`] so that closures may take references
+	/// of any lifetime, as `Bound` ensures the backend outlives them.
+	pub trait BackendRefs<'a, 'w, S, Bound = &'w Self>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// A reference to an entity
+		type Ref: ReadEntity<'a, 'w, S>;
+		
+		/// A mutable reference to an entity
+		/// 
+		/// Components may only be modified through it, not added or removed.
+		type Mut;
+		
+		/// A view over the components of all entities declared by an access
+		type View: View<'a, 'w, S>;
+	}
+	
+	/// A reference to an entity of any backend
+	/// 
+	/// This is what predicates, filters and observers use to inspect entities,
+	/// each backend's reference type also has typed methods, such as `get` and `has`.
+	pub trait ReadEntity<'a, 'w, S>: Copy
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// Returns a reference to a component's storage given it's id
+		fn get_id(self, id: &S::Id) -> Option<&'w S>;
+		
+		/// Checks if this entity has a component given it's id
+		fn has_id(self, id: &S::Id) -> bool;
+		
+		/// Returns the ticks of a component given it's id
+		fn ticks_id(self, id: &S::Id) -> Option<ComponentTicks>;
+		
+		/// Returns the mask of all components in this entity
+		/// 
+		/// The mask is empty if the storage doesn't map it's ids to indices, see [`Storage::COUNT`].
+		fn mask(self) -> &'w ComponentMask;
+		
+		/// Returns an iterator over all component ids in this entity
+		fn ids(self) -> impl Iterator<Item = &'w S::Id>
+		where
+			S::Id: 'w;
+		
+		/// Returns an iterator over all components in this entity
+		fn components(self) -> impl Iterator<Item = &'w S>
+		where
+			S: 'w;
+	}
+	
+	/// The rows of a query over a backend
+	pub trait Fetch<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// Fetches a query from the next entity that matches it
+		fn next<Q: Query<'a, 'w, S>>(&mut self) -> Option<(EntityId, Q)>;
+		
+		/// Returns the bounds on the number of entities left
+		fn size_hint(&self) -> (usize, Option<usize>);
+	}
+	
+	/// A view over the components of a backend declared by an access
+	/// 
+	/// Views only contain the entities with any of their components.
+	pub trait View<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The rows of a query over this view, see [`Fetch`]
+		type Fetch<'b>: Fetch<'a, 'b, S>
+		where
+			Self: 'b;
+		
+		/// Returns the rows of a query over this view
+		/// 
+		/// `ids` are the entities matched by the query's predicate, in no particular
+		/// order, or `None` to go through every entity in this view.
+		fn fetch(&mut self, ids: Option<&[EntityId]>, change_tick: Tick, since: Tick) -> Self::Fetch<'_>;
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Returns the id of the next entity matched by a predicate, going through it's ids
+	/// 
+	/// `cursor` is the index of the next id to check.
+	#[allow(clippy::arithmetic_side_effects)] // We need to add one to get the next index
+	pub(in super) fn next_pred_id<'a, S, B>(backend: &B, pred: &PredicateIds<'a, S, B>, cursor: &mut usize) -> Option<EntityId>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		loop {
+			let id = *pred.ids().entities().get(*cursor)?;
+			*cursor += 1;
+			
+			// Note: Ids are removed from all predicates along with their entity, so it must be alive
+			let entity = backend.get(id).expect("Predicate contained a dead entity");
+			if pred.matches_ticks(entity) {
+				return Some(id);
+			}
+		}
+	}
+	
+	/// Calls a closure on every chunk from multiple threads
+	/// 
+	/// At most [`std::thread::available_parallelism`] threads are spawned,
+	/// each taking the next chunk left once it's done with the previous one.
+	pub fn par_chunks<T, I, F>(chunks: I, f: F)
+	where
+		T: Send,
+		I: IntoIterator<Item = T>,
+		F: Fn(T) + Sync,
+	{
+		let chunks: Vec<T> = chunks.into_iter().collect();
+		let threads = std::thread::available_parallelism()
+			.map_or(1, std::num::NonZeroUsize::get)
+			.min( chunks.len() );
+		
+		// Note: We never panic while holding the lock, so we ignore any poisoning.
+		let chunks = Mutex::new( chunks.into_iter() );
+		std::thread::scope(|scope| for _ in 0..threads {
+			scope.spawn(|| loop {
+				let chunk = chunks.lock().unwrap_or_else(PoisonError::into_inner).next();
+				match chunk {
+					Some(chunk) => f(chunk),
+					None => break,
+				}
+			});
+		});
+	}
+//--------------------------------------------------------------------------------------------------
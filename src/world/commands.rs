@@ -0,0 +1,258 @@
+//! Command buffers
+//! 
+//! A command buffer records structural changes to a world, such as
+//! adding and removing entities or components, so they may be made
+//! while the world is borrowed, for example, during iteration.
+//! They are then all applied at once with [`World::apply`].
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{World, DynStorage};
+//! let mut world = World::new();
+//! world.add( mecs::entity![ DynStorage::new(1i32) ] );
+//! world.add( mecs::entity![ DynStorage::new(2i32) ] );
+//! let pred_id = world.add_pred(|entity| entity.has::<i32>());
+//! 
+//! // Replace every entity with an even number with a new entity
+//! let mut commands = world.commands();
+//! for (entity, id) in world.iter_pred(pred_id).unwrap() {
+//! 	if entity.get::<i32>().unwrap() % 2 == 0 {
+//! 		commands.despawn(id);
+//! 		let new_id = commands.spawn( mecs::entity![ DynStorage::new(3i32) ] );
+//! 		commands.insert_component(new_id, DynStorage::new("Hello, World!"));
+//! 	}
+//! }
+//! world.apply(commands);
+//! 
+//! let mut nums: Vec<i32> = world.iter_pred(pred_id).unwrap().map(|(entity, _)| *entity.get::<i32>().unwrap()).collect();
+//! nums.sort();
+//! assert_eq!(nums, [1, 3]);
+//! ```
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Collections
+use std::collections::{HashMap, HashSet};
+
+// Crate
+use crate::{util::KeyType, Storage, Component, Entity, EntityId};
+use super::{World, Backend, entity_id::EntityReserver};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A buffer of commands to apply to a world
+	/// 
+	/// Created with [`World::commands`] and only valid for that world.
+	/// 
+	/// If dropped without being applied, the ids of all entities it
+	/// would've spawned are released, so they're never created.
+	/// Ids created before that by [`World::flush_reserved`] are kept
+	/// as empty entities.
+	pub struct Commands<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The reserver for new entity ids
+		reserver: EntityReserver,
+		
+		/// All commands, in the order they were recorded
+		commands: Vec< Command<'a, S> >,
+	}
+	
+	/// A command to apply to a world
+	enum Command<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// Adds an entity with a reserved id
+		Spawn(EntityId, Entity<'a, S>),
+		
+		/// Removes an entity
+		Despawn(EntityId),
+		
+		/// Adds a component to an entity
+		InsertComponent(EntityId, S),
+		
+		/// Removes a component from an entity
+		RemoveComponent(EntityId, S::Id),
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S> Commands<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty command buffer for a world
+			#[must_use]
+			pub(in super) const fn new(reserver: EntityReserver) -> Self {
+				Self { reserver, commands: vec![] }
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Commands
+		//--------------------------------------------------------------------------------------------------
+			/// Adds an entity
+			/// 
+			/// # Return value
+			/// The id of the entity is reserved immediately, so it may be used by
+			/// any further commands, but it's only alive once the commands are applied.
+			pub fn spawn(&mut self, entity: Entity<'a, S>) -> EntityId
+			{
+				let id = self.reserver.reserve();
+				self.commands.push( Command::Spawn(id, entity) );
+				id
+			}
+			
+			/// Removes an entity
+			pub fn despawn(&mut self, id: EntityId) {
+				self.commands.push( Command::Despawn(id) );
+			}
+			
+			/// Adds a component to an entity
+			/// 
+			/// If the entity already contains a component with the
+			/// same id as `storage`, it is replaced.
+			pub fn insert_component(&mut self, id: EntityId, storage: S) {
+				self.commands.push( Command::InsertComponent(id, storage) );
+			}
+			
+			/// Removes a component from an entity given it's type
+			pub fn remove_component<C: Component<'a, S>>(&mut self, id: EntityId) {
+				self.remove_component_id( id, C::id() );
+			}
+			
+			/// Removes a component from an entity given it's id
+			pub fn remove_component_id(&mut self, id: EntityId, component_id: S::Id) {
+				self.commands.push( Command::RemoveComponent(id, component_id) );
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Access
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the number of commands recorded
+			#[must_use]
+			pub const fn len(&self) -> usize {
+				self.commands.len()
+			}
+			
+			/// Checks if no commands were recorded
+			#[must_use]
+			pub const fn is_empty(&self) -> bool {
+				self.commands.is_empty()
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl<'a, S, B> World<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		/// Creates an empty command buffer for this world
+		#[must_use]
+		pub fn commands(&self) -> Commands<'a, S> {
+			Commands::new( self.ids.reserver() )
+		}
+		
+		/// Applies all commands of a command buffer, in the order they were recorded
+		/// 
+		/// Commands on entities that aren't alive are ignored.
+		/// Spawned entities are inserted directly, while any other reserved
+		/// ids are created as empty entities, see [`World::flush_reserved`].
+		/// If a spawned entity was already created by a flush, it's components are
+		/// added to it instead. All predicates of entities that weren't spawned are only
+		/// updated once all commands are applied, and only if they depend on a changed
+		/// component, so each predicate is re-evaluated at most once for each entity.
+		/// 
+		/// # Panics
+		/// Panics if the command buffer was created by another world.
+		pub fn apply(&mut self, mut commands: Commands<'a, S>)
+		{
+			assert!(self.ids.owns(&commands.reserver), "Commands were created by another world");
+			let commands = std::mem::take(&mut commands.commands);
+			
+			// Create all reserved entities, except the ones we're spawning
+			let spawned: HashSet<EntityId> = commands.iter()
+				.filter_map(|command| match *command {
+					Command::Spawn(id, _) => Some(id),
+					_ => None,
+				})
+				.collect();
+			for id in self.ids.flush() {
+				if !spawned.contains(&id) {
+					self.insert_entity(id, Entity::new());
+				}
+			}
+			
+			// Then apply each command, keeping track of which components of each entity changed
+			let change_tick = self.change_tick;
+			let mut changed: HashMap<EntityId, Vec<S::Id>> = HashMap::new();
+			for command in commands {
+				match command {
+					// Note: If the id was flushed before we were applied, it's entity already exists, so we add our components to it
+					Command::Spawn(id, entity) => if self.entities.get(id).is_some() {
+						for storage in entity.into_components() {
+							let component_id = storage.id();
+							if let Ok(replaced) = self.entities.insert_component(id, storage, change_tick) {
+								self.observe_insert(id, &component_id, replaced.as_ref());
+								changed.entry(id).or_default().push(component_id);
+							}
+						}
+					} else if self.ids.is_alive(id) {
+						self.insert_entity(id, entity);
+					},
+					Command::Despawn(id) => {
+						self.remove(id);
+						changed.remove(&id);
+					},
+					Command::InsertComponent(id, storage) => {
+						let component_id = storage.id();
+						if let Ok(replaced) = self.entities.insert_component(id, storage, change_tick) {
+							self.observe_insert(id, &component_id, replaced.as_ref());
+							changed.entry(id).or_default().push(component_id);
+						}
+					},
+					Command::RemoveComponent(id, component_id) => if let Some(component) = self.entities.remove_component(id, &component_id) {
+						self.observers.removed(id, &component);
+						changed.entry(id).or_default().push(component_id);
+					},
+				}
+			}
+			
+			// And update all predicates that depend on the changed components
+			for (id, component_ids) in changed {
+				if let Some(entity) = self.entities.get(id) {
+					self.predicates.update_components(id, entity, &component_ids);
+				}
+			}
+		}
+	}
+	
+	impl<'a, S> Drop for Commands<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		fn drop(&mut self)
+		{
+			// Release the ids of all entities we didn't spawn
+			// Note: If we were applied, we won't have any commands left
+			for command in &self.commands {
+				if let Command::Spawn(id, _) = *command {
+					self.reserver.release(id);
+				}
+			}
+		}
+	}
+//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,205 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{World, Entity, Filter, EventReader, world::events::Spawned};
+
+// Sync
+use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type
+		#[derive(PartialEq, Clone, Debug)]
+		enum Components {
+			A(i32),
+			B(&'static str),
+			C(f32),
+		}
+	
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn apply()
+	{
+		let mut world: World<Components> = World::new();
+		let has_a = world.add_pred(Entity::has::<i32>);
+		let has_b = world.add_pred(Entity::has::<&str>);
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		let id2 = world.add( mecs::entity![ Components::A(2), Components::B("2") ] );
+		
+		let mut commands = world.commands();
+		let id3 = commands.spawn( mecs::entity![ Components::A(3) ] );
+		commands.insert_component(id3, Components::B("3"));
+		commands.insert_component(id1, Components::C(1.0));
+		commands.remove_component::<&str>(id2);
+		commands.despawn(id1);
+		assert_eq!(commands.len(), 5);
+		
+		// Nothing should change until the commands are applied
+		assert!(!world.is_alive(id3));
+		assert!(world.is_alive(id1));
+		
+		world.apply(commands);
+		assert!(!world.is_alive(id1));
+		assert_eq!(world[id2].get::<&str>(), None);
+		assert_eq!(world[id3].get::<i32>(), Some(&3));
+		assert_eq!(world[id3].get::<&str>(), Some(&"3"));
+		
+		let ids = |world: &World<Components>, pred_id| {
+			let mut ids: Vec<_> = world.iter_pred(pred_id).unwrap().map(|(_, id)| id).collect();
+			ids.sort_by_key(|id| id.idx());
+			ids
+		};
+		assert_eq!(ids(&world, has_a), [id2, id3]);
+		assert_eq!(ids(&world, has_b), [id3]);
+	}
+	
+	#[test]
+	fn spawn_during_iteration()
+	{
+		let mut world: World<Components> = World::new();
+		let pred_id = world.add_pred(Entity::has::<i32>);
+		
+		world.add( mecs::entity![ Components::A(1) ] );
+		world.add( mecs::entity![ Components::A(2) ] );
+		
+		let mut iter = world.iter_pred_mut(pred_id).unwrap();
+		while let Some( (entity, id) ) = iter.next() {
+			let num = *entity.get::<i32>().unwrap();
+			iter.commands().spawn( mecs::entity![ Components::A(num), Components::B("copy") ] );
+			iter.remove(id);
+		}
+		drop(iter);
+		
+		let mut nums: Vec<i32> = world.iter_pred(pred_id).unwrap()
+			.map(|(entity, _)| *entity.get::<i32>().unwrap())
+			.collect();
+		nums.sort_unstable();
+		assert_eq!(nums, [1, 2]);
+		assert!(world.iter_all().all(Entity::has::<&str>));
+	}
+	
+	#[test]
+	fn panic_during_iteration()
+	{
+		let mut world: World<Components> = World::new();
+		let pred_id = world.add_pred(Entity::has::<i32>);
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		
+		// Commands recorded before panicking shouldn't be applied
+		let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			let mut iter = world.iter_pred_mut(pred_id).unwrap();
+			let (_, id) = iter.next().unwrap();
+			iter.remove(id);
+			iter.commands().spawn( mecs::entity![ Components::A(2) ] );
+			panic!("Stopped iterating");
+		}));
+		assert!(result.is_err());
+		
+		world.flush_reserved();
+		assert!(world.is_alive(id1));
+		assert_eq!(world.iter_all().count(), 1);
+	}
+	
+	#[test]
+	fn reserved_before_add()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let mut commands = world.commands();
+		let id1 = commands.spawn( mecs::entity![ Components::A(1) ] );
+		
+		// Adding an entity before applying shouldn't take the reserved id
+		let id2 = world.add( mecs::entity![ Components::A(2) ] );
+		assert_ne!(id1, id2);
+		
+		world.apply(commands);
+		assert_eq!(world[id1].get::<i32>(), Some(&1));
+		assert_eq!(world[id2].get::<i32>(), Some(&2));
+	}
+	
+	#[test]
+	fn spawn_directly()
+	{
+		let mut world: World<Components> = World::new();
+		world.add_events::<Spawned>();
+		let empty = world.add_pred(|entity: &Entity<Components>| entity.ids().next().is_none());
+		let added = Arc::new( AtomicUsize::new(0) );
+		let added_clone = Arc::clone(&added);
+		world.on_add::<i32, _>(move |_, _| { added_clone.fetch_add(1, Ordering::Relaxed); });
+		
+		let mut commands = world.commands();
+		let id = commands.spawn( mecs::entity![ Components::A(1) ] );
+		let reserved_id = world.reserve_entity();
+		world.apply(commands);
+		
+		// The spawned entity should be inserted once, with it's components, while other reserved ids are still created
+		let spawned: Vec<_> = EventReader::<Spawned>::new().read( world.events().unwrap() ).map(|&Spawned(id)| id).collect();
+		assert_eq!(spawned, [reserved_id, id]);
+		assert_eq!(added.load(Ordering::Relaxed), 1);
+		assert_eq!(world.iter_pred(empty).unwrap().map(|(_, id)| id).collect::<Vec<_>>(), [reserved_id]);
+	}
+	
+	#[test]
+	fn spawn_flushed()
+	{
+		let mut world: World<Components> = World::new();
+		let has_all = world.add_filter( Filter::has::<i32>().and( Filter::has::<&str>() ).and( Filter::has::<f32>() ) );
+		let replaced = Arc::new( AtomicUsize::new(0) );
+		let replaced_clone = Arc::clone(&replaced);
+		world.on_replace::<i32, _>(move |_, _, _| { replaced_clone.fetch_add(1, Ordering::Relaxed); });
+		
+		let mut commands = world.commands();
+		let id = commands.spawn( mecs::entity![ Components::A(1), Components::B("1") ] );
+		
+		// Flushing before applying creates the entity, which may then be changed
+		world.flush_reserved();
+		assert_eq!(world.insert_component(id, Components::A(2)), Ok(None));
+		assert_eq!(world.insert_component(id, Components::C(2.0)), Ok(None));
+		world.apply(commands);
+		
+		// The spawned components should be added to it, replacing any with the same id
+		assert_eq!(world[id].get::<i32>(), Some(&1));
+		assert_eq!(world[id].get::<&str>(), Some(&"1"));
+		assert_eq!(world[id].get::<f32>(), Some(&2.0));
+		assert_eq!(replaced.load(Ordering::Relaxed), 1);
+		assert_eq!(world.iter_pred(has_all).unwrap().map(|(_, id)| id).collect::<Vec<_>>(), [id]);
+	}
+	
+	#[test]
+	fn dropped()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let mut commands = world.commands();
+		let id1 = commands.spawn( mecs::entity![ Components::A(1) ] );
+		drop(commands);
+		
+		// The reserved id should be released, so it's slot is reused
+		world.flush_reserved();
+		assert!(!world.is_alive(id1));
+		assert_eq!(world.iter_all().count(), 0);
+		
+		let id2 = world.add( mecs::entity![ Components::A(2) ] );
+		assert_eq!(id1.idx(), id2.idx());
+		assert_ne!(id1, id2);
+	}
+	
+	#[test]
+	#[should_panic(expected = "Commands were created by another world")]
+	fn other_world()
+	{
+		let world: World<Components> = World::new();
+		let mut other_world: World<Components> = World::new();
+		
+		other_world.apply( world.commands() );
+	}
+//--------------------------------------------------------------------------------------------------
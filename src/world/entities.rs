@@ -0,0 +1,537 @@
+//! The default backend of a world
+//! 
+//! Each entity is stored with it's own map of components, in a slot
+//! given by the index of it's id, so entities may be freely borrowed
+//! as an [`Entity`], with components added or removed without moving
+//! any other entity.
+//! 
+//! Entities are borrowed mutably as an [`EntityMut`], which may only modify
+//! their components, so they can only be added or removed through the world.
+
+// Crate
+use crate::{util::KeyType, Storage, Component, Entity, EntityId, Tick, ComponentTicks, Access, component::ComponentMask};
+use super::{backend, Backend, BackendRefs, PredicateIds, BackendRef, BackendMut, ReadEntity, Fetch, View, Query, query::{self, QueryComponent, ComponentRef}};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// Backend storing each entity with it's own map of components
+	/// 
+	/// This is the default backend of [`World`](super::World), which is the only
+	/// one whose entities may be borrowed as an [`Entity`], so it's the
+	/// only one supporting [`World::iter_all_mut`](super::World::iter_all_mut)
+	/// and indexing the world by entity id.
+	pub struct Entities<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// All of the entities, stored by the index of their entity id
+		pub(in super) slots: Vec< Option<(EntityId, Entity<'a, S>)> >,
+	}
+	
+	/// A mutable reference to an entity of [`Entities`]
+	/// 
+	/// Dereferences to the [`Entity`], but, unlike it, can't add or remove components.
+	pub struct EntityMut<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The entity
+		entity: &'w mut Entity<'a, S>,
+	}
+	
+	/// A view over the components of [`Entities`]
+	/// 
+	/// As each entity stores it's own components, they're split off from their
+	/// entities once, when the view is created, into a single list per view.
+	pub struct EntitiesView<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The components of all entities in this view, one entity after another
+		components: Vec<( &'w S::Id, ComponentRef<'w, S> )>,
+		
+		/// The id of each entity in this view, along with the end of it's components, sorted by index
+		entities: Vec<(EntityId, usize)>,
+	}
+	
+	/// The rows of a query over an [`EntitiesView`]
+	pub struct EntitiesViewFetch<'a, 'w, 'b, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The components of all entities after the last entity we returned
+		components: &'b mut [( &'w S::Id, ComponentRef<'w, S> )],
+		
+		/// The index of the first component in `components`
+		offset: usize,
+		
+		/// The ids and components of all entities left to return, sorted
+		entities: std::vec::IntoIter<( EntityId, std::ops::Range<usize> )>,
+		
+		/// The tick to mark components with if they're changed
+		change_tick: Tick,
+		
+		/// The tick to check if components were added or changed since
+		since: Tick,
+	}
+	
+	/// The rows of a query over [`Entities`]
+	pub struct EntitiesFetch<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// All slots after the last entity we returned
+		slots: &'w mut [Option<(EntityId, Entity<'a, S>)>],
+		
+		/// The index of the first slot in `slots`
+		offset: usize,
+		
+		/// The indices of all entities left to return, sorted
+		idxs: std::vec::IntoIter<usize>,
+		
+		/// The tick to mark components with if they're changed
+		change_tick: Tick,
+		
+		/// The tick to check if components were added or changed since
+		since: Tick,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S> Entities<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		/// Returns a mutable iterator over all entities, marking any changes with `change_tick`
+		pub(in super) fn iter_mut(&mut self, change_tick: Tick) -> impl Iterator<Item = EntityMut<'a, '_, S>>
+		{
+			self.slots.iter_mut()
+				.flatten()
+				.map(move |(_, entity)| {
+					entity.change_tick = change_tick;
+					EntityMut { entity }
+				})
+		}
+		
+		/// Mutably borrows an entity, marking any changes with `change_tick`
+		fn entity_mut(&mut self, id: EntityId, change_tick: Tick) -> Option<&mut Entity<'a, S>>
+		{
+			match self.slots.get_mut( id.idx() ) {
+				Some( Some( (entity_id, entity) ) ) if *entity_id == id => {
+					entity.change_tick = change_tick;
+					Some(entity)
+				},
+				_ => None,
+			}
+		}
+		
+		/// Mutably borrows a set of entities at once
+		/// 
+		/// # Panics
+		/// Panics if any of the entities doesn't exist or appears more than once.
+		#[allow(clippy::arithmetic_side_effects)] // We need to subtract the offset and add one to get the next offset
+		fn entities_mut(&mut self, ids: &[EntityId], change_tick: Tick) -> Vec<(EntityId, &mut Entity<'a, S>)>
+		{
+			let mut slot_idxs: Vec<usize> = ids.iter().map(|id| id.idx()).collect();
+			slot_idxs.sort_unstable();
+			
+			// Split each entity off from the ones after it
+			// Note: As the indices are sorted, we never need to go back.
+			let mut rest = &mut self.slots[..];
+			let mut offset = 0;
+			let mut entities = Vec::with_capacity( slot_idxs.len() );
+			for idx in slot_idxs {
+				let (slot, next) = std::mem::take(&mut rest)
+					.get_mut(idx - offset..)
+					.and_then(<[_]>::split_first_mut)
+					.expect("Entity appeared more than once");
+				rest = next;
+				offset = idx + 1;
+				
+				let (id, entity) = slot.as_mut().expect("Entity didn't exist");
+				entity.change_tick = change_tick;
+				entities.push( (*id, entity) );
+			}
+			
+			entities
+		}
+	}
+	
+	impl<'a, S> Backend<'a, S> for Entities<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		type Fetch<'w> = EntitiesFetch<'a, 'w, S> where Self: 'w;
+		type Cursor = usize;
+		
+		#[allow(clippy::arithmetic_side_effects)] // We need to add one to get the number of slots
+		fn insert(&mut self, id: EntityId, entity: Entity<'a, S>)
+		{
+			if self.slots.len() <= id.idx() {
+				self.slots.resize_with(id.idx() + 1, || None);
+			}
+			
+			self.slots[id.idx()] = Some( (id, entity) );
+		}
+		
+		#[allow(clippy::arithmetic_side_effects)] // We need to add one to get the number of slots
+		fn insert_batch(&mut self, entities: Vec<( EntityId, Entity<'a, S> )>)
+		{
+			// Make sure we have a slot for all of them at once
+			if let Some(max_idx) = entities.iter().map(|(id, _)| id.idx()).max() {
+				if self.slots.len() <= max_idx {
+					self.slots.resize_with(max_idx + 1, || None);
+				}
+			}
+			
+			for (id, entity) in entities {
+				self.slots[id.idx()] = Some( (id, entity) );
+			}
+		}
+		
+		fn remove(&mut self, id: EntityId) -> Option< Entity<'a, S> >
+		{
+			let slot = self.slots.get_mut( id.idx() )?;
+			match slot {
+				Some( (entity_id, _) ) if *entity_id == id => slot.take().map(|(_, entity)| entity),
+				_ => None,
+			}
+		}
+		
+		fn insert_component(&mut self, id: EntityId, storage: S, change_tick: Tick) -> Result<Option<S>, S>
+		{
+			match self.entity_mut(id, change_tick) {
+				Some(entity) => Ok( entity.add(storage) ),
+				None => Err(storage),
+			}
+		}
+		
+		fn remove_component(&mut self, id: EntityId, component_id: &S::Id) -> Option<S> {
+			self.entity_mut(id, Tick::default())?.remove_id(component_id)
+		}
+		
+		fn get(&self, id: EntityId) -> Option< BackendRef<'a, '_, S, Self> >
+		{
+			match self.slots.get( id.idx() ) {
+				Some( Some( (entity_id, entity) ) ) if *entity_id == id => Some(entity),
+				_ => None,
+			}
+		}
+		
+		fn get_mut(&mut self, id: EntityId, change_tick: Tick) -> Option< BackendMut<'a, '_, S, Self> > {
+			self.entity_mut(id, change_tick).map(|entity| EntityMut { entity })
+		}
+		
+		fn next_pred(&self, pred: &PredicateIds<'a, S, Self>, cursor: &mut usize) -> Option<( EntityId, BackendRef<'a, '_, S, Self> )>
+		{
+			let id = backend::next_pred_id(self, pred, cursor)?;
+			self.get(id).map(|entity| (id, entity))
+		}
+		
+		fn next_pred_mut(&mut self, pred: &PredicateIds<'a, S, Self>, cursor: &mut usize, change_tick: Tick) -> Option<( EntityId, BackendMut<'a, '_, S, Self> )>
+		{
+			let id = backend::next_pred_id(self, pred, cursor)?;
+			self.get_mut(id, change_tick).map(|entity| (id, entity))
+		}
+		
+		fn iter(&self) -> impl Iterator<Item = ( EntityId, BackendRef<'a, '_, S, Self> )> {
+			self.slots.iter()
+				.flatten()
+				.map(|(id, entity)| (*id, entity))
+		}
+		
+		fn into_entities(self) -> Vec<( EntityId, Entity<'a, S> )> {
+			self.slots.into_iter()
+				.flatten()
+				.collect()
+		}
+		
+		fn par_for_each<F>(&mut self, ids: &[EntityId], chunk_size: usize, change_tick: Tick, f: &F)
+		where
+			S    : Send,
+			S::Id: Send,
+			F    : Fn(BackendMut<'a, '_, S, Self>, EntityId) + Sync,
+		{
+			let mut entities = self.entities_mut(ids, change_tick).into_iter();
+			let chunks = std::iter::from_fn(|| Some( entities.by_ref().take(chunk_size).collect::<Vec<_>>() ).filter(|chunk| !chunk.is_empty()));
+			
+			// Note: Every entity was borrowed separately, so each chunk may be sent to a different thread
+			backend::par_chunks(chunks, |chunk| for (id, entity) in chunk {
+				f(EntityMut { entity }, id);
+			});
+		}
+		
+		fn fetch<'w>(&'w mut self, _required: &[S::Id], ids: &[EntityId], change_tick: Tick, since: Tick) -> Self::Fetch<'w>
+		{
+			// Get the indices of all entities, sorted, so we can borrow each of them in order
+			let mut slot_idxs: Vec<usize> = ids.iter().map(|id| id.idx()).collect();
+			slot_idxs.sort_unstable();
+			
+			EntitiesFetch {
+				slots: &mut self.slots,
+				offset: 0,
+				idxs: slot_idxs.into_iter(),
+				change_tick,
+				since,
+			}
+		}
+		
+		fn views(&mut self, accesses: &[&Access<'a, S>]) -> Vec< EntitiesView<'a, '_, S> >
+		{
+			let mut views: Vec<_> = accesses.iter()
+				.map(|_| EntitiesView { components: vec![], entities: vec![] })
+				.collect();
+			
+			for (id, entity) in self.slots.iter_mut().flatten() {
+				// Give each component to the view that writes it, or else to all views that read it
+				for (component_id, storage, ticks) in entity.components_ticks_mut() {
+					if let Some(writer) = accesses.iter().position(|access| access.writes_id(component_id)) {
+						views[writer].components.push( (component_id, ComponentRef::Unique(storage, ticks)) );
+						continue;
+					}
+					
+					let (storage, ticks) = (&*storage, &*ticks);
+					for (view, access) in views.iter_mut().zip(accesses) {
+						if access.reads_id(component_id) {
+							view.components.push( (component_id, ComponentRef::Shared(storage, ticks)) );
+						}
+					}
+				}
+				
+				// Then add the entity to all views that got any of it's components
+				for view in &mut views {
+					let start = view.entities.last().map_or(0, |&(_, end)| end);
+					if view.components.len() != start {
+						view.entities.push( (*id, view.components.len()) );
+					}
+				}
+			}
+			
+			views
+		}
+	}
+	
+	impl<'a, 'w, S> BackendRefs<'a, 'w, S> for Entities<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		type Ref = &'w Entity<'a, S>;
+		type Mut = EntityMut<'a, 'w, S>;
+		type View = EntitiesView<'a, 'w, S>;
+	}
+	
+	impl<'a, 'w, S> View<'a, 'w, S> for EntitiesView<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		type Fetch<'b> = EntitiesViewFetch<'a, 'w, 'b, S> where Self: 'b;
+		
+		#[allow(clippy::arithmetic_side_effects)] // We need to subtract one to get the previous entity
+		fn fetch(&mut self, ids: Option<&[EntityId]>, change_tick: Tick, since: Tick) -> EntitiesViewFetch<'a, 'w, '_, S>
+		{
+			// Get the positions of all entities in this view, sorted, so we can borrow each of them in order
+			let mut positions: Vec<usize> = match ids {
+				Some(ids) => ids.iter()
+					.filter_map(|id| {
+						let position = self.entities.binary_search_by_key(&id.idx(), |(id, _)| id.idx()).ok()?;
+						(self.entities[position].0 == *id).then_some(position)
+					})
+					.collect(),
+				None => (0..self.entities.len()).collect(),
+			};
+			positions.sort_unstable();
+			
+			#[allow(clippy::needless_collect)] // The fetch needs to name it's iterator
+			let entities: Vec<_> = positions.into_iter()
+				.map(|position| {
+					let start = position.checked_sub(1).map_or(0, |prev| self.entities[prev].1);
+					let (id, end) = self.entities[position];
+					(id, start..end)
+				})
+				.collect();
+			
+			EntitiesViewFetch {
+				components: &mut self.components,
+				offset: 0,
+				entities: entities.into_iter(),
+				change_tick,
+				since,
+			}
+		}
+	}
+	
+	impl<'a, 'b, S> Fetch<'a, 'b, S> for EntitiesViewFetch<'a, '_, 'b, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		#[allow(clippy::arithmetic_side_effects)] // We need to subtract the offset to get the start of each entity
+		fn next<Q: Query<'a, 'b, S>>(&mut self) -> Option<(EntityId, Q)>
+		{
+			let (change_tick, since) = (self.change_tick, self.since);
+			for (id, range) in self.entities.by_ref()
+			{
+				// Split the entity's components off from the ones after them
+				// Note: As the entities are sorted, we never need to go back.
+				let (components, rest) = std::mem::take(&mut self.components)[range.start - self.offset..]
+					.split_at_mut(range.len());
+				self.components = rest;
+				self.offset = range.end;
+				
+				// And fetch the query from them
+				let components = components.iter_mut()
+					.map(|(component_id, storage)| QueryComponent::new(*component_id, storage.reborrow(), change_tick, since));
+				if let Some(query) = query::fetch(components) {
+					return Some( (id, query) );
+				}
+			}
+			
+			// If we get here, return None
+			None
+		}
+		
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(0, Some( self.entities.len() ))
+		}
+	}
+	
+	impl<'a, 'w, S> ReadEntity<'a, 'w, S> for &'w Entity<'a, S>
+	where
+		S    : Storage<'a> + 'w,
+		S::Id: KeyType + 'a + 'w,
+	{
+		fn get_id(self, id: &S::Id) -> Option<&'w S> {
+			Entity::get_id(self, id)
+		}
+		
+		fn has_id(self, id: &S::Id) -> bool {
+			Entity::has_id(self, id)
+		}
+		
+		fn ticks_id(self, id: &S::Id) -> Option<ComponentTicks> {
+			Entity::ticks_id(self, id)
+		}
+		
+		fn mask(self) -> &'w ComponentMask {
+			Entity::mask(self)
+		}
+		
+		fn ids(self) -> impl Iterator<Item = &'w S::Id>
+		where
+			S::Id: 'w,
+		{
+			Entity::ids(self)
+		}
+		
+		fn components(self) -> impl Iterator<Item = &'w S>
+		where
+			S: 'w,
+		{
+			Entity::components(self)
+		}
+	}
+	
+	impl<'a, 'w, S> Fetch<'a, 'w, S> for EntitiesFetch<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		#[allow(clippy::arithmetic_side_effects)] // We need to subtract the offset and add one to get the next offset
+		fn next<Q: Query<'a, 'w, S>>(&mut self) -> Option<(EntityId, Q)>
+		{
+			// While we have a next index, try to fetch the query from it
+			for idx in self.idxs.by_ref()
+			{
+				// Split the entity off from the ones after it
+				// Note: As the indices are sorted, we never need to go back.
+				let slots = std::mem::take(&mut self.slots);
+				let (slot, rest) = slots[idx - self.offset..]
+					.split_first_mut()
+					.expect("Query index was out of bounds");
+				self.slots = rest;
+				self.offset = idx + 1;
+				
+				// Get the entity
+				// Note: Ids are removed from all predicates along with their entity, so it must be alive
+				let (id, entity) = slot.as_mut().expect("Query contained a dead entity");
+				entity.change_tick = self.change_tick;
+				
+				// And fetch the query from it
+				let (change_tick, since) = (self.change_tick, self.since);
+				let components = entity.components_ticks_mut()
+					.map(|(id, storage, ticks)| QueryComponent::new(id, ComponentRef::Unique(storage, ticks), change_tick, since));
+				if let Some(query) = query::fetch(components) {
+					return Some( (*id, query) );
+				}
+			}
+			
+			// If we get here, return None
+			None
+		}
+		
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(0, Some( self.idxs.len() ))
+		}
+	}
+	
+	impl<'a, S> EntityMut<'a, '_, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		/// Returns a mutable reference to a component given it's type
+		/// 
+		/// This marks the component as changed.
+		#[must_use]
+		pub fn get_mut<C: Component<'a, S>>(&mut self) -> Option<&mut C> {
+			self.entity.get_mut()
+		}
+		
+		/// Returns a mutable reference to a component's storage given it's id
+		/// 
+		/// This marks the component as changed.
+		#[must_use]
+		pub fn get_mut_id(&mut self, id: &S::Id) -> Option<&mut S> {
+			self.entity.get_mut_id(id)
+		}
+		
+		/// Returns a mutable iterator over all components in this entity
+		/// 
+		/// This marks all components as changed.
+		pub fn components_mut(&mut self) -> impl Iterator<Item = &mut S> + use<'a, '_, S> {
+			self.entity.components_mut()
+		}
+	}
+	
+	impl<'a, S> std::ops::Deref for EntityMut<'a, '_, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		type Target = Entity<'a, S>;
+		
+		fn deref(&self) -> &Entity<'a, S> {
+			self.entity
+		}
+	}
+	
+	impl<'a, S> Default for Entities<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		fn default() -> Self {
+			Self { slots: vec![] }
+		}
+	}
+//--------------------------------------------------------------------------------------------------
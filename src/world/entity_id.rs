@@ -1,11 +1,117 @@
 //! Entity Ids
+//! 
+//! Each entity id is made of an index, which identifies the slot
+//! the entity lives in, and a generation, which is increased every
+//! time the slot is freed.
+//! This allows slots to be reused once their entity is removed, while
+//! still being able to detect when an old id is used after it's entity
+//! has been removed.
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Conversions
+use std::convert::TryFrom;
+
+// Collections
+use std::collections::HashSet;
+
+// Atomic
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, atomic::{AtomicU32, Ordering}};
 
 // Types
 //--------------------------------------------------------------------------------------------------
 	/// An entity's ID
 	#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
 	#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize, serde::Deserialize))]
-	pub struct EntityId( usize );
+	#[cfg_attr(feature = "serde-serialize", serde(from = "u64", into = "u64"))]
+	pub struct EntityId
+	{
+		/// The index of this id's slot
+		idx: u32,
+		
+		/// The generation of this id's slot
+		generation: u32,
+	}
+	
+	/// A range of contiguous entity ids, as returned by [`World::spawn_batch`](crate::World::spawn_batch)
+	#[derive(PartialEq, Eq, Clone, Debug)]
+	pub struct EntityIdRange
+	{
+		/// The index of the first slot
+		start: u32,
+		
+		/// The index after the last slot
+		end: u32,
+	}
+	
+	/// Allocator for entity ids
+	/// 
+	/// Slots are reused after being freed, with their
+	/// generation increased so that any ids still referring
+	/// to the old entity are no longer alive.
+	/// 
+	/// # Reserving
+	/// Ids may also be reserved without mutable access to the
+	/// allocator through an [`EntityReserver`]. Reserved ids reuse
+	/// free slots like allocated ones, but they're only alive once
+	/// the allocator is flushed with [`EntityAllocator::flush`].
+	/// Reserved ids may be released before then, in which case their
+	/// slots are freed by the flush instead.
+	/// 
+	/// Ranges of ids must be contiguous, so they always use new slots.
+	#[derive(Debug)]
+	pub(crate) struct EntityAllocator
+	{
+		/// All slots
+		slots: Vec<EntitySlot>,
+		
+		/// The number of slots, including all reserved ones
+		len: Arc<AtomicU32>,
+		
+		/// The free and reserved ids, shared with all reservers
+		shared: Arc< Mutex<SharedIds> >,
+		
+		/// Ids reserved while allocating that weren't yet flushed
+		pending: Vec<EntityId>,
+	}
+	
+	/// Reserves entity ids from an [`EntityAllocator`]
+	#[derive(Clone, Debug)]
+	pub(crate) struct EntityReserver
+	{
+		/// The number of slots of the allocator, including all reserved ones
+		len: Arc<AtomicU32>,
+		
+		/// The free and reserved ids of the allocator
+		shared: Arc< Mutex<SharedIds> >,
+	}
+	
+	/// Ids shared between an [`EntityAllocator`] and it's reservers
+	#[derive(Default, Debug)]
+	struct SharedIds
+	{
+		/// Ids of all free slots, with their next generation
+		free: Vec<EntityId>,
+		
+		/// Ids of free slots reserved since the last flush
+		reserved: Vec<EntityId>,
+		
+		/// Reserved ids released before being flushed
+		released: Vec<EntityId>,
+	}
+	
+	/// A slot within an [`EntityAllocator`]
+	#[derive(Clone, Copy, Debug)]
+	struct EntitySlot
+	{
+		/// The current generation of this slot
+		generation: u32,
+		
+		/// If this slot is currently in use
+		alive: bool,
+	}
 //--------------------------------------------------------------------------------------------------
 
 // Impl
@@ -14,25 +120,47 @@
 	{
 		// Constructors
 		//--------------------------------------------------------------------------------------------------
-			/// Constructs an entity id from a [`usize`]
+			/// Constructs an entity id from it's index and generation
 			#[must_use]
-			pub(crate) const fn new(value: usize) -> Self {
-				Self(value)
+			pub(crate) const fn new(idx: u32, generation: u32) -> Self {
+				Self { idx, generation }
 			}
 			
 			/// Returns a null entity id
 			#[must_use]
 			pub const fn null() -> Self {
-				Self::new(0)
+				Self::new(0, 0)
+			}
+			
+			/// Constructs an entity id from it's bits, as returned by [`EntityId::to_bits`]
+			#[must_use]
+			#[allow(clippy::as_conversions, clippy::cast_possible_truncation)] // We're splitting the bits in half
+			pub const fn from_bits(bits: u64) -> Self {
+				Self::new(bits as u32, (bits >> 32) as u32)
 			}
 		//--------------------------------------------------------------------------------------------------
 		
-		// Modifiers
+		// Access
 		//--------------------------------------------------------------------------------------------------
-			/// Increments this id
-			#[allow(clippy::integer_arithmetic)] // We need to add one to get the next id
-			pub(crate) fn inc(&mut self) {
-				self.0 += 1;
+			/// Returns the index of this id, for use in dense collections
+			#[must_use]
+			#[allow(clippy::as_conversions)] // A `u32` always fits into a `usize` on the platforms we support
+			pub const fn idx(self) -> usize {
+				self.idx as usize
+			}
+			
+			/// Returns the generation of this id
+			#[must_use]
+			pub const fn generation(self) -> u32 {
+				self.generation
+			}
+			
+			/// Returns the bits of this id, with the generation
+			/// in the upper half and the index in the lower half
+			#[must_use]
+			#[allow(clippy::as_conversions)] // We're joining both halves
+			pub const fn to_bits(self) -> u64 {
+				((self.generation as u64) << 32) | (self.idx as u64)
 			}
 		//--------------------------------------------------------------------------------------------------
 		
@@ -41,8 +169,356 @@
 			/// Checks if this id is null
 			#[must_use]
 			pub const fn is_null(self) -> bool {
-				self.0 == 0
+				self.idx == 0
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl From<u64> for EntityId {
+		fn from(bits: u64) -> Self {
+			Self::from_bits(bits)
+		}
+	}
+	
+	impl From<EntityId> for u64 {
+		fn from(id: EntityId) -> Self {
+			id.to_bits()
+		}
+	}
+	
+	impl EntityIdRange
+	{
+		/// Checks if an id is within this range
+		#[must_use]
+		pub const fn contains(&self, id: EntityId) -> bool {
+			id.generation == 0 && self.start <= id.idx && id.idx < self.end
+		}
+	}
+	
+	impl Iterator for EntityIdRange
+	{
+		type Item = EntityId;
+		
+		fn next(&mut self) -> Option<Self::Item>
+		{
+			let idx = (self.start..self.end).next()?;
+			self.start = idx.wrapping_add(1);
+			Some( EntityId::new(idx, 0) )
+		}
+		
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(self.start..self.end).size_hint()
+		}
+	}
+	
+	impl DoubleEndedIterator for EntityIdRange
+	{
+		fn next_back(&mut self) -> Option<Self::Item>
+		{
+			let idx = (self.start..self.end).next_back()?;
+			self.end = idx;
+			Some( EntityId::new(idx, 0) )
+		}
+	}
+	
+	impl ExactSizeIterator for EntityIdRange {}
+	
+	impl EntityAllocator
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates a new allocator
+			#[must_use]
+			pub(crate) fn new() -> Self
+			{
+				// Note: The slot 0 is never given out so it can be a null id
+				Self {
+					slots: vec![ EntitySlot{ generation: 0, alive: false } ],
+					len: Arc::new( AtomicU32::new(1) ),
+					shared: Arc::new( Mutex::new( SharedIds::default() ) ),
+					pending: vec![],
+				}
 			}
+			
+			/// Creates an allocator given the generation of each slot and the ids of all alive slots
+			/// 
+			/// Returns `None` if any id isn't valid or is repeated.
+			#[cfg(feature = "serde-serialize")]
+			pub(crate) fn from_generations<I>(generations: Vec<u32>, alive: I) -> Option<Self>
+			where
+				I: IntoIterator<Item = EntityId>
+			{
+				let mut slots: Vec<_> = generations.into_iter()
+					.map(|generation| EntitySlot{ generation, alive: false })
+					.collect();
+				let len = u32::try_from( slots.len() ).ok().filter(|&len| len != 0 && len != u32::MAX)?;
+				
+				for id in alive {
+					let slot = slots.get_mut( id.idx() ).filter(|_| !id.is_null())?;
+					if slot.alive || slot.generation != id.generation {
+						return None;
+					}
+					slot.alive = true;
+				}
+				
+				// Note: We reverse them so the lowest slots are reused first
+				// Note: Slots whose generation can't be bumped anymore were retired, see `free`
+				let free = (1..len).rev()
+					.map(|idx| EntityId::new(idx, slots[EntityId::new(idx, 0).idx()].generation))
+					.filter(|id| !slots[id.idx()].alive && id.generation != u32::MAX)
+					.collect();
+				
+				Some( Self {
+					slots,
+					len: Arc::new( AtomicU32::new(len) ),
+					shared: Arc::new( Mutex::new( SharedIds { free, ..SharedIds::default() } ) ),
+					pending: vec![],
+				} )
+			}
+			
+			/// Returns a reserver for this allocator
+			#[must_use]
+			pub(crate) fn reserver(&self) -> EntityReserver {
+				EntityReserver { len: Arc::clone(&self.len), shared: Arc::clone(&self.shared) }
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Alloc / Free
+		//--------------------------------------------------------------------------------------------------
+			/// Allocates a new id, reusing a free slot if there are any
+			pub(crate) fn alloc(&mut self) -> EntityId
+			{
+				// If we have a free slot, reuse it
+				let free = lock(&self.shared).free.pop();
+				if let Some(id) = free
+				{
+					self.slots[id.idx()].alive = true;
+					return id;
+				}
+				
+				// Else create a new one
+				// Note: If any ids were reserved since the last flush, we'll create their slots
+				//       too, but as we can't return them, they're kept until the next flush.
+				let id = EntityId::new(reserve_range(&self.len, 1).start, 0);
+				self.push_pending(id.idx);
+				self.slots.push( EntitySlot{ generation: 0, alive: true } );
+				id
+			}
+			
+			/// Allocates `len` ids, reusing free slots if there are any
+			/// 
+			/// Unlike [`EntityAllocator::alloc_range`], the ids aren't contiguous.
+			pub(crate) fn alloc_many(&mut self, len: usize) -> Vec<EntityId>
+			{
+				// Reuse as many free slots as we can
+				let mut ids = {
+					let mut shared = lock(&self.shared);
+					let start = shared.free.len().saturating_sub(len);
+					shared.free.split_off(start)
+				};
+				ids.reverse();
+				for id in &ids {
+					self.slots[id.idx()].alive = true;
+				}
+				
+				// And create new ones for the rest
+				// Note: `ids` has at most `len` elements.
+				#[allow(clippy::arithmetic_side_effects)]
+				let range = self.alloc_range(len - ids.len());
+				ids.extend(range);
+				ids
+			}
+			
+			/// Allocates a range of contiguous ids, always using new slots
+			pub(crate) fn alloc_range(&mut self, len: usize) -> EntityIdRange
+			{
+				let range = reserve_range(&self.len, len);
+				
+				self.push_pending(range.start);
+				for _ in range.start..range.end {
+					self.slots.push( EntitySlot{ generation: 0, alive: true } );
+				}
+				
+				range
+			}
+			
+			/// Creates the slots of all ids reserved before `idx` and keeps them until the next flush
+			fn push_pending(&mut self, idx: u32)
+			{
+				let start = u32::try_from( self.slots.len() ).expect("Ran out of entity ids");
+				for idx in start..idx {
+					self.slots.push( EntitySlot{ generation: 0, alive: true } );
+					self.pending.push( EntityId::new(idx, 0) );
+				}
+			}
+			
+			/// Reserves a new id
+			/// 
+			/// See [`EntityReserver::reserve`].
+			pub(crate) fn reserve(&self) -> EntityId {
+				reserve(&self.len, &self.shared)
+			}
+			
+			/// Reserves a range of contiguous new ids
+			/// 
+			/// The ids only become alive once this allocator is flushed.
+			pub(crate) fn reserve_range(&self, len: usize) -> EntityIdRange {
+				reserve_range(&self.len, len)
+			}
+			
+			/// Creates the slots of all reserved ids, returning them
+			/// 
+			/// The slots of all released ids are freed instead.
+			pub(crate) fn flush(&mut self) -> Vec<EntityId>
+			{
+				// Note: Pending ids may have been freed before being created
+				let mut ids = std::mem::take(&mut self.pending);
+				ids.retain(|&id| self.is_alive(id));
+				
+				// Then create all reserved free slots
+				let (reserved, released) = {
+					let mut shared = lock(&self.shared);
+					(std::mem::take(&mut shared.reserved), std::mem::take(&mut shared.released))
+				};
+				for id in reserved {
+					self.slots[id.idx()].alive = true;
+					ids.push(id);
+				}
+				
+				// And all reserved new slots
+				let len = self.len.load(Ordering::Relaxed);
+				let start = u32::try_from( self.slots.len() ).expect("Ran out of entity ids");
+				for idx in start..len {
+					self.slots.push( EntitySlot{ generation: 0, alive: true } );
+					ids.push( EntityId::new(idx, 0) );
+				}
+				
+				// Finally free all released ids we're creating
+				// Note: Any ids released after being flushed were already created, so we keep them.
+				let released: HashSet<EntityId> = released.into_iter().collect();
+				if !released.is_empty() {
+					ids.retain(|id| !released.contains(id) || !self.free(*id));
+				}
+				
+				ids
+			}
+			
+			/// Frees an id, returning if it was alive
+			pub(crate) fn free(&mut self, id: EntityId) -> bool
+			{
+				if !self.is_alive(id) {
+					return false;
+				}
+				
+				// Bump the generation so any old ids are no longer alive
+				// Note: If the generation would overflow, we retire the slot instead, else
+				//       ids from before the generation wrapped around would be alive again.
+				let slot = &mut self.slots[id.idx()];
+				slot.alive = false;
+				if let Some(generation) = slot.generation.checked_add(1) {
+					slot.generation = generation;
+					lock(&self.shared).free.push( EntityId::new(id.idx, generation) );
+				}
+				
+				true
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Access
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the current generation of each slot
+			#[cfg(feature = "serde-serialize")]
+			pub(crate) fn generations(&self) -> impl Iterator<Item = u32> + '_ {
+				self.slots.iter().map(|slot| slot.generation)
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Checks
 		//--------------------------------------------------------------------------------------------------
+			/// Checks if an id is alive
+			#[must_use]
+			pub(crate) fn is_alive(&self, id: EntityId) -> bool {
+				self.slots.get(id.idx())
+					.is_some_and(|slot| slot.alive && slot.generation == id.generation)
+			}
+			
+			/// Checks if a reserver belongs to this allocator
+			#[must_use]
+			pub(crate) fn owns(&self, reserver: &EntityReserver) -> bool {
+				Arc::ptr_eq(&self.len, &reserver.len)
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl EntityReserver
+	{
+		/// Reserves a new id
+		/// 
+		/// The id only becomes alive once it's allocator is flushed.
+		pub(crate) fn reserve(&self) -> EntityId {
+			reserve(&self.len, &self.shared)
+		}
+		
+		/// Releases a reserved id, so it's slot is freed instead of created on the next flush
+		/// 
+		/// Does nothing if the id was already flushed.
+		pub(crate) fn release(&self, id: EntityId) {
+			lock(&self.shared).released.push(id);
+		}
+	}
+	
+	impl Default for EntityAllocator {
+		fn default() -> Self {
+			Self::new()
+		}
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Reserves an id, reusing a free slot if there are any, or else reserving a new slot given the number of slots
+	fn reserve(len: &AtomicU32, shared: &Mutex<SharedIds>) -> EntityId
+	{
+		// If we have a free slot, reuse it
+		let mut shared = lock(shared);
+		if let Some(id) = shared.free.pop() {
+			shared.reserved.push(id);
+			return id;
+		}
+		drop(shared);
+		
+		// Else reserve a new one
+		let range = reserve_range(len, 1);
+		EntityId::new(range.start, 0)
+	}
+	
+	/// Reserves the ids of a range of new slots given the number of slots
+	/// 
+	/// # Panics
+	/// Panics if there aren't enough ids left, without reserving any.
+	fn reserve_range(len: &AtomicU32, count: usize) -> EntityIdRange
+	{
+		let count = u32::try_from(count).expect("Ran out of entity ids");
+		
+		// Note: We can't just add to the number of slots, as once it
+		//       wraps around, we'd be handing out slots already in use.
+		let mut start = len.load(Ordering::Relaxed);
+		loop {
+			let end = start.checked_add(count)
+				.filter(|&end| end != u32::MAX)
+				.expect("Ran out of entity ids");
+			
+			match len.compare_exchange_weak(start, end, Ordering::Relaxed, Ordering::Relaxed) {
+				Ok(_) => return EntityIdRange { start, end },
+				Err(cur_len) => start = cur_len,
+			}
+		}
+	}
+	
+	/// Locks the ids shared with reservers
+	/// 
+	/// As the ids are always left valid, we ignore any poisoning.
+	fn lock(shared: &Mutex<SharedIds>) -> MutexGuard<'_, SharedIds> {
+		shared.lock().unwrap_or_else(PoisonError::into_inner)
 	}
 //--------------------------------------------------------------------------------------------------
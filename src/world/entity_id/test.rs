@@ -0,0 +1,226 @@
+//! Tests
+
+// Crate
+use super::{EntityId, EntityAllocator};
+
+// Atomic
+use std::sync::atomic::Ordering;
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn alloc_reuses_slots()
+	{
+		let mut ids = EntityAllocator::new();
+		
+		let id1 = ids.alloc();
+		let id2 = ids.alloc();
+		assert!(!id1.is_null());
+		assert_ne!(id1, id2);
+		assert!(ids.is_alive(id1));
+		assert!(ids.is_alive(id2));
+		
+		assert!( ids.free(id1));
+		assert!(!ids.free(id1));
+		assert!(!ids.is_alive(id1));
+		
+		let id3 = ids.alloc();
+		assert_eq!(id3.idx(), id1.idx());
+		assert_ne!(id3.generation(), id1.generation());
+		assert!(!ids.is_alive(id1));
+		assert!( ids.is_alive(id3));
+	}
+	
+	#[test]
+	fn null_is_never_alive()
+	{
+		let mut ids = EntityAllocator::new();
+		ids.alloc();
+		
+		assert!(EntityId::null().is_null());
+		assert!(!ids.is_alive( EntityId::null() ));
+		assert!(!ids.free( EntityId::null() ));
+	}
+	
+	#[test]
+	fn reserve()
+	{
+		let mut ids = EntityAllocator::new();
+		let reserver = ids.reserver();
+		assert!(ids.owns(&reserver));
+		assert!(!EntityAllocator::new().owns(&reserver));
+		
+		let id1 = reserver.reserve();
+		assert!(!ids.is_alive(id1));
+		
+		// Allocating after reserving shouldn't give out the reserved slot
+		let id2 = ids.alloc();
+		let id3 = reserver.reserve();
+		assert_ne!(id1, id2);
+		assert_ne!(id2, id3);
+		
+		assert_eq!(ids.flush(), [id1, id3]);
+		assert!(ids.is_alive(id1));
+		assert!(ids.is_alive(id3));
+		assert!(ids.flush().is_empty());
+	}
+	
+	#[test]
+	fn alloc_range()
+	{
+		let mut ids = EntityAllocator::new();
+		let reserver = ids.reserver();
+		
+		let id1 = ids.alloc();
+		assert!(ids.free(id1));
+		let id2 = reserver.reserve();
+		
+		// Ranges never reuse free slots nor reserved ones
+		let range = ids.alloc_range(3);
+		assert_eq!(range.len(), 3);
+		assert!(range.clone().all(|id| ids.is_alive(id) && range.contains(id)));
+		assert!(!range.contains(id1));
+		assert!(!range.contains(id2));
+		assert_eq!(ids.flush(), [id2]);
+		
+		assert_eq!(ids.alloc_range(0).count(), 0);
+	}
+	
+	#[test]
+	fn alloc_many()
+	{
+		let mut ids = EntityAllocator::new();
+		
+		let id1 = ids.alloc();
+		let id2 = ids.alloc();
+		assert!(ids.free(id1));
+		assert!(ids.free(id2));
+		
+		// Free slots should be reused first, in the same order as `alloc`
+		let many = ids.alloc_many(3);
+		assert_eq!(many.len(), 3);
+		assert_eq!(many[0].idx(), id2.idx());
+		assert_eq!(many[1].idx(), id1.idx());
+		assert!(many.iter().all(|&id| ids.is_alive(id)));
+		assert!(ids.alloc_many(0).is_empty());
+	}
+	
+	#[test]
+	fn reserve_reuses_slots()
+	{
+		let mut ids = EntityAllocator::new();
+		let reserver = ids.reserver();
+		
+		let id1 = ids.alloc();
+		assert!(ids.free(id1));
+		
+		// Reserving should reuse the free slot, without allocating giving it out again
+		let id2 = reserver.reserve();
+		assert_eq!(id2.idx(), id1.idx());
+		assert!(!ids.is_alive(id2));
+		let id3 = ids.alloc();
+		assert_ne!(id3.idx(), id2.idx());
+		
+		assert_eq!(ids.flush(), [id2]);
+		assert!(ids.is_alive(id2));
+	}
+	
+	#[test]
+	fn release()
+	{
+		let mut ids = EntityAllocator::new();
+		let reserver = ids.reserver();
+		
+		let id1 = ids.alloc();
+		assert!(ids.free(id1));
+		let id2 = reserver.reserve();
+		let id3 = reserver.reserve();
+		
+		// Released ids should be freed by the flush, instead of created
+		reserver.release(id2);
+		reserver.release(id3);
+		assert!(ids.flush().is_empty());
+		assert!(!ids.is_alive(id2));
+		assert!(!ids.is_alive(id3));
+		assert_eq!(ids.alloc().idx(), id3.idx());
+		assert_eq!(ids.alloc().idx(), id2.idx());
+	}
+	
+	#[test]
+	fn reserve_range()
+	{
+		let mut ids = EntityAllocator::new();
+		
+		let id1 = ids.reserve();
+		let range = ids.reserve_range(2);
+		assert!(!range.contains(id1));
+		assert!(range.clone().all(|id| !ids.is_alive(id)));
+		
+		// Allocating should skip over all reserved ids
+		let id2 = ids.alloc();
+		assert!(!range.contains(id2));
+		
+		let mut flushed = vec![id1];
+		flushed.extend(range);
+		assert_eq!(ids.flush(), flushed);
+	}
+	
+	#[test]
+	fn free_pending()
+	{
+		let mut ids = EntityAllocator::new();
+		let reserver = ids.reserver();
+		
+		// Ids reserved while allocating are alive, but only created on the next flush
+		let id1 = reserver.reserve();
+		let id2 = ids.alloc();
+		assert!(ids.is_alive(id1));
+		assert!(ids.free(id1));
+		
+		assert!(ids.flush().is_empty());
+		assert!(ids.is_alive(id2));
+	}
+	
+	#[test]
+	fn free_retires_exhausted_slots()
+	{
+		let mut ids = EntityAllocator::new();
+		
+		// Pretend the slot was already reused as many times as it may be
+		let id1 = ids.alloc();
+		ids.slots[id1.idx()].generation = u32::MAX;
+		let id1 = EntityId::new(id1.idx, u32::MAX);
+		assert!(ids.is_alive(id1));
+		assert!(ids.free(id1));
+		
+		// It's slot should never be reused, as it's generation would wrap around
+		let id2 = ids.alloc();
+		let id3 = ids.alloc();
+		assert_ne!(id2.idx(), id1.idx());
+		assert_ne!(id3.idx(), id1.idx());
+		assert!(!ids.is_alive(id1));
+		assert!(!ids.is_alive( EntityId::new(id1.idx, 0) ));
+	}
+	
+	#[test]
+	fn reserve_exhausted()
+	{
+		let ids = EntityAllocator::new();
+		ids.len.store(u32::MAX - 1, Ordering::Relaxed);
+		
+		// Running out of ids shouldn't wrap around to slots already in use
+		assert!(std::panic::catch_unwind(|| ids.reserve()).is_err());
+		assert!(std::panic::catch_unwind(|| ids.reserve_range(2)).is_err());
+		assert_eq!(ids.len.load(Ordering::Relaxed), u32::MAX - 1);
+	}
+	
+	#[test]
+	fn bits()
+	{
+		let id = EntityId::new(5, 7);
+		
+		assert_eq!(EntityId::from_bits( id.to_bits() ), id);
+		assert_eq!(u64::from(id), id.to_bits());
+		assert_eq!(EntityId::from(id.to_bits()), id);
+	}
+//--------------------------------------------------------------------------------------------------
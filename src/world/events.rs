@@ -0,0 +1,261 @@
+//! Events
+//! 
+//! Events are messages sent between systems, stored in the
+//! world as a resource of type [`Events`] for each type of event.
+//! Each system keeps it's own [`EventReader`], through which it
+//! sees every event exactly once.
+//! 
+//! Events are double buffered, so each event stays alive for
+//! two ticks, see [`World::increment_change_tick`], after which
+//! it's dropped, whether or not every reader has seen it.
+//! 
+//! The world sends a [`Spawned`] and [`Despawned`] event whenever an entity
+//! is added or removed, but only once they're registered, so worlds that
+//! don't read them don't keep them around.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{World, DynStorage, EventReader, world::events::Spawned};
+//! /// A collision between two entities
+//! struct Collision(mecs::EntityId, mecs::EntityId);
+//! 
+//! let mut world: World<DynStorage> = World::new();
+//! world.add_events::<Collision>();
+//! world.add_events::<Spawned>();
+//! 
+//! let mut spawned = EventReader::new();
+//! let mut collisions = EventReader::new();
+//! 
+//! let id1 = world.add( mecs::entity![] );
+//! let id2 = world.add( mecs::entity![] );
+//! world.send_event( Collision(id1, id2) );
+//! 
+//! assert_eq!(spawned.read( world.events::<Spawned>().unwrap() ).count(), 2);
+//! assert_eq!(collisions.read( world.events::<Collision>().unwrap() ).count(), 1);
+//! 
+//! // Each reader only sees each event once
+//! assert_eq!(spawned.read( world.events::<Spawned>().unwrap() ).count(), 0);
+//! ```
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Marker
+use std::marker::PhantomData;
+
+// Any
+use std::any::{Any, TypeId};
+
+// Crate
+use crate::{util::KeyType, Storage, EntityId};
+use super::{World, Backend};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// All events of a type
+	pub struct Events<T>
+	{
+		/// Events sent before the last update
+		old: Vec<T>,
+		
+		/// The index of the first event in `old`
+		old_start: usize,
+		
+		/// Events sent since the last update
+		new: Vec<T>,
+		
+		/// The index of the first event in `new`
+		new_start: usize,
+	}
+	
+	/// A cursor into the events of a type
+	/// 
+	/// Each reader sees every event once, as long as it reads
+	/// them before they're dropped.
+	pub struct EventReader<T>
+	{
+		/// The index of the next event to read
+		next: usize,
+		
+		/// Marker for the event type
+		phantom: PhantomData<fn() -> T>,
+	}
+	
+	/// Sent whenever an entity is added to a world, once registered with [`World::add_events`]
+	#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+	pub struct Spawned(pub EntityId);
+	
+	/// Sent whenever an entity is removed from a world, once registered with [`World::add_events`]
+	#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+	pub struct Despawned(pub EntityId);
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<T> Events<T>
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty list of events
+			#[must_use]
+			pub const fn new() -> Self {
+				Self { old: vec![], old_start: 0, new: vec![], new_start: 0 }
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Send
+		//--------------------------------------------------------------------------------------------------
+			/// Sends an event
+			pub fn send(&mut self, event: T) {
+				self.new.push(event);
+			}
+			
+			/// Drops all events sent before the last update
+			/// 
+			/// This is called by [`World::increment_change_tick`] for
+			/// every type of event registered with [`World::add_events`].
+			#[allow(clippy::arithmetic_side_effects)] // We can't send more events than fit in memory
+			pub fn update(&mut self)
+			{
+				self.old = std::mem::take(&mut self.new);
+				self.old_start = self.new_start;
+				self.new_start += self.old.len();
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Access
+		//--------------------------------------------------------------------------------------------------
+			/// Returns an iterator over all events that weren't dropped yet, in the order they were sent
+			pub fn iter(&self) -> impl Iterator<Item = &T> {
+				self.old.iter().chain(&self.new)
+			}
+			
+			/// Returns the number of events that weren't dropped yet
+			#[must_use]
+			#[allow(clippy::arithmetic_side_effects)] // Both lengths fit in memory at once
+			pub const fn len(&self) -> usize {
+				self.old.len() + self.new.len()
+			}
+			
+			/// Checks if all events were dropped
+			#[must_use]
+			pub const fn is_empty(&self) -> bool {
+				self.old.is_empty() && self.new.is_empty()
+			}
+			
+			/// Returns the index after the last event sent
+			#[allow(clippy::arithmetic_side_effects)] // We can't send more events than fit in memory
+			const fn end(&self) -> usize {
+				self.new_start + self.new.len()
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl<T> EventReader<T>
+	{
+		/// Creates a reader that sees all events that weren't dropped yet
+		#[must_use]
+		pub const fn new() -> Self {
+			Self { next: 0, phantom: PhantomData }
+		}
+		
+		/// Returns an iterator over all events this reader hasn't seen yet, in the order they were sent
+		/// 
+		/// All returned events are marked as seen, even if the iterator isn't consumed.
+		pub fn read<'e>(&mut self, events: &'e Events<T>) -> impl Iterator<Item = &'e T>
+		{
+			let old = &events.old[ self.next.saturating_sub(events.old_start).min( events.old.len() ).. ];
+			let new = &events.new[ self.next.saturating_sub(events.new_start).min( events.new.len() ).. ];
+			self.next = events.end();
+			
+			old.iter().chain(new)
+		}
+	}
+	
+	impl<'a, S, B> World<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		/// Registers a type of event
+		/// 
+		/// This inserts an empty [`Events`] resource for it, if it didn't
+		/// exist yet, which is updated whenever the tick is incremented.
+		pub fn add_events<T>(&mut self)
+		where
+			T: Any + Send + Sync
+		{
+			if !self.resources.contains::<Events<T>>() {
+				self.resources.insert( Events::<T>::new() );
+			}
+			
+			self.event_updates.insert(TypeId::of::<T>(), |resources| {
+				if let Some(events) = resources.get_mut::<Events<T>>() {
+					events.update();
+				}
+			});
+		}
+		
+		/// Sends an event
+		/// 
+		/// # Panics
+		/// Panics if the type of event wasn't registered with [`World::add_events`].
+		pub fn send_event<T>(&mut self, event: T)
+		where
+			T: Any + Send + Sync
+		{
+			self.resources
+				.get_mut::<Events<T>>()
+				.expect("Event type wasn't registered")
+				.send(event);
+		}
+		
+		/// Returns all events of a type
+		/// 
+		/// Returns `None` if the type of event wasn't registered
+		#[must_use]
+		pub fn events<T>(&self) -> Option<&Events<T>>
+		where
+			T: Any + Send + Sync
+		{
+			self.resources.get()
+		}
+	}
+	
+	impl<T> Default for Events<T>
+	{
+		fn default() -> Self {
+			Self::new()
+		}
+	}
+	
+	impl<T> Default for EventReader<T>
+	{
+		fn default() -> Self {
+			Self::new()
+		}
+	}
+	
+	impl<T> std::fmt::Debug for Events<T>
+	where
+		T: std::fmt::Debug
+	{
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			f.debug_list()
+				.entries(self.iter())
+				.finish()
+		}
+	}
+	
+	impl<T> std::fmt::Debug for EventReader<T>
+	{
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			f.debug_struct("EventReader")
+				.field("next", &self.next)
+				.finish()
+		}
+	}
+//--------------------------------------------------------------------------------------------------
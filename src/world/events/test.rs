@@ -0,0 +1,146 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{World, Commands, Events, EventReader};
+use super::{Spawned, Despawned};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type
+		#[derive(PartialEq, Clone, Debug)]
+		enum Components {
+			A(i32),
+			B(&'static str),
+			C(f32),
+		}
+	
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn double_buffered()
+	{
+		let mut events = Events::new();
+		let mut reader = EventReader::new();
+		
+		events.send(1);
+		events.send(2);
+		assert_eq!(reader.read(&events).collect::<Vec<_>>(), [&1, &2]);
+		
+		// Events stay alive for another update
+		events.update();
+		events.send(3);
+		assert_eq!(events.iter().collect::<Vec<_>>(), [&1, &2, &3]);
+		assert_eq!(reader.read(&events).collect::<Vec<_>>(), [&3]);
+		
+		// A new reader still sees all live events
+		let mut late_reader = EventReader::new();
+		events.update();
+		assert_eq!(late_reader.read(&events).collect::<Vec<_>>(), [&3]);
+		
+		events.update();
+		assert!(events.is_empty());
+		assert_eq!(reader.read(&events).count(), 0);
+	}
+	
+	#[test]
+	fn missed_events()
+	{
+		let mut events = Events::new();
+		let mut reader = EventReader::new();
+		
+		events.send(1);
+		events.update();
+		events.send(2);
+		events.update();
+		events.send(3);
+		
+		// Any events dropped before being read are skipped
+		assert_eq!(reader.read(&events).collect::<Vec<_>>(), [&2, &3]);
+	}
+	
+	#[test]
+	fn spawn_despawn()
+	{
+		let mut world: World<Components> = World::new();
+		world.add_events::<Spawned>();
+		world.add_events::<Despawned>();
+		let mut spawned = EventReader::<Spawned>::new();
+		let mut despawned = EventReader::<Despawned>::new();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		let id2 = world.add( mecs::entity![ Components::B("2") ] );
+		world.remove(id1);
+		
+		let mut commands: Commands<Components> = world.commands();
+		let id3 = commands.spawn( mecs::entity![ Components::C(3.0) ] );
+		commands.despawn(id2);
+		world.apply(commands);
+		
+		let spawned_ids: Vec<_> = spawned.read( world.events().unwrap() ).map(|&Spawned(id)| id).collect();
+		let despawned_ids: Vec<_> = despawned.read( world.events().unwrap() ).map(|&Despawned(id)| id).collect();
+		assert_eq!(spawned_ids, [id1, id2, id3]);
+		assert_eq!(despawned_ids, [id1, id2]);
+	}
+	
+	#[test]
+	fn despawn_reserved()
+	{
+		let mut world: World<Components> = World::new();
+		world.add_events::<Despawned>();
+		let mut despawned = EventReader::<Despawned>::new();
+		
+		// Allocating an id makes any ids reserved before it alive, before their entity is created
+		let reserved_id = world.reserve_entity();
+		let _ = world.ids.alloc();
+		assert!(world.is_alive(reserved_id));
+		
+		// Removing it should free it, but without an entity there's nothing despawned
+		assert!(world.remove(reserved_id).is_none());
+		assert!(!world.is_alive(reserved_id));
+		assert_eq!(despawned.read( world.events().unwrap() ).count(), 0);
+	}
+	
+	#[test]
+	fn spawn_despawn_opt_in()
+	{
+		// Without registering them, no events are kept
+		let mut world: World<Components> = World::new();
+		let id = world.add( mecs::entity![ Components::A(1) ] );
+		world.remove(id);
+		
+		assert!(world.events::<Spawned>().is_none());
+		assert!(world.events::<Despawned>().is_none());
+	}
+	
+	#[test]
+	fn world_ticks()
+	{
+		let mut world: World<Components> = World::new();
+		world.add_events::<u32>();
+		let mut reader = EventReader::<u32>::new();
+		
+		world.send_event(1_u32);
+		world.increment_change_tick();
+		world.send_event(2_u32);
+		world.increment_change_tick();
+		assert_eq!(world.events::<u32>().unwrap().len(), 1);
+		
+		world.increment_change_tick();
+		assert!(world.events::<u32>().unwrap().is_empty());
+		assert_eq!(reader.read( world.events().unwrap() ).count(), 0);
+	}
+	
+	#[test]
+	#[should_panic(expected = "Event type wasn't registered")]
+	fn unregistered()
+	{
+		let mut world: World<Components> = World::new();
+		world.send_event(1_u32);
+	}
+//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,436 @@
+//! Declarative filters over entities
+//! 
+//! Unlike plain closures, filters know which components they depend on,
+//! so the world may only re-evaluate them when one of those components
+//! changes, and may share the ids of identical filters.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{World, DynStorage, Filter};
+//! let mut world = World::new();
+//! 
+//! world.add( mecs::entity![ DynStorage::new(5i32), DynStorage::new(1.5f32) ] );
+//! world.add( mecs::entity![ DynStorage::new(8i32) ] );
+//! world.add( mecs::entity![ DynStorage::new("Hello, World!") ] );
+//! 
+//! let pred_id = world.add_filter(
+//! 	Filter::has::<i32>()
+//! 		.and( Filter::without::<f32>() )
+//! 		.or ( Filter::has::<&str>() )
+//! );
+//! 
+//! assert_eq!(world.iter_pred(pred_id).unwrap().count(), 2);
+//! ```
+//! 
+//! # Masks
+//! If the storage maps it's ids to indices, see [`Storage::COUNT`], filters made
+//! up of only [`Filter::has`], [`Filter::without`] and [`Filter::and`] are compiled
+//! into a [`FilterMask`], which matches entities by just comparing their component masks.
+//! 
+//! # Change detection
+//! Filters may also match entities by when their components were added or changed,
+//! see [`Filter::changed`] and [`Filter::added`]. As components may be changed without
+//! the world knowing, predicates only keep track of which entities have the component,
+//! and check when it was changed while iterating.
+//! 
+//! ```rust
+//! # use mecs::{World, DynStorage, Filter};
+//! let mut world = World::new();
+//! 
+//! let id = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+//! world.add( mecs::entity![ DynStorage::new(8i32) ] );
+//! 
+//! let last_tick = world.change_tick();
+//! world.increment_change_tick();
+//! *world.get_mut(id).unwrap().get_mut::<i32>().unwrap() += 1;
+//! 
+//! let pred_id = world.add_filter( Filter::changed::<i32>(last_tick) );
+//! let ids: Vec<_> = world.iter_pred(pred_id).unwrap().map(|(_, id)| id).collect();
+//! assert_eq!(ids, [id]);
+//! ```
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Traits
+use std::fmt::Debug;
+
+// Crate
+use crate::{util::KeyType, Storage, Component, Entity, Tick, component::ComponentMask};
+use super::{pred::Predicate, Backend, BackendRef, ReadEntity, Entities};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A filter over entities
+	/// 
+	/// Filters are generic over the backend of the world they're used with,
+	/// as closures inspect entities through the backend's references.
+	pub struct Filter<'a, S, B = Entities<'a, S>>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		/// The kind of filter
+		kind: FilterKind<'a, S, B>,
+	}
+	
+	/// All kinds of filters
+	enum FilterKind<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		/// Matches entities with a component
+		Has(S::Id),
+		
+		/// Matches entities without a component
+		Without(S::Id),
+		
+		/// Matches entities with a component changed after a tick
+		Changed(S::Id, Tick),
+		
+		/// Matches entities with a component added after a tick
+		Added(S::Id, Tick),
+		
+		/// Matches entities matched by both filters
+		And(Box<Filter<'a, S, B>>, Box<Filter<'a, S, B>>),
+		
+		/// Matches entities matched by either filter
+		Or(Box<Filter<'a, S, B>>, Box<Filter<'a, S, B>>),
+		
+		/// Matches entities matched by a predicate
+		Pred(Predicate<'a, S, B>),
+	}
+	
+	/// A filter compiled into component masks, see [`Filter::mask`]
+	#[derive(PartialEq, Eq, Clone, Debug)]
+	pub struct FilterMask
+	{
+		/// Components an entity must have
+		required: ComponentMask,
+		
+		/// Components an entity must not have
+		excluded: ComponentMask,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S, B> Filter<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates a filter matching entities with a component given it's type
+			#[must_use]
+			pub fn has<C: Component<'a, S>>() -> Self {
+				Self::has_id( C::id() )
+			}
+			
+			/// Creates a filter matching entities with a component given it's id
+			#[must_use]
+			pub const fn has_id(id: S::Id) -> Self {
+				Self { kind: FilterKind::Has(id) }
+			}
+			
+			/// Creates a filter matching entities without a component given it's type
+			#[must_use]
+			pub fn without<C: Component<'a, S>>() -> Self {
+				Self::without_id( C::id() )
+			}
+			
+			/// Creates a filter matching entities without a component given it's id
+			#[must_use]
+			pub const fn without_id(id: S::Id) -> Self {
+				Self { kind: FilterKind::Without(id) }
+			}
+			
+			/// Creates a filter matching entities with a component added or changed after `since`, given it's type
+			#[must_use]
+			pub fn changed<C: Component<'a, S>>(since: Tick) -> Self {
+				Self::changed_id( C::id(), since )
+			}
+			
+			/// Creates a filter matching entities with a component added or changed after `since`, given it's id
+			#[must_use]
+			pub const fn changed_id(id: S::Id, since: Tick) -> Self {
+				Self { kind: FilterKind::Changed(id, since) }
+			}
+			
+			/// Creates a filter matching entities with a component added after `since`, given it's type
+			#[must_use]
+			pub fn added<C: Component<'a, S>>(since: Tick) -> Self {
+				Self::added_id( C::id(), since )
+			}
+			
+			/// Creates a filter matching entities with a component added after `since`, given it's id
+			#[must_use]
+			pub const fn added_id(id: S::Id, since: Tick) -> Self {
+				Self { kind: FilterKind::Added(id, since) }
+			}
+			
+			/// Creates a filter from a closure
+			/// 
+			/// As the world can't know which components the closure
+			/// depends on, it must be re-evaluated whenever any component
+			/// changes, so declarative filters should be preferred.
+			#[must_use]
+			pub fn from_fn<F>(f: F) -> Self
+			where
+				F: Fn(BackendRef<'a, '_, S, B>) -> bool + Send + Sync + 'a
+			{
+				Self { kind: FilterKind::Pred( Predicate( Box::new(f) ) ) }
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Combinators
+		//--------------------------------------------------------------------------------------------------
+			/// Combines this filter with another, matching entities matched by both
+			#[must_use]
+			pub fn and(self, other: Self) -> Self {
+				Self { kind: FilterKind::And( Box::new(self), Box::new(other) ) }
+			}
+			
+			/// Combines this filter with another, matching entities matched by either
+			#[must_use]
+			pub fn or(self, other: Self) -> Self {
+				Self { kind: FilterKind::Or( Box::new(self), Box::new(other) ) }
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Checks
+		//--------------------------------------------------------------------------------------------------
+			/// Checks if an entity is matched by this filter
+			#[must_use]
+			pub fn matches(&self, entity: BackendRef<'a, '_, S, B>) -> bool {
+				match &self.kind {
+					FilterKind::Has    (id       ) =>  entity.has_id(id),
+					FilterKind::Without(id       ) => !entity.has_id(id),
+					FilterKind::Changed(id, since) => entity.ticks_id(id).is_some_and(|ticks| ticks.is_changed(*since)),
+					FilterKind::Added  (id, since) => entity.ticks_id(id).is_some_and(|ticks| ticks.is_added  (*since)),
+					FilterKind::And    (lhs, rhs ) => lhs.matches(entity) && rhs.matches(entity),
+					FilterKind::Or     (lhs, rhs ) => lhs.matches(entity) || rhs.matches(entity),
+					FilterKind::Pred   (pred     ) => pred.matches(entity),
+				}
+			}
+			
+			/// Checks if an entity is matched by this filter, ignoring when it's components were added or changed
+			/// 
+			/// As there's no way to negate a filter, this matches every entity matched
+			/// by [`Filter::matches`], along with any that only fail because of their ticks.
+			#[must_use]
+			pub(in super) fn matches_components(&self, entity: BackendRef<'a, '_, S, B>) -> bool {
+				match &self.kind {
+					FilterKind::Has    (id       ) |
+					FilterKind::Changed(id, _    ) |
+					FilterKind::Added  (id, _    ) =>  entity.has_id(id),
+					FilterKind::Without(id       ) => !entity.has_id(id),
+					FilterKind::And    (lhs, rhs ) => lhs.matches_components(entity) && rhs.matches_components(entity),
+					FilterKind::Or     (lhs, rhs ) => lhs.matches_components(entity) || rhs.matches_components(entity),
+					FilterKind::Pred   (pred     ) => pred.matches(entity),
+				}
+			}
+			
+			/// Compiles this filter into component masks
+			/// 
+			/// # Return
+			/// Returns `None` if the storage doesn't map it's ids to indices,
+			/// or if this filter contains either an `or`, a closure, or depends
+			/// on when components were changed, as those can't be expressed as masks.
+			#[must_use]
+			pub fn mask(&self) -> Option<FilterMask>
+			{
+				if self.uses_ticks() {
+					return None;
+				}
+				
+				self.components_mask()
+			}
+			
+			/// Compiles this filter into component masks, ignoring when components were added or changed
+			/// 
+			/// See [`Filter::matches_components`].
+			#[must_use]
+			pub(in super) fn components_mask(&self) -> Option<FilterMask>
+			{
+				let count = S::COUNT?;
+				let mut mask = FilterMask {
+					required: ComponentMask::with_capacity(count),
+					excluded: ComponentMask::with_capacity(count),
+				};
+				
+				self.add_to_mask(&mut mask)?;
+				Some(mask)
+			}
+			
+			/// Adds this filter to a mask, returning `None` if it can't be expressed as one
+			fn add_to_mask(&self, mask: &mut FilterMask) -> Option<()>
+			{
+				match &self.kind {
+					FilterKind::Has    (id   ) |
+					FilterKind::Changed(id, _) |
+					FilterKind::Added  (id, _) => mask.required.insert( S::index(id)? ),
+					FilterKind::Without(id   ) => mask.excluded.insert( S::index(id)? ),
+					FilterKind::And    (lhs, rhs) => {
+						lhs.add_to_mask(mask)?;
+						rhs.add_to_mask(mask)?;
+					},
+					FilterKind::Or(..) | FilterKind::Pred(_) => return None,
+				}
+				
+				Some(())
+			}
+			
+			/// Checks if this filter is made up of only declarative filters, that is, no closures
+			#[must_use]
+			pub fn is_declarative(&self) -> bool {
+				match &self.kind {
+					FilterKind::Has(_) | FilterKind::Without(_) | FilterKind::Changed(..) | FilterKind::Added(..) => true,
+					FilterKind::And(lhs, rhs) | FilterKind::Or(lhs, rhs) => lhs.is_declarative() && rhs.is_declarative(),
+					FilterKind::Pred(_) => false,
+				}
+			}
+			
+			/// Checks if this filter depends on when components were added or changed
+			#[must_use]
+			pub fn uses_ticks(&self) -> bool {
+				match &self.kind {
+					FilterKind::Changed(..) | FilterKind::Added(..) => true,
+					FilterKind::Has(_) | FilterKind::Without(_) | FilterKind::Pred(_) => false,
+					FilterKind::And(lhs, rhs) | FilterKind::Or(lhs, rhs) => lhs.uses_ticks() || rhs.uses_ticks(),
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Ids
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the ids of all components an entity must have to be matched by this filter
+			/// 
+			/// Closures don't require any components.
+			#[must_use]
+			pub fn required(&self) -> Vec<&S::Id> {
+				match &self.kind {
+					FilterKind::Has(id) | FilterKind::Changed(id, _) | FilterKind::Added(id, _) => vec![id],
+					FilterKind::Without(_) | FilterKind::Pred(_) => vec![],
+					FilterKind::And(lhs, rhs) => union       ( lhs.required(), rhs.required() ),
+					FilterKind::Or (lhs, rhs) => intersection( lhs.required(), &rhs.required() ),
+				}
+			}
+			
+			/// Returns the ids of all components an entity must not have to be matched by this filter
+			/// 
+			/// Closures don't exclude any components.
+			#[must_use]
+			pub fn excluded(&self) -> Vec<&S::Id> {
+				match &self.kind {
+					FilterKind::Without(id) => vec![id],
+					FilterKind::Has(_) | FilterKind::Changed(..) | FilterKind::Added(..) | FilterKind::Pred(_) => vec![],
+					FilterKind::And(lhs, rhs) => union       ( lhs.excluded(), rhs.excluded() ),
+					FilterKind::Or (lhs, rhs) => intersection( lhs.excluded(), &rhs.excluded() ),
+				}
+			}
+			
+			/// Returns the ids of all components this filter depends on
+			/// 
+			/// # Return
+			/// Returns `None` if this filter isn't declarative, as
+			/// closures may depend on any component.
+			#[must_use]
+			pub fn dependencies(&self) -> Option< Vec<&S::Id> > {
+				match &self.kind {
+					FilterKind::Has(id) | FilterKind::Without(id) | FilterKind::Changed(id, _) | FilterKind::Added(id, _) => Some( vec![id] ),
+					FilterKind::And(lhs, rhs) | FilterKind::Or(lhs, rhs) => Some( union( lhs.dependencies()?, rhs.dependencies()? ) ),
+					FilterKind::Pred(_) => None,
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl FilterMask
+	{
+		/// Checks if an entity is matched by this mask
+		#[must_use]
+		pub fn matches<'a, S>(&self, entity: &Entity<'a, S>) -> bool
+		where
+			S    : Storage<'a>,
+			S::Id: KeyType + 'a,
+		{
+			self.matches_mask(entity.mask())
+		}
+		
+		/// Checks if the mask of an entity's components is matched by this mask
+		#[must_use]
+		pub fn matches_mask(&self, mask: &ComponentMask) -> bool {
+			mask.contains_all(&self.required) && !mask.intersects(&self.excluded)
+		}
+	}
+	
+	impl<'a, S, B> PartialEq for Filter<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		fn eq(&self, other: &Self) -> bool
+		{
+			// Note: Closures are never equal to anything, as we can't compare them
+			match (&self.kind, &other.kind) {
+				(FilterKind::Has    (lhs), FilterKind::Has    (rhs)) |
+				(FilterKind::Without(lhs), FilterKind::Without(rhs)) => lhs == rhs,
+				(FilterKind::Changed(lhs, lhs_since), FilterKind::Changed(rhs, rhs_since)) |
+				(FilterKind::Added  (lhs, lhs_since), FilterKind::Added  (rhs, rhs_since)) => lhs == rhs && lhs_since == rhs_since,
+				(FilterKind::And(lhs_lhs, lhs_rhs), FilterKind::And(rhs_lhs, rhs_rhs)) |
+				(FilterKind::Or (lhs_lhs, lhs_rhs), FilterKind::Or (rhs_lhs, rhs_rhs)) => lhs_lhs == rhs_lhs && lhs_rhs == rhs_rhs,
+				_ => false,
+			}
+		}
+	}
+	
+	impl<'a, S, B> std::fmt::Debug for Filter<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + Debug,
+		B    : Backend<'a, S>,
+	{
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			match &self.kind {
+				FilterKind::Has    (id       ) => f.debug_tuple("Has"    ).field(id).finish(),
+				FilterKind::Without(id       ) => f.debug_tuple("Without").field(id).finish(),
+				FilterKind::Changed(id, since) => f.debug_tuple("Changed").field(id).field(since).finish(),
+				FilterKind::Added  (id, since) => f.debug_tuple("Added"  ).field(id).field(since).finish(),
+				FilterKind::And    (lhs, rhs ) => f.debug_tuple("And"    ).field(lhs).field(rhs).finish(),
+				FilterKind::Or     (lhs, rhs ) => f.debug_tuple("Or"     ).field(lhs).field(rhs).finish(),
+				FilterKind::Pred   (pred     ) => pred.fmt(f),
+			}
+		}
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Returns all ids in either list
+	fn union<'b, I: PartialEq>(mut lhs: Vec<&'b I>, rhs: Vec<&'b I>) -> Vec<&'b I>
+	{
+		for id in rhs {
+			if !lhs.contains(&id) {
+				lhs.push(id);
+			}
+		}
+		
+		lhs
+	}
+	
+	/// Returns all ids in both lists
+	fn intersection<'b, I: PartialEq>(mut lhs: Vec<&'b I>, rhs: &[&'b I]) -> Vec<&'b I>
+	{
+		lhs.retain(|id| rhs.contains(id));
+		lhs
+	}
+//--------------------------------------------------------------------------------------------------
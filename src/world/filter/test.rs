@@ -0,0 +1,185 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{World, Filter, Entity, DynStorage};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type
+		#[derive(PartialEq, Clone, Debug)]
+		enum Components {
+			A(i32),
+			B(&'static str),
+			C(f32),
+		}
+	
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn ids()
+	{
+		let filter: Filter<Components> = Filter::has::<i32>()
+			.and( Filter::without::<f32>() )
+			.or ( Filter::has::<i32>().and( Filter::has::<&str>() ) );
+		
+		assert_eq!(filter.required(), [&0]);
+		assert!(filter.excluded().is_empty());
+		assert_eq!(filter.dependencies(), Some( vec![&0, &2, &1] ));
+		assert!(filter.is_declarative());
+		
+		let filter = filter.and( Filter::from_fn(|_| true) );
+		assert_eq!(filter.required(), [&0]);
+		assert_eq!(filter.dependencies(), None);
+		assert!(!filter.is_declarative());
+	}
+	
+	#[test]
+	fn updates()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let has_a      = world.add_filter( Filter::has::<i32>() );
+		let without_b  = world.add_filter( Filter::without::<&str>() );
+		let a_or_c     = world.add_filter( Filter::has::<i32>().or( Filter::has::<f32>() ) );
+		let closure    = world.add_pred(|entity| entity.get::<i32>().is_some_and(|&num| num > 0));
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		let id2 = world.add( mecs::entity![ Components::B("2"), Components::C(2.0) ] );
+		
+		let ids = |world: &World<Components>, pred_id| {
+			let mut ids: Vec<_> = world.iter_pred(pred_id).unwrap().map(|(_, id)| id).collect();
+			ids.sort_by_key(|id| id.idx());
+			ids
+		};
+		assert_eq!(ids(&world, has_a    ), [id1]);
+		assert_eq!(ids(&world, without_b), [id1]);
+		assert_eq!(ids(&world, a_or_c   ), [id1, id2]);
+		assert_eq!(ids(&world, closure  ), [id1]);
+		
+		world.insert_component(id2, Components::A(2)).unwrap();
+		world.remove_component::<&str>(id2);
+		assert_eq!(ids(&world, has_a    ), [id1, id2]);
+		assert_eq!(ids(&world, without_b), [id1, id2]);
+		assert_eq!(ids(&world, closure  ), [id1, id2]);
+		
+		world.remove_component::<i32>(id1);
+		assert_eq!(ids(&world, has_a    ), [id2]);
+		assert_eq!(ids(&world, a_or_c   ), [id2]);
+		assert_eq!(ids(&world, closure  ), [id2]);
+	}
+	
+	#[test]
+	fn batch()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let has_a      = world.add_filter( Filter::has::<i32>() );
+		let without_b  = world.add_filter( Filter::without::<&str>() );
+		let closure    = world.add_pred(|entity| entity.get::<i32>().is_some_and(|&num| num > 0));
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		let ids: Vec<_> = world.spawn_batch(vec![
+			mecs::entity![ Components::A(2), Components::B("2") ],
+			mecs::entity![ Components::C(3.0) ],
+			mecs::entity![ Components::A(-4) ],
+		]).collect();
+		
+		let pred_ids = |world: &World<Components>, pred_id| {
+			let mut ids: Vec<_> = world.iter_pred(pred_id).unwrap().map(|(_, id)| id).collect();
+			ids.sort_by_key(|id| id.idx());
+			ids
+		};
+		assert_eq!(pred_ids(&world, has_a    ), [id1, ids[0], ids[2]]);
+		assert_eq!(pred_ids(&world, without_b), [id1, ids[1], ids[2]]);
+		assert_eq!(pred_ids(&world, closure  ), [id1, ids[0]]);
+		
+		// Extending should also go through all predicates
+		world.extend( std::iter::once( mecs::entity![ Components::A(5) ] ) );
+		assert_eq!(world.iter_pred(closure).unwrap().count(), 3);
+	}
+	
+	#[test]
+	fn shared()
+	{
+		let mut world: World<Components> = World::new();
+		let id = world.add( mecs::entity![ Components::A(1) ] );
+		
+		// Both predicates share the same filter, but have their own ids
+		let pred_id1 = world.add_filter( Filter::has::<i32>() );
+		let pred_id2 = world.add_filter( Filter::has::<i32>() );
+		assert_ne!(pred_id1, pred_id2);
+		
+		// So removing one doesn't affect the other
+		assert!(world.remove_pred(pred_id1));
+		assert!(!world.has_pred(pred_id1));
+		assert_eq!(world.iter_pred(pred_id2).unwrap().map(|(_, id)| id).collect::<Vec<_>>(), [id]);
+		
+		// And neither does replacing it
+		let pred_id3 = world.add_filter( Filter::has::<i32>() );
+		assert!(world.replace_filter( pred_id3, Filter::has::<f32>() ));
+		assert_eq!(world.iter_pred(pred_id2).unwrap().count(), 1);
+		assert_eq!(world.iter_pred(pred_id3).unwrap().count(), 0);
+	}
+	
+	#[test]
+	fn mask()
+	{
+		let entities: Vec<Entity<Components>> = vec![
+			mecs::entity![ Components::A(1) ],
+			mecs::entity![ Components::A(1), Components::C(1.0) ],
+			mecs::entity![ Components::B("Hello, World!") ],
+		];
+		
+		// Conjunctions are compiled into masks, which match the same entities
+		let filter: Filter<Components> = Filter::has::<i32>().and( Filter::without::<f32>() );
+		let mask = filter.mask().unwrap();
+		for entity in &entities {
+			assert_eq!(mask.matches(entity), filter.matches(entity));
+		}
+		assert_eq!(entities.iter().filter(|entity| mask.matches(entity)).count(), 1);
+		
+		// But anything else isn't
+		assert_eq!(Filter::<Components>::has::<i32>().or( Filter::has::<f32>() ).mask(), None);
+		assert_eq!(Filter::<Components>::from_fn(|_| true).mask(), None);
+		assert_eq!(Filter::<DynStorage>::has::<i32>().mask(), None);
+	}
+	
+	#[test]
+	fn changed_added()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		let id2 = world.add( mecs::entity![ Components::A(2), Components::C(2.0) ] );
+		
+		let last_tick = world.change_tick();
+		let changed = world.add_filter( Filter::changed::<i32>(last_tick) );
+		let added = world.add_filter( Filter::added::<f32>(last_tick).or( Filter::added::<i32>(last_tick) ) );
+		assert_eq!(world.iter_pred(changed).unwrap().count(), 0);
+		
+		// Changing a component after the tick should match it, even without the predicate being updated
+		world.increment_change_tick();
+		*world.get_mut(id1).unwrap().get_mut::<i32>().unwrap() += 1;
+		let ids: Vec<_> = world.iter_pred(changed).unwrap().map(|(_, id)| id).collect();
+		assert_eq!(ids, [id1]);
+		{
+			let mut iter = world.iter_pred_mut(changed).unwrap();
+			assert_eq!(iter.next().map(|(_, id)| id), Some(id1));
+			assert!(iter.next().is_none());
+		}
+		assert_eq!(world.iter_pred(added).unwrap().count(), 0);
+		
+		// And adding one should match it too
+		world.insert_component(id2, Components::B("2")).unwrap();
+		world.remove_component::<f32>(id2);
+		world.insert_component(id2, Components::C(3.0)).unwrap();
+		let ids: Vec<_> = world.iter_pred(added).unwrap().map(|(_, id)| id).collect();
+		assert_eq!(ids, [id2]);
+	}
+//--------------------------------------------------------------------------------------------------
@@ -3,124 +3,161 @@
 // Traits
 use std::iter::Iterator;
 
+// Any
+use std::any::Any;
+
 // Crate
-use crate::{util::KeyType, Storage, Entity, World, EntityId};
+use crate::{util::KeyType, Storage, World, EntityId};
+use super::{PredId, Commands, Backend, BackendRef, BackendMut, Entities};
 
 // Types
 //--------------------------------------------------------------------------------------------------
 	/// Iterator over a world predicate
 	#[allow(clippy::module_name_repetitions)]
-	pub struct PredIter<'a, 'b, S>
+	pub struct PredIter<'a, 'b, S, B = Entities<'a, S>>
 	where
 		S    : Storage<'a>,
 		S::Id: KeyType,
+		B    : Backend<'a, S>,
 	{
 		/// Reference to the world
-		pub(in super) world: &'b World<'a, S>,
+		pub(in super) world: &'b World<'a, S, B>,
 		
 		/// The predicate id
-		pub(in super) id: usize,
+		pub(in super) id: PredId<S>,
 		
-		/// The current index
-		pub(in super) cur_idx: usize,
+		/// The position of the next entity
+		pub(in super) cursor: B::Cursor,
 	}
 	
 	/// Mutable iterator over a world predicate
+	/// 
+	/// As each entity borrows from the iterator, this type
+	/// can't implement [`Iterator`], so it must be iterated
+	/// either with [`PredIterMut::next`] or [`PredIterMut::for_each`]
+	/// 
+	/// # Modifying the world
+	/// Entities may be removed with [`PredIterMut::remove`], and any
+	/// other changes recorded with [`PredIterMut::commands`], which are
+	/// only applied once this iterator is dropped, unless it's dropped
+	/// while panicking.
+	/// 
+	/// # Resources
+	/// As the iterator borrows the world, resources must be
+	/// accessed through [`PredIterMut::resource`] and
+	/// [`PredIterMut::resource_mut`] in between entities.
 	#[allow(clippy::module_name_repetitions)]
-	pub struct PredIterMut<'a, 'b, S>
+	pub struct PredIterMut<'a, 'b, S, B = Entities<'a, S>>
 	where
 		S    : Storage<'a>,
-		S::Id: KeyType,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
 	{
 		/// Reference to the world
-		pub world: &'b mut World<'a, S>,
+		pub(in super) world: &'b mut World<'a, S, B>,
 		
 		/// The predicate id
-		pub(in super) id: usize,
+		pub(in super) id: PredId<S>,
 		
-		/// The current index
-		pub(in super) cur_idx: usize,
+		/// The position of the next entity
+		pub(in super) cursor: B::Cursor,
+		
+		/// All commands to apply once we're dropped
+		pub(in super) commands: Commands<'a, S>,
 	}
 //--------------------------------------------------------------------------------------------------
 
 // Impl
 //--------------------------------------------------------------------------------------------------
-	impl<'a, 'b, S> Iterator for PredIter<'a, 'b, S>
+	impl<'a, 'b, S, B> Iterator for PredIter<'a, 'b, S, B>
 	where
 		S    : Storage<'a>,
-		S::Id: KeyType,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
 	{
-		type Item = (&'b Entity<'a, S>, EntityId);
+		type Item = (BackendRef<'a, 'b, S, B>, EntityId);
 		
-		#[allow(clippy::integer_arithmetic)] // We need to add one to get the next index
 		fn next(&mut self) -> Option< Self::Item >
 		{
-			// While we have a next id, try to get it
-			while let Some(entity_id) = self.world.predicates
-				.get(&self.id)
-				.expect("Could not get predicate from id")
-				.ids
-				.get(self.cur_idx)
-			{
-				// Increase the current index
-				self.cur_idx += 1;
-				
-				// Try to get the entity
-				if let Some(entity) = self.world.entities.get( &entity_id.get() )
-				{
-					// Transmute it to it's lifetime
-					return Some( (entity, entity_id.get()));
-				}
-				
-				// Else set the id to null
-				else {
-					entity_id.set( EntityId::null() );
-				}
-			}
+			let pred = self.world.predicates.get(self.id).expect("Could not get predicate from id");
+			let (id, entity) = self.world.entities.next_pred(pred, &mut self.cursor)?;
 			
-			// If we get here, return None
-			None
+			Some( (entity, id) )
 		}
 	}
 	
-	impl<'a, 'b, S> Iterator for PredIterMut<'a, 'b, S>
+	impl<'a, S, B> PredIterMut<'a, '_, S, B>
 	where
 		S    : Storage<'a>,
-		S::Id: KeyType,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
 	{
-		type Item = (&'b mut Entity<'a, S>, EntityId);
+		/// Returns the next entity, borrowed from this iterator
+		#[allow(clippy::should_implement_trait)] // We can't implement `Iterator`, see the type docs
+		#[allow(clippy::missing_panics_doc)] // We borrow the world, so the predicate can't be removed while iterating
+		pub fn next(&mut self) -> Option< (BackendMut<'a, '_, S, B>, EntityId) >
+		{
+			// Note: As we borrow the world, the ids of the predicate can't change while iterating.
+			let world = &mut *self.world;
+			let pred = world.predicates.get(self.id).expect("Could not get predicate from id");
+			let (id, entity) = world.entities.next_pred_mut(pred, &mut self.cursor, world.change_tick)?;
+			
+			Some( (entity, id) )
+		}
 		
-		#[allow(clippy::integer_arithmetic)] // We need to add one to get the next index
-		fn next(&mut self) -> Option< Self::Item >
+		/// Calls a closure on every remaining entity
+		pub fn for_each<F>(mut self, mut f: F)
+		where
+			F: FnMut(BackendMut<'a, '_, S, B>, EntityId)
+		{
+			while let Some( (entity, id) ) = self.next() {
+				f(entity, id);
+			}
+		}
+		
+		/// Removes an entity once this iterator is dropped
+		pub fn remove(&mut self, id: EntityId) {
+			self.commands.despawn(id);
+		}
+		
+		/// Returns the commands to apply once this iterator is dropped
+		pub const fn commands(&mut self) -> &mut Commands<'a, S> {
+			&mut self.commands
+		}
+		
+		/// Returns a reference to a resource of the world given it's type
+		#[must_use]
+		pub fn resource<R>(&self) -> Option<&R>
+		where
+			R: Any + Send + Sync
+		{
+			self.world.resource()
+		}
+		
+		/// Returns a mutable reference to a resource of the world given it's type
+		pub fn resource_mut<R>(&mut self) -> Option<&mut R>
+		where
+			R: Any + Send + Sync
+		{
+			self.world.resource_mut()
+		}
+	}
+	
+	impl<'a, S, B> Drop for PredIterMut<'a, '_, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		fn drop(&mut self)
 		{
-			// While we have a next id, try to get it
-			while let Some(entity_id) = self.world.predicates
-				.get_mut(&self.id)
-				.expect("Could not get predicate from id")
-				.ids
-				.get_mut(self.cur_idx)
-			{
-				// Increase the current index
-				self.cur_idx += 1;
-				
-				// Try to get the entity
-				if let Some(entity) = self.world.entities.get_mut( &entity_id.get() )
-				{
-					// Transmute it to it's lifetime
-					// TODO: Check if this is fine
-					return Some( (unsafe {
-						std::mem::transmute::<&'_ mut _, &'b mut _>(entity)
-					}, entity_id.get()));
-				}
-				
-				// Else set the id to null
-				else {
-					entity_id.set( EntityId::null() );
-				}
+			// Note: If we're unwinding, the commands are from a half-finished pass, so we drop them
+			if std::thread::panicking() {
+				return;
 			}
 			
-			// If we get here, return None
-			None
+			let commands = std::mem::replace(&mut self.commands, self.world.commands());
+			self.world.apply(commands);
 		}
 	}
 //--------------------------------------------------------------------------------------------------
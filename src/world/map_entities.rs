@@ -0,0 +1,132 @@
+//! Mapping entity ids between worlds
+//! 
+//! Components may refer to other entities by storing their ids.
+//! When entities are moved into another world, with [`World::merge`],
+//! they're given new ids, so any ids stored within their components
+//! must be mapped to the new ids, through the [`MapEntities`] trait.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{World, EntityId, MapEntities};
+//! /// The parent of an entity
+//! #[derive(PartialEq, Debug)]
+//! struct Parent(EntityId);
+//! 
+//! mecs::impl_enum_storage! {
+//! 	enum Components {
+//! 		Name(&'static str),
+//! 		Parent(Parent),
+//! 	}
+//! }
+//! 
+//! impl MapEntities for Components {
+//! 	fn map_entities(&mut self, map: &mut dyn FnMut(EntityId) -> EntityId) {
+//! 		if let Self::Parent( Parent(id) ) = self {
+//! 			id.map_entities(map);
+//! 		}
+//! 	}
+//! }
+//! 
+//! let mut level: World<Components> = World::new();
+//! let parent_id = level.add( mecs::entity![ Components::Name("Parent") ] );
+//! let child_id = level.add( mecs::entity![ Components::Name("Child"), Components::Parent( Parent(parent_id) ) ] );
+//! 
+//! let mut world: World<Components> = World::new();
+//! world.add( mecs::entity![ Components::Name("Player") ] );
+//! let ids = world.merge(level);
+//! 
+//! // The child now refers to the new id of it's parent
+//! assert_eq!(world[ ids[&child_id] ].get::<Parent>(), Some(&Parent( ids[&parent_id] )));
+//! ```
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Collections
+use std::collections::HashMap;
+
+// Crate
+use crate::{util::KeyType, Storage, EntityId};
+use super::{World, Backend};
+
+// Traits
+//--------------------------------------------------------------------------------------------------
+	/// Types that may store entity ids
+	/// 
+	/// Storages should implement this trait by mapping every
+	/// entity id stored within any of their components.
+	pub trait MapEntities
+	{
+		/// Replaces every entity id stored within this value with the result of `map`
+		fn map_entities(&mut self, map: &mut dyn FnMut(EntityId) -> EntityId);
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl MapEntities for EntityId
+	{
+		fn map_entities(&mut self, map: &mut dyn FnMut(EntityId) -> EntityId) {
+			*self = map(*self);
+		}
+	}
+	
+	impl<T: MapEntities> MapEntities for Option<T>
+	{
+		fn map_entities(&mut self, map: &mut dyn FnMut(EntityId) -> EntityId) {
+			if let Some(value) = self {
+				value.map_entities(map);
+			}
+		}
+	}
+	
+	impl<T: MapEntities> MapEntities for Vec<T>
+	{
+		fn map_entities(&mut self, map: &mut dyn FnMut(EntityId) -> EntityId) {
+			for value in self {
+				value.map_entities(map);
+			}
+		}
+	}
+	
+	impl<'a, S, B> World<'a, S, B>
+	where
+		S    : Storage<'a> + MapEntities,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		/// Moves all entities of another world into this world
+		/// 
+		/// Each entity is given a new id, and all ids stored within their
+		/// components are mapped to the new ids through [`MapEntities`].
+		/// Ids that didn't belong to any entity of `other` are mapped to
+		/// the null id, see [`EntityId::null`].
+		/// 
+		/// The predicates, resources and observers of `other` are dropped.
+		/// 
+		/// # Return value
+		/// Returns the new id of each entity, by it's id in `other`.
+		pub fn merge(&mut self, other: Self) -> HashMap<EntityId, EntityId>
+		{
+			let (old_ids, mut entities): (Vec<_>, Vec<_>) = other.entities.into_entities()
+				.into_iter()
+				.unzip();
+			
+			// Get the new ids of all entities
+			let ids = self.alloc_batch( entities.len() );
+			let map: HashMap<_, _> = old_ids.into_iter().zip(ids.iter().copied()).collect();
+			
+			// Then map all ids within their components and insert them
+			for entity in &mut entities {
+				for storage in entity.components_mut() {
+					storage.map_entities(&mut |id| map.get(&id).copied().unwrap_or_else(EntityId::null));
+				}
+			}
+			self.insert_batch( ids.into_iter().zip(entities).collect() );
+			
+			map
+		}
+	}
+//--------------------------------------------------------------------------------------------------
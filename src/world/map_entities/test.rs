@@ -0,0 +1,76 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{World, Entity, EntityId, MapEntities};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type
+		#[derive(PartialEq, Clone, Debug)]
+		enum Components {
+			A(i32),
+			Targets(Vec<EntityId>),
+			Parent(Option<EntityId>),
+		}
+	
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl MapEntities for Components
+	{
+		fn map_entities(&mut self, map: &mut dyn FnMut(EntityId) -> EntityId)
+		{
+			match self {
+				Self::A(_) => (),
+				Self::Targets(ids) => ids.map_entities(map),
+				Self::Parent(id) => id.map_entities(map),
+			}
+		}
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn merge()
+	{
+		let mut other: World<Components> = World::new();
+		let id1 = other.add( mecs::entity![ Components::A(1) ] );
+		let id2 = other.add( mecs::entity![ Components::Parent( Some(id1) ) ] );
+		let id3 = other.add( mecs::entity![ Components::Targets( vec![id1, id2] ) ] );
+		
+		// Use up the slots of the other world's entities, so they can't keep their ids
+		let mut world: World<Components> = World::new();
+		let pred_id = world.add_pred(Entity::has::<Option<EntityId>>);
+		let existing: Vec<_> = (0..3).map(|num| world.add( mecs::entity![ Components::A(num) ] )).collect();
+		
+		let ids = world.merge(other);
+		assert_eq!(ids.len(), 3);
+		assert!(ids.values().all(|id| !existing.contains(id)));
+		
+		assert_eq!(world[ ids[&id1] ].get::<i32>(), Some(&1));
+		assert_eq!(world[ ids[&id2] ].get::<Option<EntityId>>(), Some(&Some( ids[&id1] )));
+		assert_eq!(world[ ids[&id3] ].get::<Vec<EntityId>>(), Some(&vec![ ids[&id1], ids[&id2] ]));
+		assert_eq!(world.iter_pred(pred_id).unwrap().map(|(_, id)| id).collect::<Vec<_>>(), [ ids[&id2] ]);
+	}
+	
+	#[test]
+	fn dangling()
+	{
+		let mut other: World<Components> = World::new();
+		let id1 = other.add( mecs::entity![ Components::A(1) ] );
+		let id2 = other.add( mecs::entity![ Components::Parent( Some(id1) ) ] );
+		other.remove(id1);
+		
+		let mut world: World<Components> = World::new();
+		let ids = world.merge(other);
+		
+		// Ids of entities that weren't moved shouldn't point to any entity
+		assert_eq!(world[ ids[&id2] ].get::<Option<EntityId>>(), Some(&Some( EntityId::null() )));
+	}
+//--------------------------------------------------------------------------------------------------
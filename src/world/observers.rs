@@ -0,0 +1,129 @@
+//! Component observers of a world
+//! 
+//! Observers are called whenever a component is added to, replaced on or
+//! removed from an entity of a world. As entities in a world can only gain
+//! or lose components through it, such as with [`World::insert_component`](crate::World::insert_component),
+//! every change is observed, including a component replaced by another one:
+//! 
+//! ```rust
+//! # use mecs::{World, DynStorage};
+//! # use std::sync::{Arc, Mutex};
+//! let mut world = World::new();
+//! 
+//! let replaced = Arc::new( Mutex::new( vec![] ) );
+//! let replaced_clone = Arc::clone(&replaced);
+//! world.on_replace::<i32, _>(move |_, &old, &new| replaced_clone.lock().unwrap().push( (old, new) ));
+//! 
+//! let id = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+//! world.insert_component(id, DynStorage::new(8i32)).unwrap();
+//! assert_eq!(*replaced.lock().unwrap(), [ (5, 8) ]);
+//! ```
+//! 
+//! Entities borrowed from the world can't be given components directly:
+//! 
+//! ```rust,compile_fail
+//! # use mecs::{World, DynStorage};
+//! let mut world = World::new();
+//! let id = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+//! world.get_mut(id).unwrap().add( DynStorage::new(8i32) );
+//! ```
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Collections
+use std::collections::HashMap;
+
+// Crate
+use crate::{util::KeyType, Storage, EntityId};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// An observer of a component being added or removed
+	/// 
+	/// Observers must be `Send` and `Sync`, so that the
+	/// world may be shared between threads.
+	pub type Observer<'a, S> = Box<dyn FnMut(EntityId, &S) + Send + Sync + 'a>;
+	
+	/// An observer of a component being replaced, called with the old and new components
+	pub type ReplaceObserver<'a, S> = Box<dyn FnMut(EntityId, &S, &S) + Send + Sync + 'a>;
+	
+	/// All component observers of a world
+	pub struct Observers<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType
+	{
+		/// Observers of components being added, by component id
+		added: HashMap< S::Id, Vec<Observer<'a, S>> >,
+		
+		/// Observers of components being replaced, by component id
+		replaced: HashMap< S::Id, Vec<ReplaceObserver<'a, S>> >,
+		
+		/// Observers of components being removed, by component id
+		removed: HashMap< S::Id, Vec<Observer<'a, S>> >,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S> Observers<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty list of observers
+			pub fn new() -> Self {
+				Self { added: HashMap::new(), replaced: HashMap::new(), removed: HashMap::new() }
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Register
+		//--------------------------------------------------------------------------------------------------
+			/// Registers an observer of a component being added
+			pub fn on_add(&mut self, component_id: S::Id, observer: Observer<'a, S>) {
+				self.added.entry(component_id).or_default().push(observer);
+			}
+			
+			/// Registers an observer of a component being replaced
+			pub fn on_replace(&mut self, component_id: S::Id, observer: ReplaceObserver<'a, S>) {
+				self.replaced.entry(component_id).or_default().push(observer);
+			}
+			
+			/// Registers an observer of a component being removed
+			pub fn on_remove(&mut self, component_id: S::Id, observer: Observer<'a, S>) {
+				self.removed.entry(component_id).or_default().push(observer);
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Notify
+		//--------------------------------------------------------------------------------------------------
+			/// Notifies all observers of a component being added
+			pub fn added(&mut self, id: EntityId, storage: &S)
+			{
+				for observer in self.added.get_mut( &storage.id() ).into_iter().flatten() {
+					observer(id, storage);
+				}
+			}
+			
+			/// Notifies all observers of a component being replaced
+			pub fn replaced(&mut self, id: EntityId, old: &S, new: &S)
+			{
+				for observer in self.replaced.get_mut( &new.id() ).into_iter().flatten() {
+					observer(id, old, new);
+				}
+			}
+			
+			/// Notifies all observers of a component being removed
+			pub fn removed(&mut self, id: EntityId, storage: &S)
+			{
+				for observer in self.removed.get_mut( &storage.id() ).into_iter().flatten() {
+					observer(id, storage);
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+//--------------------------------------------------------------------------------------------------
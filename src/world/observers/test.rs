@@ -0,0 +1,111 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{World, EntityId};
+
+// Sync
+use std::sync::{Arc, Mutex};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type
+		#[derive(PartialEq, Clone, Debug)]
+		enum Components {
+			A(i32),
+			B(&'static str),
+			C(f32),
+		}
+	
+	}
+	
+	/// An observed change
+	#[derive(PartialEq, Debug)]
+	enum Change {
+		Added(EntityId, i32),
+		Replaced(EntityId, i32, i32),
+		Removed(EntityId, i32),
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Creates a world that records all changes to it's `i32`s
+	fn observed_world() -> (World<'static, Components>, Arc< Mutex< Vec<Change> > >)
+	{
+		let mut world = World::new();
+		let changes = Arc::new( Mutex::new( vec![] ) );
+		
+		let added = Arc::clone(&changes);
+		world.on_add::<i32, _>(move |id, &num| added.lock().unwrap().push( Change::Added(id, num) ));
+		let replaced = Arc::clone(&changes);
+		world.on_replace::<i32, _>(move |id, &old, &new| replaced.lock().unwrap().push( Change::Replaced(id, old, new) ));
+		let removed = Arc::clone(&changes);
+		world.on_remove::<i32, _>(move |id, &num| removed.lock().unwrap().push( Change::Removed(id, num) ));
+		
+		(world, changes)
+	}
+	
+	#[test]
+	fn world()
+	{
+		let (mut world, changes) = observed_world();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1), Components::B("1") ] );
+		let id2 = world.add( mecs::entity![ Components::B("2") ] );
+		world.insert_component(id2, Components::A(2)).unwrap();
+		world.insert_component(id2, Components::A(3)).unwrap();
+		world.insert_component(id2, Components::C(3.0)).unwrap();
+		world.remove_component::<i32>(id2);
+		world.remove_component::<i32>(id2);
+		world.remove(id1);
+		
+		assert_eq!(*changes.lock().unwrap(), [
+			Change::Added(id1, 1),
+			Change::Added(id2, 2),
+			Change::Replaced(id2, 2, 3),
+			Change::Removed(id2, 3),
+			Change::Removed(id1, 1),
+		]);
+	}
+	
+	#[test]
+	fn commands()
+	{
+		let (mut world, changes) = observed_world();
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		
+		let mut commands = world.commands();
+		let id2 = commands.spawn( mecs::entity![ Components::A(2) ] );
+		commands.insert_component(id1, Components::A(3));
+		commands.insert_component(id2, Components::B("2"));
+		commands.remove_component::<i32>(id2);
+		commands.despawn(id1);
+		world.apply(commands);
+		
+		assert_eq!(*changes.lock().unwrap(), [
+			Change::Added(id1, 1),
+			Change::Added(id2, 2),
+			Change::Replaced(id1, 1, 3),
+			Change::Removed(id2, 2),
+			Change::Removed(id1, 3),
+		]);
+	}
+	
+	#[test]
+	fn by_id()
+	{
+		let mut world: World<Components> = World::new();
+		let added = Arc::new( Mutex::new( vec![] ) );
+		
+		let added_names = Arc::clone(&added);
+		world.on_add_id(1, move |_, storage| added_names.lock().unwrap().push( storage.clone() ));
+		
+		world.add( mecs::entity![ Components::A(1), Components::B("1") ] );
+		world.add( mecs::entity![ Components::C(2.0) ] );
+		
+		assert_eq!(*added.lock().unwrap(), [ Components::B("1") ]);
+	}
+//--------------------------------------------------------------------------------------------------
@@ -1,75 +1,487 @@
 //! Predicates over a world
 
-// Cell
-use std::cell::Cell;
+// Collections
+use std::collections::{HashMap, HashSet};
+
+// Hash
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 // Crate
-use crate::{util::KeyType, Storage, Entity, EntityId};
+use crate::{util::KeyType, Storage, EntityId};
+use super::{sparse::SparseSet, pred_id::PredMap, Filter, FilterMask, PredId, Backend, BackendRef, ReadEntity};
 
 // Types
 //--------------------------------------------------------------------------------------------------
 	/// A predicate
-	pub struct Predicate<'a, S>( pub(in super) Box<dyn Fn(&Entity<'a, S>) -> bool> )
+	/// 
+	/// Predicates must be `Send` and `Sync`, so that the
+	/// world may be shared between threads.
+	#[allow(clippy::type_complexity)] // It's just a boxed closure
+	pub struct Predicate<'a, S, B>( pub(in super) Box<dyn Fn(BackendRef<'a, '_, S, B>) -> bool + Send + Sync + 'a> )
 	where
 		S    : Storage<'a>,
-		S::Id: KeyType;
+		S::Id: KeyType,
+		B    : Backend<'a, S>;
 	
-	/// A predicate along with it's current ids
-	#[derive(Debug)]
-	pub struct PredicateIds<'a, S>
+	/// A filter along with it's current ids
+	pub struct PredicateIds<'a, S, B>
 	where
 		S    : Storage<'a>,
-		S::Id: KeyType
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
 	{
-		/// The predicate of these ids
-		pub(in super) pred: Predicate<'a, S>,
+		/// The filter of these ids
+		filter: Filter<'a, S, B>,
+		
+		/// The filter compiled into masks, if it may be
+		mask: Option<FilterMask>,
+		
+		/// If the filter depends on when components were added or changed
+		/// 
+		/// As the ids are only updated when components are added or removed, they
+		/// include entities that don't match because of their ticks, which must
+		/// be checked when iterating, see [`Predicates::matches_ticks`].
+		uses_ticks: bool,
 		
 		/// The current ids
-		pub(in super) ids: Vec< Cell<EntityId> >,
+		/// 
+		/// The sparse array of the set doubles as a reverse index from
+		/// each entity id to it's position, so ids may be removed in constant time.
+		pub(in super) ids: SparseSet<()>,
+		
+		/// The number of predicates sharing this filter
+		refs: usize,
+	}
+	
+	/// All predicates of a world
+	/// 
+	/// Predicates with identical declarative filters share the
+	/// same filter, and so the same ids.
+	/// 
+	/// # Indexing
+	/// Filters are indexed by the hash of the component ids they
+	/// depend on, so only the filters that may be affected by a
+	/// change are re-evaluated. As hashes may collide, this may
+	/// re-evaluate some unaffected filters, which is harmless.
+	pub struct Predicates<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		/// All predicates, stored by their predicate id, along with the id of their filter
+		preds: PredMap<S, PredId<S>>,
+		
+		/// All filters, along with their ids
+		filters: PredMap<S, PredicateIds<'a, S, B>>,
+		
+		/// Filters to evaluate when adding an entity, by one of the component ids they require
+		by_required: HashMap< u64, Vec<PredId<S>> >,
+		
+		/// Filters to evaluate when adding any entity, as they don't require any components
+		unindexed: Vec<PredId<S>>,
+		
+		/// Filters to evaluate when a component changes, by each component id they depend on
+		by_dependency: HashMap< u64, Vec<PredId<S>> >,
+		
+		/// Filters to evaluate when any component changes, as they aren't declarative
+		dynamic: Vec<PredId<S>>,
 	}
 //--------------------------------------------------------------------------------------------------
 
 // Impl
 //--------------------------------------------------------------------------------------------------
-	impl<'a, S> std::fmt::Debug for Predicate<'a, S>
+	impl<'a, S, B> Predicates<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty set of predicates
+			#[must_use]
+			pub fn new() -> Self {
+				Self {
+					preds: PredMap::new(),
+					filters: PredMap::new(),
+					by_required: HashMap::new(),
+					unindexed: vec![],
+					by_dependency: HashMap::new(),
+					dynamic: vec![],
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Add / Remove
+		//--------------------------------------------------------------------------------------------------
+			/// Adds a predicate given it's filter and all entities it may match
+			pub fn insert<'b, I>(&mut self, filter: Filter<'a, S, B>, entities: I) -> PredId<S>
+			where
+				B    : 'b,
+				S::Id: 'b,
+				I    : Iterator<Item = (EntityId, BackendRef<'a, 'b, S, B>)>,
+			{
+				let filter_id = self.acquire(filter, entities);
+				self.preds.insert(filter_id)
+			}
+			
+			/// Removes a predicate, returning if it existed
+			pub fn remove(&mut self, id: PredId<S>) -> bool
+			{
+				let Some(filter_id) = self.preds.remove(id) else {
+					return false;
+				};
+				
+				self.release(filter_id);
+				true
+			}
+			
+			/// Replaces the filter of a predicate, returning if it existed
+			pub fn replace<'b, I>(&mut self, id: PredId<S>, filter: Filter<'a, S, B>, entities: I) -> bool
+			where
+				B    : 'b,
+				S::Id: 'b,
+				I    : Iterator<Item = (EntityId, BackendRef<'a, 'b, S, B>)>,
+			{
+				if !self.contains(id) {
+					return false;
+				}
+				
+				// Get the new filter before releasing the old one, in case they're the same
+				let filter_id = self.acquire(filter, entities);
+				let old_filter_id = std::mem::replace(
+					self.preds.get_mut(id).expect("Could not get predicate from id"),
+					filter_id
+				);
+				self.release(old_filter_id);
+				
+				true
+			}
+			
+			/// Returns the id of a filter, sharing an existing
+			/// identical filter if there is one.
+			#[allow(clippy::arithmetic_side_effects)] // We need to add one to increase the references
+			fn acquire<'b, I>(&mut self, filter: Filter<'a, S, B>, entities: I) -> PredId<S>
+			where
+				B    : 'b,
+				S::Id: 'b,
+				I    : Iterator<Item = (EntityId, BackendRef<'a, 'b, S, B>)>,
+			{
+				// If we already have this filter, share it
+				// Note: Non-declarative filters are never equal, so we don't bother
+				if filter.is_declarative() {
+					if let Some((filter_id, pred)) = self.filters.iter_mut().find(|(_, pred)| pred.filter == filter) {
+						pred.refs += 1;
+						return filter_id;
+					}
+				}
+				
+				// Else go through all entities and add the ones that match this filter
+				let mut pred = PredicateIds {
+					mask: filter.components_mask(),
+					uses_ticks: filter.uses_ticks(),
+					filter,
+					ids: SparseSet::new(),
+					refs: 1,
+				};
+				for (entity_id, entity) in entities {
+					if pred.matches(entity) {
+						pred.ids.insert(entity_id, ());
+					}
+				}
+				
+				// Then index it
+				let required     = pred.filter.required().first().map(|id| hash_id::<S>(id));
+				let dependencies = pred.filter.dependencies().map(|ids| ids.into_iter().map(hash_id::<S>).collect::<Vec<_>>());
+				let filter_id = self.filters.insert(pred);
+				
+				match required {
+					Some(hash) => self.by_required.entry(hash).or_default().push(filter_id),
+					None       => self.unindexed.push(filter_id),
+				}
+				match dependencies {
+					Some(hashes) => for hash in hashes {
+						self.by_dependency.entry(hash).or_default().push(filter_id);
+					},
+					None => self.dynamic.push(filter_id),
+				}
+				
+				filter_id
+			}
+			
+			/// Releases a reference to a filter, removing it if there are no more references
+			#[allow(clippy::arithmetic_side_effects)] // We need to subtract one to decrease the references
+			fn release(&mut self, filter_id: PredId<S>)
+			{
+				let pred = self.filters.get_mut(filter_id).expect("Could not get filter from id");
+				pred.refs -= 1;
+				if pred.refs != 0 {
+					return;
+				}
+				
+				// Remove it from all indices
+				let pred = self.filters.remove(filter_id).expect("Could not get filter from id");
+				match pred.filter.required().first() {
+					Some(id) => remove_from_index(&mut self.by_required, hash_id::<S>(id), filter_id),
+					None     => self.unindexed.retain(|&id| id != filter_id),
+				}
+				match pred.filter.dependencies() {
+					Some(ids) => for id in ids {
+						remove_from_index(&mut self.by_dependency, hash_id::<S>(id), filter_id);
+					},
+					None => self.dynamic.retain(|&id| id != filter_id),
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Access
+		//--------------------------------------------------------------------------------------------------
+			/// Returns the ids matched by a predicate
+			#[must_use]
+			pub fn ids(&self, id: PredId<S>) -> Option<&SparseSet<()>> {
+				self.get(id)
+					.map(|pred| &pred.ids)
+			}
+			
+			/// Returns the filter of a predicate, along with it's ids
+			#[must_use]
+			pub fn get(&self, id: PredId<S>) -> Option<&PredicateIds<'a, S, B>> {
+				self.preds.get(id)
+					.and_then(|&filter_id| self.filters.get(filter_id))
+			}
+			
+			/// Checks if a predicate exists
+			#[must_use]
+			pub fn contains(&self, id: PredId<S>) -> bool {
+				self.preds.get(id).is_some()
+			}
+			
+			/// Checks if an entity within the ids of a predicate is matched by when it's components were added or changed
+			/// 
+			/// Returns `true` if the predicate doesn't depend on ticks, or if it doesn't exist.
+			#[must_use]
+			pub fn matches_ticks(&self, id: PredId<S>, entity: BackendRef<'a, '_, S, B>) -> bool
+			{
+				self.get(id)
+					.is_none_or(|pred| pred.matches_ticks(entity))
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Entities
+		//--------------------------------------------------------------------------------------------------
+			/// Adds an entity to all filters it matches
+			pub fn add_entity<'b>(&mut self, id: EntityId, entity: BackendRef<'a, 'b, S, B>)
+			where
+				S::Id: 'b,
+			{
+				// Only filters requiring one of it's components, or none at all, may match it
+				let filters = &mut self.filters;
+				let by_required = &self.by_required;
+				let indexed = entity.ids()
+					.filter_map(|component_id| by_required.get( &hash_id::<S>(component_id) ))
+					.flatten();
+				for &filter_id in indexed.chain(&self.unindexed) {
+					update(filters, filter_id, id, entity);
+				}
+			}
+			
+			/// Adds a batch of new entities to all filters they match
+			/// 
+			/// Unlike calling [`Predicates::add_entity`] for each entity, each
+			/// filter is only looked up once for the whole batch.
+			pub fn add_entities<'b, I>(&mut self, entities: I)
+			where
+				B    : 'b,
+				S::Id: 'b,
+				I    : Iterator<Item = (EntityId, BackendRef<'a, 'b, S, B>)>,
+			{
+				// Only filters requiring one of their components, or none at all, may match them
+				let entities: Vec<_> = entities.collect();
+				let hashes: HashSet<u64> = entities.iter()
+					.flat_map(|&(_, entity)| entity.ids())
+					.map(hash_id::<S>)
+					.collect();
+				let filter_ids: HashSet<PredId<S>> = hashes.iter()
+					.filter_map(|hash| self.by_required.get(hash))
+					.flatten()
+					.chain(&self.unindexed)
+					.copied()
+					.collect();
+				
+				// Then evaluate each filter over the whole batch
+				for filter_id in filter_ids {
+					let pred = self.filters.get_mut(filter_id).expect("Could not get filter from id");
+					for &(id, entity) in &entities {
+						if pred.matches(entity) {
+							pred.ids.insert(id, ());
+						}
+					}
+				}
+			}
+			
+			/// Removes an entity from all filters
+			pub fn remove_entity(&mut self, id: EntityId)
+			{
+				for pred in self.filters.values_mut() {
+					pred.ids.remove(id);
+				}
+			}
+			
+			/// Re-evaluates all filters on an entity
+			pub fn update_entity(&mut self, id: EntityId, entity: BackendRef<'a, '_, S, B>)
+			{
+				for pred in self.filters.values_mut() {
+					pred.update(id, entity);
+				}
+			}
+			
+			/// Re-evaluates all filters that may be affected by a component of an entity changing
+			pub fn update_component(&mut self, id: EntityId, entity: BackendRef<'a, '_, S, B>, component_id: &S::Id)
+			{
+				let filters = &mut self.filters;
+				let indexed = self.by_dependency.get( &hash_id::<S>(component_id) )
+					.into_iter()
+					.flatten();
+				for &filter_id in indexed.chain(&self.dynamic) {
+					update(filters, filter_id, id, entity);
+				}
+			}
+			
+			/// Re-evaluates all filters that may be affected by any of a set of components of an entity changing
+			/// 
+			/// Unlike calling [`Predicates::update_component`] for each
+			/// component, each filter is only evaluated once.
+			pub fn update_components<'i, I>(&mut self, id: EntityId, entity: BackendRef<'a, '_, S, B>, component_ids: I)
+			where
+				S::Id: 'i,
+				I    : IntoIterator<Item = &'i S::Id>,
+			{
+				let filter_ids: HashSet<PredId<S>> = component_ids.into_iter()
+					.filter_map(|component_id| self.by_dependency.get( &hash_id::<S>(component_id) ))
+					.flatten()
+					.chain(&self.dynamic)
+					.copied()
+					.collect();
+				for filter_id in filter_ids {
+					update(&mut self.filters, filter_id, id, entity);
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl<'a, S, B> PredicateIds<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		/// Returns all entities that may match the filter
+		/// 
+		/// If the filter depends on ticks, these must still be checked, see [`PredicateIds::matches_ticks`].
+		#[must_use]
+		pub const fn ids(&self) -> &SparseSet<()> {
+			&self.ids
+		}
+		
+		/// Checks if the filter is declarative
+		/// 
+		/// Declarative filters, ignoring ticks, only depend on which components an
+		/// entity has, so all entities with the same component ids match them alike.
+		#[must_use]
+		pub fn is_declarative(&self) -> bool {
+			self.filter.is_declarative()
+		}
+		
+		/// Checks if an entity matches the filter, ignoring it's ticks, through it's mask if it has one
+		#[must_use]
+		pub fn matches(&self, entity: BackendRef<'a, '_, S, B>) -> bool
+		{
+			self.mask.as_ref().map_or_else(
+				|| self.filter.matches_components(entity),
+				|mask| mask.matches_mask(entity.mask()),
+			)
+		}
+		
+		/// Checks if an entity within the ids is matched by when it's components were added or changed
+		/// 
+		/// Returns `true` if the filter doesn't depend on ticks.
+		#[must_use]
+		pub fn matches_ticks(&self, entity: BackendRef<'a, '_, S, B>) -> bool {
+			!self.uses_ticks || self.filter.matches(entity)
+		}
+		
+		/// Adds or removes an entity depending on whether it matches the filter
+		fn update(&mut self, id: EntityId, entity: BackendRef<'a, '_, S, B>)
+		{
+			if self.matches(entity) {
+				self.ids.insert(id, ());
+			} else {
+				self.ids.remove(id);
+			}
+		}
+	}
+	
+	impl<'a, S, B> std::fmt::Debug for Predicate<'a, S, B>
 	where
 		S    : Storage<'a>,
 		S::Id: KeyType,
+		B    : Backend<'a, S>,
 	{
 		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 			write!(f, "Predicate")
 		}
 	}
 	
-	impl<'a, S> FnOnce<(&Entity<'a, S>,)> for Predicate<'a, S>
+	impl<'a, S, B> Predicate<'a, S, B>
 	where
 		S    : Storage<'a>,
 		S::Id: KeyType,
+		B    : Backend<'a, S>,
 	{
-		type Output = bool;
-		
-		extern "rust-call" fn call_once(self, args: (&Entity<'a, S>,)) -> Self::Output {
-			self.0.call_once(args)
+		/// Checks if an entity is matched by this predicate
+		#[must_use]
+		pub fn matches(&self, entity: BackendRef<'a, '_, S, B>) -> bool {
+			(self.0)(entity)
 		}
 	}
-	
-	impl<'a, S> FnMut<(&Entity<'a, S>,)> for Predicate<'a, S>
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Returns the hash of a component id, used for indexing filters
+	fn hash_id<'a, S>(id: &S::Id) -> u64
 	where
 		S    : Storage<'a>,
 		S::Id: KeyType,
 	{
-		extern "rust-call" fn call_mut(&mut self, args: (&Entity<'a, S>,)) -> Self::Output {
-			self.0.call_mut(args)
-		}
+		let mut hasher = DefaultHasher::new();
+		id.hash(&mut hasher);
+		hasher.finish()
 	}
 	
-	impl<'a, S> Fn<(&Entity<'a, S>,)> for Predicate<'a, S>
+	/// Re-evaluates a filter on an entity, given it's id
+	fn update<'a, S, B>(filters: &mut PredMap<S, PredicateIds<'a, S, B>>, filter_id: PredId<S>, id: EntityId, entity: BackendRef<'a, '_, S, B>)
 	where
 		S    : Storage<'a>,
-		S::Id: KeyType,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		filters.get_mut(filter_id)
+			.expect("Could not get filter from id")
+			.update(id, entity);
+	}
+	
+	/// Removes a filter from an index
+	fn remove_from_index<S>(index: &mut HashMap< u64, Vec<PredId<S>> >, hash: u64, filter_id: PredId<S>)
 	{
-		extern "rust-call" fn call(&self, args: (&Entity<'a, S>,)) -> Self::Output {
-			self.0.call(args)
+		if let Some(filter_ids) = index.get_mut(&hash) {
+			// Note: With colliding hashes, a filter may be indexed more than once under the same hash
+			filter_ids.retain(|&id| id != filter_id);
+			if filter_ids.is_empty() {
+				index.remove(&hash);
+			}
 		}
 	}
 //--------------------------------------------------------------------------------------------------
@@ -0,0 +1,214 @@
+//! Predicate Ids
+//! 
+//! Each predicate id is tied to the world that created it,
+//! and, like entity ids, carries a generation, so that using
+//! an id after it's predicate has been removed, or with another
+//! world, is detected instead of silently referring to another
+//! predicate.
+
+// Atomic
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Marker
+use std::marker::PhantomData;
+
+// Fmt
+use std::fmt;
+
+// Hash
+use std::hash::{Hash, Hasher};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A predicate's ID
+	/// 
+	/// Returned by the `add_pred` method of each world and only valid for that world.
+	pub struct PredId<S>
+	{
+		/// The id of the world this predicate belongs to
+		world: usize,
+		
+		/// The index of this id's slot
+		idx: usize,
+		
+		/// The generation of this id's slot
+		generation: u32,
+		
+		/// Marker for the storage type of the world
+		phantom: PhantomData<fn() -> S>,
+	}
+	
+	/// Map of predicates by their predicate ids
+	#[derive(Debug)]
+	pub(crate) struct PredMap<S, T>
+	{
+		/// The id of the world this map belongs to
+		world: usize,
+		
+		/// All slots
+		slots: Vec< PredSlot<T> >,
+		
+		/// Indices of all free slots
+		free: Vec<usize>,
+		
+		/// Marker for the storage type of the world
+		phantom: PhantomData<fn() -> S>,
+	}
+	
+	/// A slot within a [`PredMap`]
+	#[derive(Debug)]
+	struct PredSlot<T>
+	{
+		/// The current generation of this slot
+		generation: u32,
+		
+		/// The value, if this slot is in use
+		value: Option<T>,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Statics
+//--------------------------------------------------------------------------------------------------
+	/// The next world id to give out
+	static NEXT_WORLD_ID: AtomicUsize = AtomicUsize::new(0);
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<S, T> PredMap<S, T>
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates a new map, with it's own unique world id
+			#[must_use]
+			pub(crate) fn new() -> Self {
+				Self {
+					world: NEXT_WORLD_ID.fetch_add(1, Ordering::Relaxed),
+					slots: vec![],
+					free: vec![],
+					phantom: PhantomData,
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Add / Remove
+		//--------------------------------------------------------------------------------------------------
+			/// Inserts a value and returns it's id
+			#[allow(clippy::arithmetic_side_effects)] // We need to subtract one to get the last index
+			pub(crate) fn insert(&mut self, value: T) -> PredId<S>
+			{
+				// Reuse a free slot, if we have any, else create a new one
+				let idx = self.free.pop().unwrap_or_else(|| {
+					self.slots.push( PredSlot{ generation: 0, value: None } );
+					self.slots.len() - 1
+				});
+				
+				let slot = &mut self.slots[idx];
+				slot.value = Some(value);
+				
+				PredId {
+					world: self.world,
+					idx,
+					generation: slot.generation,
+					phantom: PhantomData,
+				}
+			}
+			
+			/// Removes a value given it's id
+			pub(crate) fn remove(&mut self, id: PredId<S>) -> Option<T>
+			{
+				let slot = self.slot_mut(id)?;
+				
+				// Bump the generation so any old ids are no longer valid
+				let value = slot.value.take();
+				slot.generation = slot.generation.wrapping_add(1);
+				self.free.push(id.idx);
+				
+				value
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Access
+		//--------------------------------------------------------------------------------------------------
+			/// Returns a reference to a value given it's id
+			#[must_use]
+			pub(crate) fn get(&self, id: PredId<S>) -> Option<&T>
+			{
+				self.slots.get(id.idx)
+					.filter(|slot| id.world == self.world && slot.generation == id.generation)
+					.and_then(|slot| slot.value.as_ref())
+			}
+			
+			/// Returns a mutable reference to a value given it's id
+			#[must_use]
+			pub(crate) fn get_mut(&mut self, id: PredId<S>) -> Option<&mut T> {
+				self.slot_mut(id)
+					.and_then(|slot| slot.value.as_mut())
+			}
+			
+			/// Returns a mutable reference to the slot of an id, if it's in use
+			#[must_use]
+			fn slot_mut(&mut self, id: PredId<S>) -> Option<&mut PredSlot<T>>
+			{
+				let world = self.world;
+				self.slots.get_mut(id.idx)
+					.filter(|slot| id.world == world && slot.generation == id.generation && slot.value.is_some())
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Iterators
+		//--------------------------------------------------------------------------------------------------
+			/// Returns a mutable iterator over all values
+			pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+				self.slots.iter_mut()
+					.filter_map(|slot| slot.value.as_mut())
+			}
+			
+			/// Returns a mutable iterator over all values along with their ids
+			pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (PredId<S>, &mut T)>
+			{
+				let world = self.world;
+				self.slots.iter_mut()
+					.enumerate()
+					.filter_map(move |(idx, slot)| {
+						let id = PredId { world, idx, generation: slot.generation, phantom: PhantomData };
+						slot.value.as_mut().map(|value| (id, value))
+					})
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl<S> Clone for PredId<S> {
+		fn clone(&self) -> Self {
+			*self
+		}
+	}
+	
+	impl<S> Copy for PredId<S> {}
+	
+	impl<S> PartialEq for PredId<S> {
+		fn eq(&self, other: &Self) -> bool {
+			self.world == other.world && self.idx == other.idx && self.generation == other.generation
+		}
+	}
+	
+	impl<S> Eq for PredId<S> {}
+	
+	impl<S> Hash for PredId<S> {
+		fn hash<H: Hasher>(&self, state: &mut H) {
+			self.world     .hash(state);
+			self.idx       .hash(state);
+			self.generation.hash(state);
+		}
+	}
+	
+	impl<S> fmt::Debug for PredId<S> {
+		fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+			f.debug_struct("PredId")
+				.field("world"     , &self.world     )
+				.field("idx"       , &self.idx       )
+				.field("generation", &self.generation)
+				.finish()
+		}
+	}
+//--------------------------------------------------------------------------------------------------
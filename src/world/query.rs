@@ -0,0 +1,514 @@
+//! Typed queries over a world
+//! 
+//! A query is a component reference, or a tuple of them, that
+//! may be fetched from every entity that has the required components
+//! with [`World::query`].
+//! 
+//! Each element of a query may be either:
+//! - `&C`, requiring the component `C`.
+//! - `&mut C`, requiring the component `C` and marking it as changed.
+//! - `Option<&C>` or `Option<&mut C>`, which don't require the component.
+//! - [`Changed<C>`] or [`Added<C>`], requiring the component `C` to have been
+//!   changed or added since a tick, without accessing it.
+//! 
+//! By default, [`Changed<C>`] and [`Added<C>`] match components changed or
+//! added in the current tick, see [`World::query_since`](crate::World::query_since)
+//! for checking against any other tick.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{World, DynStorage};
+//! let mut world = World::new();
+//! 
+//! world.add( mecs::entity![ DynStorage::new(5i32), DynStorage::new(1.5f32) ] );
+//! world.add( mecs::entity![ DynStorage::new(8i32) ] );
+//! 
+//! for (_, (num, scale)) in world.query::<(&mut i32, Option<&f32>)>() {
+//! 	if let Some(scale) = scale {
+//! 		*num *= *scale as i32;
+//! 	}
+//! }
+//! 
+//! let mut nums: Vec<i32> = world.query::<&i32>().map(|(_, num)| *num).collect();
+//! nums.sort();
+//! assert_eq!(nums, [5, 8]);
+//! ```
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Collections
+use std::collections::HashSet;
+
+// Marker
+use std::marker::PhantomData;
+
+// Traits
+use std::iter::Iterator;
+
+// Crate
+use crate::{util::KeyType, Storage, Component, EntityId, Tick, ComponentTicks};
+use super::{Backend, Fetch, Entities};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A component of an entity being offered to a query
+	pub struct QueryComponent<'a, 'b, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The id of the component
+		id: &'b S::Id,
+		
+		/// The component's storage and ticks
+		storage: ComponentRef<'b, S>,
+		
+		/// The tick to mark the component with if it's changed
+		change_tick: Tick,
+		
+		/// The tick to check if the component was added or changed since
+		since: Tick,
+	}
+	
+	/// A query filter requiring a component to have been changed since a tick
+	/// 
+	/// # Example
+	/// 
+	/// ```rust
+	/// # use mecs::{World, DynStorage, Changed};
+	/// let mut world = World::new();
+	/// 
+	/// let id1 = world.add( mecs::entity![ DynStorage::new(5i32) ] );
+	/// let id2 = world.add( mecs::entity![ DynStorage::new(8i32) ] );
+	/// 
+	/// let last_tick = world.change_tick();
+	/// world.increment_change_tick();
+	/// *world.get_mut(id2).unwrap().get_mut::<i32>().unwrap() += 1;
+	/// 
+	/// let ids: Vec<_> = world.query::<(&i32, Changed<i32>)>().map(|(id, _)| id).collect();
+	/// assert_eq!(ids, [id2]);
+	/// assert_eq!(world.query_since::<Changed<i32>>(last_tick).count(), 1);
+	/// # let _ = id1;
+	/// ```
+	pub struct Changed<C>(PhantomData<fn() -> C>);
+	
+	/// A query filter requiring a component to have been added since a tick
+	pub struct Added<C>(PhantomData<fn() -> C>);
+	
+	/// A reference to a component's storage and ticks
+	pub(in super) enum ComponentRef<'b, S>
+	{
+		/// The component may only be read
+		Shared(&'b S, &'b ComponentTicks),
+		
+		/// The component may be read and written
+		Unique(&'b mut S, &'b mut ComponentTicks),
+	}
+	
+	/// Iterator over a query
+	pub struct QueryIter<'a, 'b, S, Q, B = Entities<'a, S>>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S> + 'b,
+	{
+		/// The rows of the query, given by the backend
+		pub(in super) rows: B::Fetch<'b>,
+		
+		/// Marker for the query type
+		pub(in super) phantom: PhantomData<fn() -> Q>,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Traits
+//--------------------------------------------------------------------------------------------------
+	/// A query over the components of an entity
+	/// 
+	/// This trait is implemented for `&C`, `&mut C`, `Option<&C>`,
+	/// `Option<&mut C>`, [`Changed<C>`], [`Added<C>`] and tuples of up to 8 queries.
+	/// 
+	/// # Fetching
+	/// All components of an entity are first checked by every element,
+	/// then offered to the query one by one, each being taken by at most
+	/// one element, so that every element may borrow a different component
+	/// of the same entity. Once all components were offered, the query is
+	/// only finished if it matched, see [`Query::matches`].
+	pub trait Query<'a, 'b, S>: Sized
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The state kept while fetching this query
+		type State: Default;
+		
+		/// Adds the ids of all components an entity is required to have to `ids`
+		fn required(ids: &mut Vec<S::Id>);
+		
+		/// Adds the ids of all components accessed by this query to `ids`
+		fn accessed(ids: &mut Vec<S::Id>);
+		
+		/// Adds the ids of all components written by this query to `ids`
+		fn written(ids: &mut Vec<S::Id>);
+		
+		/// Checks a component, before it's offered to any element
+		/// 
+		/// This allows queries that don't take a component to inspect it
+		/// even if it's taken by another element. Does nothing by default.
+		fn check(_state: &mut Self::State, _component: &QueryComponent<'a, 'b, S>) {}
+		
+		/// Offers a component to this query
+		/// 
+		/// # Return
+		/// Returns the component back if it wasn't taken
+		fn offer(state: &mut Self::State, component: QueryComponent<'a, 'b, S>) -> Option< QueryComponent<'a, 'b, S> >;
+		
+		/// Checks if this query matched, after all components were offered
+		/// 
+		/// Queries that didn't match aren't finished, so that they have no effects,
+		/// such as marking components as changed.
+		fn matches(state: &Self::State) -> bool;
+		
+		/// Finishes fetching this query
+		/// 
+		/// # Return
+		/// Returns `None` if any required components weren't offered
+		fn finish(state: Self::State) -> Option<Self>;
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, 'b, S> QueryComponent<'a, 'b, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// Creates a new component
+		pub(in super) const fn new(id: &'b S::Id, storage: ComponentRef<'b, S>, change_tick: Tick, since: Tick) -> Self {
+			Self { id, storage, change_tick, since }
+		}
+		
+		/// Returns the id of this component
+		#[must_use]
+		pub const fn id(&self) -> &'b S::Id {
+			self.id
+		}
+		
+		/// Returns the ticks of this component
+		#[must_use]
+		pub const fn ticks(&self) -> ComponentTicks
+		{
+			match &self.storage {
+				ComponentRef::Shared(_, ticks) => **ticks,
+				ComponentRef::Unique(_, ticks) => **ticks,
+			}
+		}
+	}
+	
+	impl<S> ComponentRef<'_, S>
+	{
+		/// Reborrows this component for a shorter lifetime
+		pub(in super) const fn reborrow(&mut self) -> ComponentRef<'_, S>
+		{
+			match self {
+				ComponentRef::Shared(storage, ticks) => ComponentRef::Shared(storage, ticks),
+				ComponentRef::Unique(storage, ticks) => ComponentRef::Unique(storage, ticks),
+			}
+		}
+	}
+	
+	impl<'a, 'b, S, Q, B> Iterator for QueryIter<'a, 'b, S, Q, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		Q    : Query<'a, 'b, S>,
+		B    : Backend<'a, S> + 'b,
+	{
+		type Item = (EntityId, Q);
+		
+		fn next(&mut self) -> Option< Self::Item > {
+			self.rows.next()
+		}
+		
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			self.rows.size_hint()
+		}
+	}
+	
+	impl<'a, 'b, S, C> Query<'a, 'b, S> for &'b C
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		C    : Component<'a, S>,
+	{
+		type State = Option<Self>;
+		
+		fn required(ids: &mut Vec<S::Id>) {
+			ids.push( C::id() );
+		}
+		
+		fn accessed(ids: &mut Vec<S::Id>) {
+			ids.push( C::id() );
+		}
+		
+		fn written(_ids: &mut Vec<S::Id>) {}
+		
+		fn offer(state: &mut Self::State, component: QueryComponent<'a, 'b, S>) -> Option< QueryComponent<'a, 'b, S> >
+		{
+			if state.is_some() || *component.id != C::id() {
+				return Some(component);
+			}
+			
+			*state = match component.storage {
+				ComponentRef::Shared(storage, _) => C::get(storage),
+				ComponentRef::Unique(storage, _) => C::get(storage),
+			};
+			None
+		}
+		
+		fn matches(state: &Self::State) -> bool {
+			state.is_some()
+		}
+		
+		fn finish(state: Self::State) -> Option<Self> {
+			state
+		}
+	}
+	
+	impl<'a, 'b, S, C> Query<'a, 'b, S> for &'b mut C
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		C    : Component<'a, S>,
+	{
+		/// The component, along with it's ticks and the tick to mark it as changed with
+		type State = Option<( Self, &'b mut ComponentTicks, Tick )>;
+		
+		fn required(ids: &mut Vec<S::Id>) {
+			ids.push( C::id() );
+		}
+		
+		fn accessed(ids: &mut Vec<S::Id>) {
+			ids.push( C::id() );
+		}
+		
+		fn written(ids: &mut Vec<S::Id>) {
+			ids.push( C::id() );
+		}
+		
+		fn offer(state: &mut Self::State, component: QueryComponent<'a, 'b, S>) -> Option< QueryComponent<'a, 'b, S> >
+		{
+			if state.is_some() || *component.id != C::id() {
+				return Some(component);
+			}
+			
+			// Note: Shared components can't be written, so we can't take them
+			let (storage, ticks) = match component.storage {
+				ComponentRef::Unique(storage, ticks) => (storage, ticks),
+				storage @ ComponentRef::Shared(..) => return Some( QueryComponent { storage, ..component } ),
+			};
+			
+			let change_tick = component.change_tick;
+			*state = C::get_mut(storage).map(|storage| (storage, ticks, change_tick));
+			None
+		}
+		
+		fn matches(state: &Self::State) -> bool {
+			state.is_some()
+		}
+		
+		fn finish(state: Self::State) -> Option<Self>
+		{
+			// Mark it as changed, as we're borrowing it mutably
+			let (storage, ticks, change_tick) = state?;
+			ticks.changed = change_tick;
+			Some(storage)
+		}
+	}
+	
+	impl<'a, 'b, S, Q> Query<'a, 'b, S> for Option<Q>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		Q    : Query<'a, 'b, S>,
+	{
+		type State = Q::State;
+		
+		fn required(_ids: &mut Vec<S::Id>) {}
+		
+		fn accessed(ids: &mut Vec<S::Id>) {
+			Q::accessed(ids);
+		}
+		
+		fn written(ids: &mut Vec<S::Id>) {
+			Q::written(ids);
+		}
+		
+		fn check(state: &mut Self::State, component: &QueryComponent<'a, 'b, S>) {
+			Q::check(state, component);
+		}
+		
+		fn offer(state: &mut Self::State, component: QueryComponent<'a, 'b, S>) -> Option< QueryComponent<'a, 'b, S> > {
+			Q::offer(state, component)
+		}
+		
+		fn matches(_state: &Self::State) -> bool {
+			true
+		}
+		
+		fn finish(state: Self::State) -> Option<Self> {
+			Some( Q::finish(state) )
+		}
+	}
+	
+	/// Implements `Query` for a filter on the ticks of a component
+	macro_rules! impl_ticks_query
+	{
+		( $Filter:ident, $is_match:ident ) => {
+			impl<'a, 'b, S, C> Query<'a, 'b, S> for $Filter<C>
+			where
+				S    : Storage<'a>,
+				S::Id: KeyType,
+				C    : Component<'a, S>,
+			{
+				/// If the component was checked, and whether it matched
+				type State = Option<bool>;
+				
+				fn required(ids: &mut Vec<S::Id>) {
+					ids.push( C::id() );
+				}
+				
+				fn accessed(_ids: &mut Vec<S::Id>) {}
+				
+				fn written(_ids: &mut Vec<S::Id>) {}
+				
+				fn check(state: &mut Self::State, component: &QueryComponent<'a, 'b, S>)
+				{
+					if *component.id == C::id() {
+						*state = Some( component.ticks().$is_match(component.since) );
+					}
+				}
+				
+				fn offer(_state: &mut Self::State, component: QueryComponent<'a, 'b, S>) -> Option< QueryComponent<'a, 'b, S> > {
+					Some(component)
+				}
+				
+				fn matches(state: &Self::State) -> bool {
+					state.unwrap_or(false)
+				}
+				
+				fn finish(state: Self::State) -> Option<Self> {
+					state.unwrap_or(false).then(|| $Filter(PhantomData))
+				}
+			}
+		}
+	}
+	
+	impl_ticks_query!(Changed, is_changed);
+	impl_ticks_query!(Added  , is_added  );
+	
+	/// Implements `Query` for a tuple of queries
+	macro_rules! impl_tuple_query
+	{
+		( $( $Q:ident $idx:tt ),* ) => {
+			impl<'a, 'b, S, $( $Q ),*> Query<'a, 'b, S> for ( $( $Q, )* )
+			where
+				S    : Storage<'a>,
+				S::Id: KeyType,
+				$( $Q: Query<'a, 'b, S>, )*
+			{
+				type State = ( $( $Q::State, )* );
+				
+				fn required(ids: &mut Vec<S::Id>) {
+					$( $Q::required(ids); )*
+				}
+				
+				fn accessed(ids: &mut Vec<S::Id>) {
+					$( $Q::accessed(ids); )*
+				}
+				
+				fn written(ids: &mut Vec<S::Id>) {
+					$( $Q::written(ids); )*
+				}
+				
+				fn check(state: &mut Self::State, component: &QueryComponent<'a, 'b, S>) {
+					$( $Q::check(&mut state.$idx, component); )*
+				}
+				
+				fn offer(state: &mut Self::State, component: QueryComponent<'a, 'b, S>) -> Option< QueryComponent<'a, 'b, S> >
+				{
+					// Offer it to each element until one takes it
+					$( let component = $Q::offer(&mut state.$idx, component)?; )*
+					Some(component)
+				}
+				
+				fn matches(state: &Self::State) -> bool {
+					$( $Q::matches(&state.$idx) )&&*
+				}
+				
+				fn finish(state: Self::State) -> Option<Self> {
+					Some( ( $( $Q::finish(state.$idx)?, )* ) )
+				}
+			}
+		}
+	}
+	
+	impl_tuple_query!(A 0);
+	impl_tuple_query!(A 0, B 1);
+	impl_tuple_query!(A 0, B 1, C 2);
+	impl_tuple_query!(A 0, B 1, C 2, D 3);
+	impl_tuple_query!(A 0, B 1, C 2, D 3, E 4);
+	impl_tuple_query!(A 0, B 1, C 2, D 3, E 4, F 5);
+	impl_tuple_query!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+	impl_tuple_query!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Fetches a query from the components of an entity
+	pub(in super) fn fetch<'a, 'b, S, Q, I>(components: I) -> Option<Q>
+	where
+		S    : Storage<'a> + 'b,
+		S::Id: KeyType + 'b,
+		Q    : Query<'a, 'b, S>,
+		I    : Iterator<Item = QueryComponent<'a, 'b, S>>,
+	{
+		let mut state = Q::State::default();
+		
+		// Check and then offer all components to the query
+		for component in components {
+			Q::check(&mut state, &component);
+			
+			// Note: We don't care if it wasn't taken
+			let _ = Q::offer(&mut state, component);
+		}
+		
+		// And finish it, if it matched
+		if !Q::matches(&state) {
+			return None;
+		}
+		Q::finish(state)
+	}
+	
+	/// Checks that a query doesn't access the same component more than once
+	/// 
+	/// # Panics
+	/// Panics if any component is accessed more than once.
+	pub(in super) fn check_access<'a, 'b, S, Q>()
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		Q    : Query<'a, 'b, S>,
+	{
+		let mut accessed = vec![];
+		Q::accessed(&mut accessed);
+		
+		let mut ids = HashSet::with_capacity( accessed.len() );
+		for id in &accessed {
+			assert!(ids.insert(id), "Query accessed the same component more than once");
+		}
+	}
+//--------------------------------------------------------------------------------------------------
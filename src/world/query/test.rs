@@ -0,0 +1,137 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{World, Changed, Added};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type
+		#[derive(PartialEq, Clone, Debug)]
+		enum Components {
+			A(i32),
+			B(&'static str),
+			C(f32),
+		}
+	
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn tuple_query()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1), Components::B("1"), Components::C(1.5) ] );
+		let id2 = world.add( mecs::entity![ Components::A(2), Components::B("2") ] );
+		let id3 = world.add( mecs::entity![ Components::A(3) ] );
+		
+		let mut items: Vec<_> = world.query::<(&&str, &mut i32, Option<&f32>)>()
+			.map(|(id, (name, num, scale))| {
+				*num *= 10;
+				(id, *name, scale.copied())
+			})
+			.collect();
+		items.sort_by_key(|&(id, ..)| id.idx());
+		
+		assert_eq!(items, [ (id1, "1", Some(1.5)), (id2, "2", None) ]);
+		assert_eq!(world[id1].get::<i32>(), Some(&10));
+		assert_eq!(world[id2].get::<i32>(), Some(&20));
+		assert_eq!(world[id3].get::<i32>(), Some(&3));
+	}
+	
+	#[test]
+	fn query_tracks_entities()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		assert_eq!(world.query::<&i32>().count(), 1);
+		
+		// The cached predicate of the query should be kept up to date
+		let id2 = world.add( mecs::entity![ Components::A(2) ] );
+		world.remove(id1);
+		let ids: Vec<_> = world.query::<&i32>().map(|(id, _)| id).collect();
+		assert_eq!(ids, [id2]);
+		
+		world.remove_component::<i32>(id2);
+		assert_eq!(world.query::<&i32>().count(), 0);
+	}
+	
+	#[test]
+	fn query_order_shares_predicate()
+	{
+		let mut world: World<Components> = World::new();
+		world.add( mecs::entity![ Components::A(1), Components::B("1") ] );
+		
+		assert_eq!(world.query::<(&i32, &&str)>().count(), 1);
+		assert_eq!(world.query::<(&&str, &i32, Option<&f32>)>().count(), 1);
+		assert_eq!(world.queries.len(), 1);
+	}
+	
+	#[test]
+	fn changed_added()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1), Components::B("1") ] );
+		let id2 = world.add( mecs::entity![ Components::A(2) ] );
+		
+		// Components added in the current tick are both added and changed
+		assert_eq!(world.query::<Added<i32>>().count(), 2);
+		
+		let last_tick = world.change_tick();
+		world.increment_change_tick();
+		assert_eq!(world.query::<Changed<i32>>().count(), 0);
+		
+		// Writing a component through a query marks it as changed, even if it's checked by a later element
+		for (_, (num, _)) in world.query::<(&mut i32, Option<Changed<i32>>)>() {
+			if *num == 2 {
+				*num = 3;
+			}
+		}
+		let ids: Vec<_> = world.query::<(&i32, Changed<i32>)>().map(|(id, _)| id).collect();
+		assert_eq!(ids.len(), 2);
+		
+		world.increment_change_tick();
+		*world.get_mut(id2).unwrap().get_mut::<i32>().unwrap() += 1;
+		let ids: Vec<_> = world.query::<(&&str, Changed<i32>)>().map(|(id, _)| id).collect();
+		assert!(ids.is_empty());
+		let ids: Vec<_> = world.query::<Changed<i32>>().map(|(id, _)| id).collect();
+		assert_eq!(ids, [id2]);
+		assert_eq!(world.query_since::<Changed<i32>>(last_tick).count(), 2);
+		assert_eq!(world.query_since::<Added<&str>>(last_tick).count(), 0);
+		assert_eq!(world[id1].get::<&str>(), Some(&"1"));
+	}
+	
+	#[test]
+	fn unmatched_rows_unchanged()
+	{
+		let mut world: World<Components> = World::new();
+		
+		let id = world.add( mecs::entity![ Components::A(1), Components::C(1.0) ] );
+		let last_tick = world.change_tick();
+		world.increment_change_tick();
+		
+		// Rows rejected by a later element shouldn't mark earlier ones as changed
+		assert_eq!(world.query_since::<(&mut i32, Changed<f32>)>(last_tick).count(), 0);
+		assert!(!world[id].changed::<i32>(last_tick));
+		
+		assert_eq!(world.query_since::<(&mut i32, Option<Changed<f32>>)>(last_tick).count(), 1);
+		assert!(world[id].changed::<i32>(last_tick));
+	}
+	
+	#[test]
+	#[should_panic(expected = "Query accessed the same component more than once")]
+	fn duplicate_access()
+	{
+		let mut world: World<Components> = World::new();
+		world.add( mecs::entity![ Components::A(1) ] );
+		
+		let _ = world.query::<(&i32, &mut i32)>().count();
+	}
+//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,149 @@
+//! Resources
+//! 
+//! Resources are singleton values stored in a world outside of
+//! any entity, such as the delta time, a random number generator
+//! or the input state. Each resource is identified by it's type,
+//! so a world holds at most one resource of each type.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{World, DynStorage};
+//! /// Time since the last tick
+//! struct DeltaTime(f32);
+//! 
+//! let mut world = World::new();
+//! world.insert_resource( DeltaTime(0.5) );
+//! let id = world.add( mecs::entity![ DynStorage::new(1.0f32) ] );
+//! let pred_id = world.add_pred(|entity| entity.has::<f32>());
+//! 
+//! let mut iter = world.iter_pred_mut(pred_id).unwrap();
+//! let dt = iter.resource::<DeltaTime>().unwrap().0;
+//! iter.for_each(|mut entity, _| *entity.get_mut::<f32>().unwrap() += dt);
+//! 
+//! assert_eq!(world[id].get::<f32>(), Some(&1.5));
+//! ```
+
+// Modules
+#[cfg(test)]
+mod test;
+
+// Collections
+use std::collections::HashMap;
+
+// Any
+use std::any::{Any, TypeId};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// Collection of resources, stored by their type
+	#[derive(Default)]
+	pub struct Resources {
+		/// All resources, stored by their type id
+		resources: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl Resources
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty collection of resources
+			#[must_use]
+			pub fn new() -> Self {
+				Self::default()
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Insert / Remove
+		//--------------------------------------------------------------------------------------------------
+			/// Inserts a resource
+			/// 
+			/// # Return value
+			/// If a resource of the same type already existed, it is replaced and returned.
+			pub fn insert<R>(&mut self, resource: R) -> Option<R>
+			where
+				R: Any + Send + Sync
+			{
+				self.resources
+					.insert( TypeId::of::<R>(), Box::new(resource) )
+					.map(Self::downcast)
+			}
+			
+			/// Removes a resource given it's type
+			pub fn remove<R>(&mut self) -> Option<R>
+			where
+				R: Any + Send + Sync
+			{
+				self.resources
+					.remove( &TypeId::of::<R>() )
+					.map(Self::downcast)
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Access
+		//--------------------------------------------------------------------------------------------------
+			/// Returns a reference to a resource given it's type
+			#[must_use]
+			pub fn get<R>(&self) -> Option<&R>
+			where
+				R: Any + Send + Sync
+			{
+				self.resources
+					.get( &TypeId::of::<R>() )
+					.and_then(|resource| resource.downcast_ref())
+			}
+			
+			/// Returns a mutable reference to a resource given it's type
+			pub fn get_mut<R>(&mut self) -> Option<&mut R>
+			where
+				R: Any + Send + Sync
+			{
+				self.resources
+					.get_mut( &TypeId::of::<R>() )
+					.and_then(|resource| resource.downcast_mut())
+			}
+			
+			/// Checks if a resource of a type exists
+			#[must_use]
+			pub fn contains<R>(&self) -> bool
+			where
+				R: Any + Send + Sync
+			{
+				self.resources.contains_key( &TypeId::of::<R>() )
+			}
+			
+			/// Returns the number of resources
+			#[must_use]
+			pub fn len(&self) -> usize {
+				self.resources.len()
+			}
+			
+			/// Checks if there are no resources
+			#[must_use]
+			pub fn is_empty(&self) -> bool {
+				self.resources.is_empty()
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Helpers
+		//--------------------------------------------------------------------------------------------------
+			/// Moves a resource out of it's box
+			#[allow(clippy::missing_panics_doc)] // Resources are always stored by their own type id
+			fn downcast<R: Any>(resource: Box<dyn Any + Send + Sync>) -> R {
+				*resource.downcast().unwrap_or_else(|_| panic!("Resource was stored with the wrong type id"))
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+	
+	impl std::fmt::Debug for Resources
+	{
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			f.debug_struct("Resources")
+				.field("len", &self.resources.len())
+				.finish()
+		}
+	}
+//--------------------------------------------------------------------------------------------------
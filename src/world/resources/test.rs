@@ -0,0 +1,93 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{World, Entity, Access, Resources};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type
+		#[derive(PartialEq, Clone, Debug)]
+		enum Components {
+			A(i32),
+			B(&'static str),
+			C(f32),
+		}
+	
+	}
+	
+	/// Dummy resource
+	#[derive(PartialEq, Debug)]
+	struct Counter(usize);
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn insert_remove()
+	{
+		let mut resources = Resources::new();
+		assert!(resources.is_empty());
+		
+		assert_eq!(resources.insert( Counter(1) ), None);
+		assert_eq!(resources.insert( 5i32 ), None);
+		assert_eq!(resources.insert( Counter(2) ), Some( Counter(1) ));
+		assert_eq!(resources.len(), 2);
+		
+		assert!(resources.contains::<Counter>());
+		assert!(!resources.contains::<u32>());
+		assert_eq!(resources.get::<i32>(), Some(&5));
+		
+		resources.get_mut::<Counter>().unwrap().0 += 1;
+		assert_eq!(resources.remove::<Counter>(), Some( Counter(3) ));
+		assert_eq!(resources.remove::<Counter>(), None);
+		assert_eq!(resources.len(), 1);
+	}
+	
+	#[test]
+	fn pred_iter()
+	{
+		let mut world: World<Components> = World::new();
+		world.insert_resource( Counter(0) );
+		let pred_id = world.add_pred(Entity::has::<i32>);
+		
+		world.add( mecs::entity![ Components::A(1) ] );
+		world.add( mecs::entity![ Components::A(2) ] );
+		world.add( mecs::entity![ Components::B("3") ] );
+		
+		let mut iter = world.iter_pred_mut(pred_id).unwrap();
+		while iter.next().is_some() {
+			iter.resource_mut::<Counter>().unwrap().0 += 1;
+		}
+		drop(iter);
+		
+		assert_eq!(world.resource::<Counter>(), Some(&Counter(2)));
+	}
+	
+	#[test]
+	fn views()
+	{
+		let mut world: World<Components> = World::new();
+		world.insert_resource( Counter(5) );
+		let id = world.add( mecs::entity![ Components::A(1), Components::C(2.0) ] );
+		
+		let nums   = Access::new().write::<i32>();
+		let scales = Access::new().write::<f32>();
+		let mut views = world.views(&[&nums, &scales]);
+		for view in &mut views {
+			assert_eq!(view.resource::<Counter>(), Some(&Counter(5)));
+		}
+		
+		// Resources borrow from the world, not the view, so they may be used while querying
+		let counter = views[0].resource::<Counter>().unwrap();
+		for (_, num) in views[0].query::<&mut i32>() {
+			*num += 1;
+			assert_eq!(counter, &Counter(5));
+		}
+		drop(views);
+		
+		assert_eq!(world[id].get::<i32>(), Some(&2));
+	}
+//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,428 @@
+//! A sparse set based backend
+//! 
+//! This module provides the [`SparseSets`] backend, an alternative to
+//! [`Entities`](super::Entities) which stores every component id in it's own
+//! [`SparseSet`], a dense array of components along with a sparse index
+//! keyed by entity id.
+//! 
+//! Unlike [`Archetypes`](super::archetype::Archetypes), adding or removing
+//! a component from an existing entity doesn't move any of it's other
+//! components, so it is better suited for workloads where components are
+//! frequently added and removed.
+//! 
+//! As with any backend, the world's api stays the same, so switching is a matter of
+//! replacing `World<Components>` with [`SparseWorld<Components>`](SparseWorld).
+//! 
+//! # Examples
+//! 
+//! ```rust
+//! use mecs::{SparseWorld, DynStorage};
+//! 
+//! let mut world: SparseWorld<DynStorage> = SparseWorld::default();
+//! 
+//! let pred_id = world.add_pred(|entity| entity.has::<i32>() && entity.has::<&str>());
+//! 
+//! let id = world.add( mecs::entity![ DynStorage::new(1i32) ] );
+//! world.add( mecs::entity![ DynStorage::new(2i32), DynStorage::new("world") ] );
+//! 
+//! world.insert_component(id, DynStorage::new("hello")).unwrap();
+//! 
+//! let iter = world.iter_pred(pred_id).unwrap();
+//! for (entity, id) in iter {
+//! 	let num : &i32  = entity.get().unwrap();
+//! 	let name: &&str = entity.get().unwrap();
+//! 
+//! 	println!("{}: {}", num, name);
+//! }
+//! ```
+//! 
+//! Prints:
+//! 
+//! ```text
+//! 2: world
+//! 1: hello
+//! ```
+
+// Modules
+#[cfg(test)]
+    mod test;
+    mod set;
+    mod view;
+    mod fetch;
+
+// Exports
+pub use set  ::SparseSet;
+pub use view ::{EntityRef, EntityMut};
+pub use fetch::{SparseFetch, SparseView};
+
+// Collections
+use std::collections::HashMap;
+
+// Traits
+use std::iter::Iterator;
+
+// Crate
+use crate::{util::KeyType, Storage, Entity, EntityId, Tick, ComponentTicks, Access, component::ComponentMask};
+use super::{backend, World, Backend, BackendRefs, BackendMut, PredicateIds, query::ComponentRef};
+use view::ComponentsMut;
+use fetch::SetRef;
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A world whose components are stored in sparse sets
+	/// 
+	/// This is a [`World`] using the [`SparseSets`] backend, see the module docs.
+	#[allow(clippy::module_name_repetitions)]
+	pub type SparseWorld<'a, S> = World<'a, S, SparseSets<'a, S>>;
+	
+	/// Backend storing each component id in it's own sparse set
+	/// 
+	/// Entities only keep track of which components they have, so they're
+	/// borrowed as an [`EntityRef`] or [`EntityMut`] instead of an [`Entity`].
+	pub struct SparseSets<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The component ids of every entity
+		entities: SparseSet< ComponentIds<'a, S> >,
+		
+		/// The components of each component id, along with their ticks
+		sets: HashMap< S::Id, SparseSet<(S, ComponentTicks)> >,
+	}
+	
+	/// The component ids of an entity
+	struct ComponentIds<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// All of the component ids
+		ids: Vec<S::Id>,
+		
+		/// The mask of all component ids
+		mask: ComponentMask,
+	}
+	
+	/// A component split off from it's set, along with it's id and ticks
+	type ComponentMut<'a, 'w, S> = (&'w <S as Storage<'a>>::Id, &'w mut S, &'w mut ComponentTicks);
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S> SparseSets<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		/// Returns the set of all components of a given id, along with their ticks
+		#[must_use]
+		pub fn set(&self, id: &S::Id) -> Option<&SparseSet<(S, ComponentTicks)>> {
+			self.sets.get(id)
+		}
+		
+		/// Mutably borrows the components of a set of entities at once
+		/// 
+		/// Only the sets of the entities' components are visited.
+		/// 
+		/// # Panics
+		/// Panics if any of the entities doesn't exist or appears more than once.
+		fn components_mut(&mut self, ids: &[EntityId]) -> Vec<( EntityId, Vec<ComponentMut<'a, '_, S>> )>
+		{
+			let (rows, by_set) = group(&self.entities, ids);
+			let mut components: Vec<_> = ids.iter().map(|&id| (id, vec![])).collect();
+			
+			// Split each of the entities' sets between them
+			for (set_id, set) in &mut self.sets
+			{
+				let Some(entities) = by_set.get(set_id) else {
+					continue;
+				};
+				
+				for (id, (storage, ticks)) in set.get_many_mut(entities) {
+					components[rows[&id]].1.push( (set_id, storage, ticks) );
+				}
+			}
+			
+			components
+		}
+	}
+	
+	impl<'a, S> World<'a, S, SparseSets<'a, S>>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		/// Returns the set of all components of a given id, along with their ticks
+		/// 
+		/// # Example
+		/// 
+		/// ```rust
+		/// # use mecs::{SparseWorld, DynStorage, Component};
+		/// let mut world = SparseWorld::default();
+		/// 
+		/// world.add( mecs::entity![ DynStorage::new(1i32), DynStorage::new("1") ] );
+		/// world.add( mecs::entity![ DynStorage::new(2i32) ] );
+		/// 
+		/// assert_eq!(world.set( &<i32 as Component<DynStorage>>::id() ).map(|set| set.len()), Some(2));
+		/// ```
+		#[must_use]
+		pub fn set(&self, id: &S::Id) -> Option<&SparseSet<(S, ComponentTicks)>> {
+			self.entities.set(id)
+		}
+	}
+	
+	impl<'a, S> Backend<'a, S> for SparseSets<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		type Fetch<'w> = SparseFetch<'a, 'w, S> where Self: 'w;
+		type Cursor = usize;
+		
+		fn insert(&mut self, id: EntityId, entity: Entity<'a, S>)
+		{
+			// Move each component into it's set
+			let mut component_ids = ComponentIds {
+				ids: vec![],
+				mask: ComponentMask::with_capacity( S::COUNT.unwrap_or(0) ),
+			};
+			for (storage, ticks) in entity.into_components_ticks() {
+				component_ids.insert( storage.id() );
+				self.sets.entry( storage.id() )
+					.or_insert_with(SparseSet::new)
+					.insert(id, (storage, ticks));
+			}
+			
+			self.entities.insert(id, component_ids);
+		}
+		
+		fn remove(&mut self, id: EntityId) -> Option< Entity<'a, S> >
+		{
+			// Remove the entity's components from just their sets
+			let component_ids = self.entities.remove(id)?;
+			let components = component_ids.ids.iter()
+				.filter_map(|component_id| self.sets.get_mut(component_id)?.remove(id));
+			
+			Some( Entity::from_components_ticks(components) )
+		}
+		
+		fn insert_component(&mut self, id: EntityId, storage: S, change_tick: Tick) -> Result<Option<S>, S>
+		{
+			let Some(component_ids) = self.entities.get_mut(id) else {
+				return Err(storage);
+			};
+			
+			let component_id = storage.id();
+			let replaced = self.sets.entry( storage.id() )
+				.or_insert_with(SparseSet::new)
+				.insert(id, (storage, ComponentTicks::new(change_tick)))
+				.map(|(storage, _)| storage);
+			if replaced.is_none() {
+				component_ids.insert(component_id);
+			}
+			
+			Ok(replaced)
+		}
+		
+		fn remove_component(&mut self, id: EntityId, component_id: &S::Id) -> Option<S>
+		{
+			let component_ids = self.entities.get_mut(id)?;
+			let (storage, _) = self.sets.get_mut(component_id)?.remove(id)?;
+			component_ids.remove(component_id);
+			
+			Some(storage)
+		}
+		
+		fn get(&self, id: EntityId) -> Option< EntityRef<'a, '_, S> >
+		{
+			let component_ids = self.entities.get(id)?;
+			
+			Some( EntityRef{ sets: &self.sets, component_ids, id } )
+		}
+		
+		fn get_mut(&mut self, id: EntityId, change_tick: Tick) -> Option< EntityMut<'a, '_, S> >
+		{
+			if !self.entities.contains(id) {
+				return None;
+			}
+			
+			Some( EntityMut{ components: ComponentsMut::Sets(&mut self.sets), id, change_tick } )
+		}
+		
+		fn next_pred(&self, pred: &PredicateIds<'a, S, Self>, cursor: &mut usize) -> Option<( EntityId, EntityRef<'a, '_, S> )>
+		{
+			let id = backend::next_pred_id(self, pred, cursor)?;
+			self.get(id).map(|entity| (id, entity))
+		}
+		
+		fn next_pred_mut(&mut self, pred: &PredicateIds<'a, S, Self>, cursor: &mut usize, change_tick: Tick) -> Option<( EntityId, EntityMut<'a, '_, S> )>
+		{
+			let id = backend::next_pred_id(self, pred, cursor)?;
+			self.get_mut(id, change_tick).map(|entity| (id, entity))
+		}
+		
+		fn iter(&self) -> impl Iterator<Item = ( EntityId, EntityRef<'a, '_, S> )> {
+			let sets = &self.sets;
+			self.entities.entities().iter()
+				.zip( self.entities.values() )
+				.map(move |(&id, component_ids)| (id, EntityRef{ sets, component_ids, id }))
+		}
+		
+		fn into_entities(mut self) -> Vec<( EntityId, Entity<'a, S> )> {
+			let sets = &mut self.sets;
+			self.entities.entities().iter()
+				.zip( self.entities.values() )
+				.map(|(&id, component_ids)| {
+					let components = component_ids.ids.iter()
+						.filter_map(|component_id| sets.get_mut(component_id)?.remove(id));
+					(id, Entity::from_components_ticks(components))
+				})
+				.collect()
+		}
+		
+		fn par_for_each<F>(&mut self, ids: &[EntityId], chunk_size: usize, change_tick: Tick, f: &F)
+		where
+			S    : Send,
+			S::Id: Send,
+			F    : Fn(BackendMut<'a, '_, S, Self>, EntityId) + Sync,
+		{
+			let mut entities = self.components_mut(ids).into_iter()
+				.map(|(id, components)| {
+					let components = components.into_iter().map(|(_, storage, ticks)| (storage, ticks)).collect();
+					(id, components)
+				});
+			
+			let chunks = std::iter::from_fn(|| Some( entities.by_ref().take(chunk_size).collect::<Vec<_>>() ).filter(|chunk| !chunk.is_empty()));
+			
+			// Note: Every component was borrowed separately, so each chunk may be sent to a different thread
+			backend::par_chunks(chunks, |chunk| for (id, components) in chunk {
+				f(EntityMut{ components: ComponentsMut::Split(components), id, change_tick }, id);
+			});
+		}
+		
+		fn fetch<'w>(&'w mut self, _required: &[S::Id], ids: &[EntityId], change_tick: Tick, since: Tick) -> Self::Fetch<'w>
+		{
+			let rows: Vec<_> = self.components_mut(ids).into_iter()
+				.map(|(id, components)| {
+					let components = components.into_iter()
+						.map(|(component_id, storage, ticks)| (component_id, ComponentRef::Unique(storage, ticks)))
+						.collect();
+					(id, components)
+				})
+				.collect();
+			
+			SparseFetch {
+				rows: rows.into_iter(),
+				change_tick,
+				since,
+			}
+		}
+		
+		fn views(&mut self, accesses: &[&Access<'a, S>]) -> Vec< SparseView<'a, '_, S> >
+		{
+			let Self { entities, sets } = self;
+			let entities = &*entities;
+			let mut views: Vec<_> = accesses.iter()
+				.map(|_| SparseView { entities, sets: HashMap::new() })
+				.collect();
+			
+			// Give each set to the view that writes it, or else to all views that read it
+			for (id, set) in sets {
+				if let Some(writer) = accesses.iter().position(|access| access.writes_id(id)) {
+					views[writer].sets.insert(id, SetRef::Unique(set));
+					continue;
+				}
+				
+				let set = &*set;
+				for (view, access) in views.iter_mut().zip(accesses) {
+					if access.reads_id(id) {
+						view.sets.insert(id, SetRef::Shared(set));
+					}
+				}
+			}
+			
+			views
+		}
+	}
+	
+	impl<'a, 'w, S> BackendRefs<'a, 'w, S> for SparseSets<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		type Ref = EntityRef<'a, 'w, S>;
+		type Mut = EntityMut<'a, 'w, S>;
+		type View = SparseView<'a, 'w, S>;
+	}
+	
+	impl<'a, S> ComponentIds<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// Adds a component id
+		fn insert(&mut self, id: S::Id)
+		{
+			if let Some(index) = S::index(&id) {
+				self.mask.insert(index);
+			}
+			
+			self.ids.push(id);
+		}
+		
+		/// Removes a component id
+		fn remove(&mut self, id: &S::Id)
+		{
+			if let Some(index) = S::index(id) {
+				self.mask.remove(index);
+			}
+			
+			self.ids.retain(|other_id| other_id != id);
+		}
+	}
+	
+	impl<'a, S> Default for SparseSets<'a, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		fn default() -> Self {
+			Self {
+				entities: SparseSet::new(),
+				sets: HashMap::new(),
+			}
+		}
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	/// Groups a set of entities by each of their component ids
+	/// 
+	/// # Return value
+	/// Returns the position of each entity within `ids`, along with the entities of each component id.
+	/// 
+	/// # Panics
+	/// Panics if any of the entities doesn't exist.
+	#[allow(clippy::type_complexity)] // It's just two maps
+	fn group<'a, 'w, S>(entities: &'w SparseSet< ComponentIds<'a, S> >, ids: &[EntityId]) -> ( HashMap<EntityId, usize>, HashMap<&'w S::Id, Vec<EntityId>> )
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		let mut rows = HashMap::with_capacity( ids.len() );
+		let mut by_set: HashMap<&S::Id, Vec<EntityId>> = HashMap::new();
+		for (row, &id) in ids.iter().enumerate()
+		{
+			let component_ids = entities.get(id).expect("Entity didn't exist");
+			for component_id in &component_ids.ids {
+				by_set.entry(component_id).or_default().push(id);
+			}
+			
+			rows.insert(id, row);
+		}
+		
+		(rows, by_set)
+	}
+//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,124 @@
+//! Queries over sparse sets
+
+// Collections
+use std::collections::HashMap;
+
+// Crate
+use crate::{util::KeyType, Storage, EntityId, Tick, ComponentTicks};
+use crate::world::{Fetch, View, Query, query::{self, QueryComponent, ComponentRef}};
+use super::{SparseSet, ComponentIds};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A view over the sets of [`SparseSets`](super::SparseSets)
+	pub struct SparseView<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The component ids of every entity
+		pub(in super) entities: &'w SparseSet< ComponentIds<'a, S> >,
+		
+		/// The sets in this view, stored by their component id
+		pub(in super) sets: HashMap< &'w S::Id, SetRef<'w, S> >,
+	}
+	
+	/// The entities of a query over [`SparseSets`](super::SparseSets)
+	pub struct SparseFetch<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The components left to fetch of each entity, split off from their sets, along with their ids
+		#[allow(clippy::type_complexity)] // It's just a list of components per entity
+		pub(in super) rows: std::vec::IntoIter<( EntityId, Vec<( &'w S::Id, ComponentRef<'w, S> )> )>,
+		
+		/// The tick to mark components with if they're changed
+		pub(in super) change_tick: Tick,
+		
+		/// The tick to check if components were added or changed since
+		pub(in super) since: Tick,
+	}
+	
+	/// A set of components, along with their ticks
+	pub(in super) enum SetRef<'w, S>
+	{
+		/// The set may only be read
+		Shared(&'w SparseSet<(S, ComponentTicks)>),
+		
+		/// The set may be read and written
+		Unique(&'w mut SparseSet<(S, ComponentTicks)>),
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, 'w, S> View<'a, 'w, S> for SparseView<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		type Fetch<'b> = SparseFetch<'a, 'b, S> where Self: 'b;
+		
+		fn fetch(&mut self, ids: Option<&[EntityId]>, change_tick: Tick, since: Tick) -> SparseFetch<'a, '_, S>
+		{
+			let ids = ids.unwrap_or_else(|| self.entities.entities());
+			let (rows, by_set) = super::group(self.entities, ids);
+			let mut components: Vec<_> = ids.iter().map(|&id| (id, vec![])).collect();
+			
+			// Split each of our sets between the entities with it
+			for (&set_id, set) in &mut self.sets
+			{
+				let Some(entities) = by_set.get(set_id) else {
+					continue;
+				};
+				
+				match set {
+					SetRef::Shared(set) => for &id in entities {
+						let (storage, ticks) = set.get(id).expect("Entity had no value");
+						components[rows[&id]].1.push( (set_id, ComponentRef::Shared(storage, ticks)) );
+					},
+					SetRef::Unique(set) => for (id, (storage, ticks)) in set.get_many_mut(entities) {
+						components[rows[&id]].1.push( (set_id, ComponentRef::Unique(storage, ticks)) );
+					},
+				}
+			}
+			
+			// Note: Entities without any of our sets aren't in this view
+			components.retain(|(_, components)| !components.is_empty());
+			
+			SparseFetch {
+				rows: components.into_iter(),
+				change_tick,
+				since,
+			}
+		}
+	}
+	
+	impl<'a, 'w, S> Fetch<'a, 'w, S> for SparseFetch<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+	{
+		fn next<Q: Query<'a, 'w, S>>(&mut self) -> Option<(EntityId, Q)>
+		{
+			let (change_tick, since) = (self.change_tick, self.since);
+			for (id, components) in self.rows.by_ref()
+			{
+				let components = components.into_iter()
+					.map(|(component_id, storage)| QueryComponent::new(component_id, storage, change_tick, since));
+				
+				if let Some(query) = query::fetch(components) {
+					return Some( (id, query) );
+				}
+			}
+			
+			// If we get here, return None
+			None
+		}
+		
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(0, Some( self.rows.len() ))
+		}
+	}
+//--------------------------------------------------------------------------------------------------
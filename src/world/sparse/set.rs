@@ -0,0 +1,180 @@
+//! Sparse sets keyed by entity id
+
+// Crate
+use crate::EntityId;
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A sparse set of values keyed by entity id
+	/// 
+	/// All values are stored contiguously in a dense array,
+	/// along with the id of the entity owning each of them, while
+	/// a sparse array indexed by entity id points into the dense array.
+	#[derive(Debug)]
+	pub struct SparseSet<T>
+	{
+		/// Index into `dense` for each entity id
+		sparse: Vec< Option<usize> >,
+		
+		/// All of the values
+		dense: Vec<T>,
+		
+		/// The entity id owning each value in `dense`
+		entities: Vec<EntityId>,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<T> SparseSet<T>
+	{
+		// Constructors
+		//--------------------------------------------------------------------------------------------------
+			/// Creates an empty sparse set
+			#[must_use]
+			pub(in crate::world) const fn new() -> Self {
+				Self {
+					sparse: vec![],
+					dense: vec![],
+					entities: vec![],
+				}
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Add / Remove
+		//--------------------------------------------------------------------------------------------------
+			/// Inserts a value for an entity
+			/// 
+			/// # Return
+			/// If the entity already had a value, it is replaced and returned.
+			#[allow(clippy::arithmetic_side_effects)] // We need to add one to get the length
+			pub(in crate::world) fn insert(&mut self, id: EntityId, value: T) -> Option<T>
+			{
+				// If we already have it, replace it
+				if let Some(value_ref) = self.get_mut(id) {
+					return Some( std::mem::replace(value_ref, value) );
+				}
+				
+				// Else make sure the sparse array is big enough
+				if self.sparse.len() <= id.idx() {
+					self.sparse.resize(id.idx() + 1, None);
+				}
+				
+				// And push it onto the dense array
+				self.sparse[id.idx()] = Some( self.dense.len() );
+				self.dense.push(value);
+				self.entities.push(id);
+				
+				None
+			}
+			
+			/// Removes the value of an entity
+			pub(in crate::world) fn remove(&mut self, id: EntityId) -> Option<T>
+			{
+				let dense_idx = self.dense_idx(id)?;
+				self.sparse[id.idx()] = None;
+				
+				// Swap remove it and fix the index of the value that took it's place
+				self.entities.swap_remove(dense_idx);
+				if let Some(&moved) = self.entities.get(dense_idx) {
+					self.sparse[moved.idx()] = Some(dense_idx);
+				}
+				
+				Some( self.dense.swap_remove(dense_idx) )
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Access
+		//--------------------------------------------------------------------------------------------------
+			/// Returns a reference to the value of an entity
+			#[must_use]
+			pub fn get(&self, id: EntityId) -> Option<&T> {
+				self.dense_idx(id)
+					.map(|idx| &self.dense[idx])
+			}
+			
+			/// Returns a mutable reference to the value of an entity
+			#[must_use]
+			pub fn get_mut(&mut self, id: EntityId) -> Option<&mut T> {
+				match self.dense_idx(id) {
+					Some(idx) => Some( &mut self.dense[idx] ),
+					None      => None,
+				}
+			}
+			
+			/// Mutably borrows the values of a set of entities at once, along with their ids
+			/// 
+			/// # Panics
+			/// Panics if any of the entities doesn't have a value or appears more than once.
+			#[allow(clippy::arithmetic_side_effects)] // We need to subtract the offset and add one to get the next offset
+			pub(in crate::world) fn get_many_mut(&mut self, ids: &[EntityId]) -> Vec<(EntityId, &mut T)>
+			{
+				let mut dense_idxs: Vec<usize> = ids.iter()
+					.map(|&id| self.dense_idx(id).expect("Entity had no value"))
+					.collect();
+				dense_idxs.sort_unstable();
+				
+				// Split each value off from the ones after it
+				// Note: As the indices are sorted, we never need to go back.
+				let mut rest = &mut self.dense[..];
+				let mut offset = 0;
+				let mut values = Vec::with_capacity( dense_idxs.len() );
+				for idx in dense_idxs {
+					let (value, next) = std::mem::take(&mut rest)
+						.get_mut(idx - offset..)
+						.and_then(<[_]>::split_first_mut)
+						.expect("Entity appeared more than once");
+					rest = next;
+					offset = idx + 1;
+					
+					values.push( (self.entities[idx], value) );
+				}
+				
+				values
+			}
+			
+			/// Returns all values in this set
+			#[must_use]
+			pub fn values(&self) -> &[T] {
+				&self.dense
+			}
+			
+			/// Returns all entity ids in this set, in the same order as [`SparseSet::values`]
+			#[must_use]
+			pub fn entities(&self) -> &[EntityId] {
+				&self.entities
+			}
+			
+			/// Returns the number of values in this set
+			#[must_use]
+			pub const fn len(&self) -> usize {
+				self.dense.len()
+			}
+			
+			/// Checks if this set is empty
+			#[must_use]
+			pub const fn is_empty(&self) -> bool {
+				self.dense.is_empty()
+			}
+			
+			/// Returns the index into the dense array of an entity
+			#[must_use]
+			fn dense_idx(&self, id: EntityId) -> Option<usize>
+			{
+				self.sparse.get(id.idx())
+					.copied()
+					.flatten()
+					.filter(|&idx| self.entities[idx] == id)
+			}
+		//--------------------------------------------------------------------------------------------------
+		
+		// Checks
+		//--------------------------------------------------------------------------------------------------
+			/// Checks if an entity has a value in this set
+			#[must_use]
+			pub fn contains(&self, id: EntityId) -> bool {
+				self.dense_idx(id).is_some()
+			}
+		//--------------------------------------------------------------------------------------------------
+	}
+//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,147 @@
+//! Tests
+
+// Crate
+use crate as mecs;
+use mecs::{SparseWorld, Access};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	mecs::impl_enum_storage!{
+		
+		/// Dummy storage type
+		#[derive(PartialEq, Clone, Debug)]
+		enum Components {
+			A(i32),
+			B(&'static str),
+			C(f32),
+		}
+	
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Functions
+//--------------------------------------------------------------------------------------------------
+	#[test]
+	fn remove_keeps_sets_dense()
+	{
+		let mut world: SparseWorld<Components> = SparseWorld::default();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1), Components::B("1") ] );
+		let id2 = world.add( mecs::entity![ Components::A(2) ] );
+		let id3 = world.add( mecs::entity![ Components::A(3), Components::B("3") ] );
+		
+		let entity = world.remove(id1).expect("Entity wasn't in world");
+		assert_eq!(entity.get::<i32>(), Some(&1));
+		assert_eq!(entity.get::<&str>(), Some(&"1"));
+		assert!(world.get(id1).is_none());
+		assert!(world.remove(id1).is_none());
+		
+		let id = <i32 as mecs::Component<Components>>::id();
+		assert_eq!(world.set(&id).map(mecs::world::sparse::SparseSet::len), Some(2));
+		assert_eq!(world.get(id2).and_then(|entity| entity.get::<i32>().copied()), Some(2));
+		assert_eq!(world.get(id3).and_then(|entity| entity.get::<&str>().copied()), Some("3"));
+	}
+	
+	#[test]
+	fn pred_component_changes()
+	{
+		let mut world: SparseWorld<Components> = SparseWorld::default();
+		
+		let pred_id = world.add_pred(|entity| entity.has::<i32>() && !entity.has::<f32>());
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		let id2 = world.add( mecs::entity![ Components::B("2") ] );
+		assert_eq!(world.iter_pred(pred_id).map(Iterator::count), Some(1));
+		
+		assert_eq!(world.insert_component(id2, Components::A(2)), Ok(None));
+		assert_eq!(world.insert_component(id2, Components::A(3)), Ok( Some(Components::A(2)) ));
+		assert_eq!(world.iter_pred(pred_id).map(Iterator::count), Some(2));
+		
+		world.insert_component(id1, Components::C(1.0)).unwrap();
+		assert_eq!(world.iter_pred(pred_id).map(|mut iter| iter.next().map(|(_, id)| id)), Some(Some(id2)));
+		
+		world.remove_component::<f32>(id1);
+		world.remove_component::<i32>(id2);
+		
+		let mut iter = world.iter_pred_mut(pred_id).expect("Unknown predicate");
+		while let Some( (mut entity, id) ) = iter.next() {
+			assert_eq!(id, id1);
+			*entity.get_mut::<i32>().expect("Entity had no `i32`") += 10;
+		}
+		drop(iter);
+		assert_eq!(world.get(id1).and_then(|entity| entity.get::<i32>().copied()), Some(11));
+	}
+	
+	#[test]
+	fn remove_only_visits_own_sets()
+	{
+		let mut world: SparseWorld<Components> = SparseWorld::default();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1) ] );
+		let id2 = world.add( mecs::entity![ Components::A(2), Components::C(2.0) ] );
+		
+		assert_eq!(world.remove_component::<i32>(id2), Some( Components::A(2) ));
+		assert!(world.remove(id2).is_some());
+		
+		let float_id = <f32 as mecs::Component<Components>>::id();
+		let int_id = <i32 as mecs::Component<Components>>::id();
+		assert_eq!(world.set(&float_id).map(mecs::world::sparse::SparseSet::len), Some(0));
+		assert_eq!(world.set(&int_id).map(mecs::world::sparse::SparseSet::len), Some(1));
+		assert_eq!(world.get(id1).and_then(|entity| entity.get::<i32>().copied()), Some(1));
+	}
+	
+	#[test]
+	fn query_and_par_iter_pred_mut()
+	{
+		let mut world: SparseWorld<Components> = SparseWorld::default();
+		
+		for num in 0..10 {
+			world.add( mecs::entity![ Components::A(num) ] );
+			world.add( mecs::entity![ Components::A(num), Components::C(0.0) ] );
+		}
+		let pred_id = world.add_pred(|entity| entity.has::<i32>());
+		
+		assert!(world.par_iter_pred_mut(pred_id, 3, |mut entity, _| {
+			*entity.get_mut::<i32>().expect("Entity had no `i32`") += 1;
+		}));
+		
+		let sum: i32 = world.query::<(&i32, &mut f32)>()
+			.map(|(_, (&num, float))| { *float += 1.0; num })
+			.sum();
+		assert_eq!(sum, (1..=10).sum::<i32>());
+		
+		let sum: i32 = world.iter_all().map(|entity| *entity.get::<i32>().expect("Entity had no `i32`")).sum();
+		assert_eq!(sum, 2 * (1..=10).sum::<i32>());
+	}
+	
+	#[test]
+	fn views()
+	{
+		let mut world: SparseWorld<Components> = SparseWorld::default();
+		
+		let id1 = world.add( mecs::entity![ Components::A(1), Components::C(2.0) ] );
+		let id2 = world.add( mecs::entity![ Components::B("2"), Components::C(3.0) ] );
+		let id3 = world.add( mecs::entity![ Components::A(3) ] );
+		let _ = world.query_pred::<(&mut i32, &f32)>();
+		
+		// Both views share `f32`, while each writes it's own component
+		let nums = Access::new().write::<i32>().read::<f32>();
+		let names = Access::new().write::<&str>().read::<f32>();
+		let mut views = world.views(&[&nums, &names]);
+		let (lhs, rhs) = views.split_at_mut(1);
+		for (_, (num, scale)) in lhs[0].query::<(&mut i32, &f32)>() {
+			if *scale > 1.0 {
+				*num += 1;
+			}
+		}
+		for (_, (name, _)) in rhs[0].query::<(&mut &str, &f32)>() {
+			*name = "scaled";
+		}
+		assert_eq!(lhs[0].query::<&i32>().count(), 2);
+		drop(views);
+		
+		assert_eq!(world.get(id1).and_then(|entity| entity.get::<i32>().copied()), Some(2));
+		assert_eq!(world.get(id2).and_then(|entity| entity.get::<&str>().copied()), Some("scaled"));
+		assert_eq!(world.get(id3).and_then(|entity| entity.get::<i32>().copied()), Some(3));
+	}
+//--------------------------------------------------------------------------------------------------
@@ -0,0 +1,311 @@
+//! Views over a single entity of a sparse world
+
+// Collections
+use std::collections::HashMap;
+
+// Traits
+use std::iter::Iterator;
+
+// Crate
+use crate::{util::KeyType, Storage, Component, EntityId, Tick, ComponentTicks, component::ComponentMask};
+use crate::world::ReadEntity;
+use super::{SparseSet, ComponentIds};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A reference to an entity living within a sparse world
+	pub struct EntityRef<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// All component sets of the world
+		pub(in super) sets: &'w HashMap< S::Id, SparseSet<(S, ComponentTicks)> >,
+		
+		/// The component ids of the entity
+		pub(in super) component_ids: &'w ComponentIds<'a, S>,
+		
+		/// The id of the entity
+		pub(in super) id: EntityId,
+	}
+	
+	/// A mutable reference to an entity living within a sparse world
+	/// 
+	/// Components may only be modified through it, not added or removed.
+	pub struct EntityMut<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// The components of the entity
+		pub(in super) components: ComponentsMut<'a, 'w, S>,
+		
+		/// The id of the entity
+		pub(in super) id: EntityId,
+		
+		/// The tick to mark components with if they're changed
+		pub(in super) change_tick: Tick,
+	}
+	
+	/// Where the components of a mutable entity are borrowed from
+	pub(in super) enum ComponentsMut<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// All component sets of the world
+		Sets(&'w mut HashMap< S::Id, SparseSet<(S, ComponentTicks)> >),
+		
+		/// Each component of the entity, split off from it's set
+		Split(Vec<( &'w mut S, &'w mut ComponentTicks )>),
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, 'w, S> EntityRef<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// Returns the id of this entity
+		#[must_use]
+		pub const fn id(&self) -> EntityId {
+			self.id
+		}
+		
+		/// Returns a reference to a component given it's type
+		#[must_use]
+		pub fn get<C: Component<'a, S>>(&self) -> Option<&'w C>
+		{
+			self.get_id( &C::id() )
+				.and_then(C::get)
+		}
+		
+		/// Returns a reference to a component's storage given it's id
+		#[must_use]
+		pub fn get_id(&self, id: &S::Id) -> Option<&'w S> {
+			self.component(id)
+				.map(|(storage, _)| storage)
+		}
+		
+		/// Returns an iterator over all component ids in this entity
+		pub fn ids(&self) -> impl Iterator<Item = &'w S::Id> {
+			self.component_ids.ids.iter()
+		}
+		
+		/// Returns an iterator over all components in this entity
+		pub fn components(&self) -> impl Iterator<Item = &'w S> + use<'a, 'w, S>
+		{
+			let (sets, id) = (self.sets, self.id);
+			self.component_ids.ids.iter()
+				.filter_map(move |component_id| sets.get(component_id)?.get(id))
+				.map(|(storage, _)| storage)
+		}
+		
+		/// Checks if this entity has a component given it's type
+		#[must_use]
+		pub fn has<C: Component<'a, S>>(&self) -> bool {
+			self.has_id( &C::id() )
+		}
+		
+		/// Checks if this entity has a component given it's id
+		#[must_use]
+		pub fn has_id(&self, id: &S::Id) -> bool {
+			self.component(id).is_some()
+		}
+		
+		/// Returns the mask of all components in this entity
+		/// 
+		/// The mask is empty if the storage doesn't map it's ids to indices, see [`Storage::COUNT`].
+		#[must_use]
+		pub const fn mask(&self) -> &'w ComponentMask {
+			&self.component_ids.mask
+		}
+		
+		/// Returns the ticks of a component given it's type
+		#[must_use]
+		pub fn ticks<C: Component<'a, S>>(&self) -> Option<ComponentTicks> {
+			self.ticks_id( &C::id() )
+		}
+		
+		/// Returns the ticks of a component given it's id
+		#[must_use]
+		pub fn ticks_id(&self, id: &S::Id) -> Option<ComponentTicks> {
+			self.component(id)
+				.map(|&(_, ticks)| ticks)
+		}
+		
+		/// Checks if a component was added after the tick `since`, given it's type
+		/// 
+		/// Returns `false` if the component is not present.
+		#[must_use]
+		pub fn added<C: Component<'a, S>>(&self, since: Tick) -> bool {
+			self.ticks::<C>()
+				.is_some_and(|ticks| ticks.is_added(since))
+		}
+		
+		/// Checks if a component was added or mutably accessed after the tick `since`, given it's type
+		/// 
+		/// Returns `false` if the component is not present.
+		#[must_use]
+		pub fn changed<C: Component<'a, S>>(&self, since: Tick) -> bool {
+			self.ticks::<C>()
+				.is_some_and(|ticks| ticks.is_changed(since))
+		}
+		
+		/// Returns a component given it's id, along with it's ticks
+		fn component(&self, id: &S::Id) -> Option<&'w (S, ComponentTicks)> {
+			self.sets.get(id)?.get(self.id)
+		}
+	}
+	
+	impl<'a, S> EntityMut<'a, '_, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		/// Returns the id of this entity
+		#[must_use]
+		pub const fn id(&self) -> EntityId {
+			self.id
+		}
+		
+		/// Returns a reference to a component given it's type
+		#[must_use]
+		pub fn get<C: Component<'a, S>>(&self) -> Option<&C>
+		{
+			self.get_id( &C::id() )
+				.and_then(C::get)
+		}
+		
+		/// Returns a mutable reference to a component given it's type
+		/// 
+		/// This marks the component as changed.
+		#[must_use]
+		pub fn get_mut<C: Component<'a, S>>(&mut self) -> Option<&mut C>
+		{
+			self.get_mut_id( &C::id() )
+				.and_then(C::get_mut)
+		}
+		
+		/// Returns a reference to a component's storage given it's id
+		#[must_use]
+		pub fn get_id(&self, id: &S::Id) -> Option<&S> {
+			self.component(id)
+				.map(|(storage, _)| storage)
+		}
+		
+		/// Returns a mutable reference to a component's storage given it's id
+		/// 
+		/// This marks the component as changed.
+		#[must_use]
+		pub fn get_mut_id(&mut self, id: &S::Id) -> Option<&mut S>
+		{
+			let entity_id = self.id;
+			let (storage, ticks) = match &mut self.components {
+				ComponentsMut::Sets(sets) => {
+					let (storage, ticks) = sets.get_mut(id)?.get_mut(entity_id)?;
+					(storage, ticks)
+				},
+				ComponentsMut::Split(components) => {
+					let (storage, ticks) = components.iter_mut().find(|(storage, _)| storage.id() == *id)?;
+					(&mut **storage, &mut **ticks)
+				},
+			};
+			
+			ticks.changed = self.change_tick;
+			Some(storage)
+		}
+		
+		/// Checks if this entity has a component given it's type
+		#[must_use]
+		pub fn has<C: Component<'a, S>>(&self) -> bool {
+			self.has_id( &C::id() )
+		}
+		
+		/// Checks if this entity has a component given it's id
+		#[must_use]
+		pub fn has_id(&self, id: &S::Id) -> bool {
+			self.component(id).is_some()
+		}
+		
+		/// Returns the ticks of a component given it's type
+		#[must_use]
+		pub fn ticks<C: Component<'a, S>>(&self) -> Option<ComponentTicks> {
+			self.ticks_id( &C::id() )
+		}
+		
+		/// Returns the ticks of a component given it's id
+		#[must_use]
+		pub fn ticks_id(&self, id: &S::Id) -> Option<ComponentTicks> {
+			self.component(id)
+				.map(|(_, ticks)| ticks)
+		}
+		
+		/// Returns a component given it's id, along with it's ticks
+		fn component(&self, id: &S::Id) -> Option<(&S, ComponentTicks)>
+		{
+			match &self.components {
+				ComponentsMut::Sets(sets) => sets.get(id)?
+					.get(self.id)
+					.map(|(storage, ticks)| (storage, *ticks)),
+				ComponentsMut::Split(components) => components.iter()
+					.find(|(storage, _)| storage.id() == *id)
+					.map(|(storage, ticks)| (&**storage, **ticks)),
+			}
+		}
+	}
+	
+	impl<'a, S> Clone for EntityRef<'a, '_, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		fn clone(&self) -> Self {
+			*self
+		}
+	}
+	
+	impl<'a, S> Copy for EntityRef<'a, '_, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{}
+	
+	impl<'a, 'w, S> ReadEntity<'a, 'w, S> for EntityRef<'a, 'w, S>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+	{
+		fn get_id(self, id: &S::Id) -> Option<&'w S> {
+			Self::get_id(&self, id)
+		}
+		
+		fn has_id(self, id: &S::Id) -> bool {
+			Self::has_id(&self, id)
+		}
+		
+		fn ticks_id(self, id: &S::Id) -> Option<ComponentTicks> {
+			Self::ticks_id(&self, id)
+		}
+		
+		fn mask(self) -> &'w ComponentMask {
+			Self::mask(&self)
+		}
+		
+		fn ids(self) -> impl Iterator<Item = &'w S::Id>
+		where
+			S::Id: 'w,
+		{
+			Self::ids(&self)
+		}
+		
+		fn components(self) -> impl Iterator<Item = &'w S>
+		where
+			S: 'w,
+		{
+			Self::components(&self)
+		}
+	}
+//--------------------------------------------------------------------------------------------------
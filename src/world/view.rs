@@ -0,0 +1,219 @@
+//! Views over a world
+//! 
+//! A view borrows only the components of a world declared by
+//! an [`Access`], so that multiple views with non-conflicting
+//! accesses may be used at once, for example, from different threads.
+//! 
+//! # Example
+//! 
+//! ```rust
+//! # use mecs::{World, Access};
+//! mecs::impl_enum_storage! {
+//! 	enum Components {
+//! 		Num(i32),
+//! 		Scale(f32),
+//! 	}
+//! }
+//! 
+//! let mut world: World<Components> = World::new();
+//! let id = world.add( mecs::entity![ Components::Num(5), Components::Scale(2.0) ] );
+//! 
+//! let nums   = Access::new().write::<i32>();
+//! let scales = Access::new().write::<f32>();
+//! let mut views = world.views(&[&nums, &scales]);
+//! let (nums_view, scales_view) = views.split_at_mut(1);
+//! 
+//! std::thread::scope(|scope| {
+//! 	scope.spawn(|| for (_, num) in nums_view[0].query::<&mut i32>() {
+//! 		*num += 1;
+//! 	});
+//! 	scope.spawn(|| for (_, scale) in scales_view[0].query::<&mut f32>() {
+//! 		*scale *= 2.0;
+//! 	});
+//! });
+//! 
+//! assert_eq!(world[id].get::<i32>(), Some(&6));
+//! assert_eq!(world[id].get::<f32>(), Some(&4.0));
+//! ```
+
+// Marker
+use std::marker::PhantomData;
+
+// Any
+use std::any::Any;
+
+// Collections
+use std::collections::HashMap;
+
+// Crate
+use crate::{util::{KeyType, SortedKeyType}, Storage, EntityId, Tick, Access};
+use super::{World, Query, Resources, PredId, Predicates, Backend, BackendView, View, Fetch, Entities, query, sparse::SparseSet};
+
+// Types
+//--------------------------------------------------------------------------------------------------
+	/// A view over the components of a world declared by an access
+	pub struct WorldView<'w, 'a, S, B = Entities<'a, S>>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+	{
+		/// Our components, borrowed from the backend
+		view: BackendView<'a, 'w, S, B>,
+		
+		/// The access of this view
+		access: &'w Access<'a, S>,
+		
+		/// The current change tick of the world
+		change_tick: Tick,
+		
+		/// The resources of the world
+		resources: &'w Resources,
+		
+		/// The predicates of the world
+		predicates: &'w Predicates<'a, S, B>,
+		
+		/// The predicates of all queries of the world, stored by their required component ids
+		queries: &'w HashMap<Vec<S::Id>, PredId<S>>,
+	}
+	
+	/// Iterator over a query of a view
+	pub struct ViewQueryIter<'a, 'b, 'w, S, Q, B = Entities<'a, S>>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType,
+		B    : Backend<'a, S>,
+		BackendView<'a, 'w, S, B>: 'b,
+	{
+		/// The rows of the query, given by the view
+		rows: <BackendView<'a, 'w, S, B> as View<'a, 'w, S>>::Fetch<'b>,
+		
+		/// Marker for the query type
+		phantom: PhantomData<fn() -> Q>,
+	}
+//--------------------------------------------------------------------------------------------------
+
+// Impl
+//--------------------------------------------------------------------------------------------------
+	impl<'a, S, B> World<'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		/// Splits this world into views, one for each access
+		/// 
+		/// Each written component is given only to the view that
+		/// writes it, while read components are shared between all
+		/// views that read them. The components are borrowed from
+		/// the backend, see [`Backend::views`].
+		/// 
+		/// # Panics
+		/// Panics if any two accesses conflict, see [`Access::conflicts`].
+		pub fn views<'w>(&'w mut self, accesses: &[&'w Access<'a, S>]) -> Vec< WorldView<'w, 'a, S, B> >
+		{
+			for (idx, lhs) in accesses.iter().enumerate() {
+				for rhs in &accesses[idx..][1..] {
+					assert!(lhs.conflicts(rhs).is_empty(), "Views may not have conflicting accesses");
+				}
+			}
+			
+			let Self { entities, change_tick, resources, predicates, queries, .. } = self;
+			let (change_tick, resources, predicates, queries) = (*change_tick, &*resources, &*predicates, &*queries);
+			entities.views(accesses)
+				.into_iter()
+				.zip(accesses)
+				.map(|(view, &access)| WorldView { view, access, change_tick, resources, predicates, queries })
+				.collect()
+		}
+	}
+	
+	impl<'w, 'a, S, B> WorldView<'w, 'a, S, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+	{
+		/// Returns the access of this view
+		#[must_use]
+		pub const fn access(&self) -> &'w Access<'a, S> {
+			self.access
+		}
+		
+		/// Returns the change tick of the world
+		#[must_use]
+		pub const fn change_tick(&self) -> Tick {
+			self.change_tick
+		}
+		
+		/// Returns a reference to a resource of the world given it's type
+		#[must_use]
+		pub fn resource<R>(&self) -> Option<&'w R>
+		where
+			R: Any + Send + Sync
+		{
+			self.resources.get()
+		}
+		
+		/// Returns an iterator over a query of all entities in this view
+		/// 
+		/// As views can't register predicates, this only goes through the entities of
+		/// the query's predicate if the world already registered it, see [`World::query_pred`],
+		/// or else through every entity in this view.
+		/// 
+		/// # Panics
+		/// Panics if the query accesses the same component more than once,
+		/// reads a component not read by this view or writes a component
+		/// not written by this view.
+		pub fn query<'b, Q>(&'b mut self) -> ViewQueryIter<'a, 'b, 'w, S, Q, B>
+		where
+			Q    : Query<'a, 'b, S>,
+			S::Id: SortedKeyType,
+		{
+			query::check_access::<S, Q>();
+			
+			let mut ids = vec![];
+			Q::accessed(&mut ids);
+			assert!(ids.iter().all(|id| self.access.reads_id(id)), "Query read a component not read by the view");
+			
+			let mut ids = vec![];
+			Q::written(&mut ids);
+			assert!(ids.iter().all(|id| self.access.writes_id(id)), "Query wrote a component not written by the view");
+			
+			// Get the entities of the query's predicate, if it's registered
+			// Note: Query predicates don't depend on ticks, so we don't need to check them.
+			let mut required = vec![];
+			Q::required(&mut required);
+			required.sort_unstable();
+			required.dedup();
+			let ids = self.queries.get(&required)
+				.and_then(|&pred_id| self.predicates.ids(pred_id))
+				.map(SparseSet::entities);
+			
+			// Note: Views check for components added or changed in the current tick
+			let since = Tick::new( self.change_tick.get().wrapping_sub(1) );
+			ViewQueryIter {
+				rows: self.view.fetch(ids, self.change_tick, since),
+				phantom: PhantomData,
+			}
+		}
+	}
+	
+	impl<'a, 'b, S, Q, B> Iterator for ViewQueryIter<'a, 'b, '_, S, Q, B>
+	where
+		S    : Storage<'a>,
+		S::Id: KeyType + 'a,
+		B    : Backend<'a, S>,
+		Q    : Query<'a, 'b, S>,
+	{
+		type Item = (EntityId, Q);
+		
+		fn next(&mut self) -> Option< Self::Item > {
+			self.rows.next()
+		}
+		
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			self.rows.size_hint()
+		}
+	}
+//--------------------------------------------------------------------------------------------------